@@ -0,0 +1,217 @@
+//! Derive macros for the [`letta`](https://docs.rs/letta) crate.
+//!
+//! Enable the `derive` feature of `letta` rather than depending on this crate
+//! directly; the macros are re-exported from `letta::tool`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Field, Fields, LitStr, Result,
+};
+
+const MISSING_TOOL_DOC: &str =
+    "add a doc comment or `#[letta_tool(description = \"...\")]` to describe the tool";
+const MISSING_PARAM_DOC: &str =
+    "add a doc comment to describe this parameter; Letta rejects undocumented parameters";
+
+/// Derive `letta::tool::LettaTool` for a struct with named fields (or a unit
+/// struct, for tools without parameters).
+///
+/// The struct's doc comment is the tool description and each field's doc
+/// comment is the parameter description. Field types must implement
+/// `letta::tool::ToolParamType`; `Option<T>` fields are optional parameters.
+///
+/// Container attributes (`#[letta_tool(...)]` on the struct):
+/// - `name = "..."`: tool name (defaults to the struct name in `snake_case`)
+/// - `description = "..."`: overrides the doc comment
+/// - `returns = "..."`: documents a `str` return value
+///
+/// Field attributes:
+/// - `rename = "..."`: parameter name (defaults to the field name)
+/// - `default = <literal>`: Python default, making the parameter optional
+#[proc_macro_derive(LettaTool, attributes(letta_tool))]
+pub fn derive_letta_tool(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_letta_tool(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_letta_tool(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "LettaTool can only be derived for structs",
+        ));
+    };
+    let fields: Vec<&Field> = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "LettaTool requires named fields",
+            ))
+        }
+    };
+
+    let mut name = None;
+    let mut description = None;
+    let mut returns = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("letta_tool"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("description") {
+                description = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("returns") {
+                returns = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `name`, `description` or `returns`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let name = name.unwrap_or_else(|| to_snake_case(&input.ident.unraw().to_string()));
+    let Some(description) = description.or_else(|| doc_comment(&input.attrs)) else {
+        return Err(Error::new_spanned(&input.ident, MISSING_TOOL_DOC));
+    };
+
+    let mut params = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+
+        let mut rename = None;
+        let mut default: Option<Expr> = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("letta_tool"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    default = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `rename` or `default`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let param_name = rename.unwrap_or_else(|| ident.unraw().to_string());
+        let Some(param_description) = doc_comment(&field.attrs) else {
+            return Err(Error::new_spanned(ident, MISSING_PARAM_DOC));
+        };
+        let default = default.map(|expr| quote!(.default_value(#expr)));
+
+        params.push(quote! {
+            .param({
+                let ty = <#ty as ::letta::tool::ToolParamType>::param_type();
+                if <#ty as ::letta::tool::ToolParamType>::OPTIONAL {
+                    ::letta::tool::ToolParam::optional(#param_name, ty, #param_description)
+                } else {
+                    ::letta::tool::ToolParam::new(#param_name, ty, #param_description)
+                }
+                #default
+            })
+        });
+    }
+
+    let returns = returns.map(|desc| quote!(.returns(::letta::tool::ParamType::String, #desc)));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::letta::tool::LettaTool for #ident #ty_generics #where_clause {
+            fn tool_definition() -> ::letta::tool::ToolDefinition {
+                ::letta::tool::ToolDefinition::new(#name, #description)
+                    #(#params)*
+                    #returns
+            }
+        }
+    })
+}
+
+/// Collect `///` doc comment lines into a single string.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Convert a `CamelCase` identifier to `snake_case`.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            let boundary = prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(char::is_uppercase) && next.is_some_and(|n| n.is_lowercase()));
+            if boundary {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("RollDice"), "roll_dice");
+        assert_eq!(to_snake_case("HTTPRequest"), "http_request");
+        assert_eq!(to_snake_case("Search2Docs"), "search2_docs");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+    }
+
+    #[test]
+    fn test_expand_requires_docs() {
+        let input: DeriveInput = syn::parse_quote! {
+            /// Search.
+            struct Search {
+                query: String,
+            }
+        };
+        let err = expand_letta_tool(&input).unwrap_err();
+        assert!(err.to_string().contains("doc comment"));
+    }
+}
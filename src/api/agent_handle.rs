@@ -0,0 +1,577 @@
+//! Agent-scoped API handle.
+//!
+//! [`AgentHandle`] groups every operation that is addressed to a single agent
+//! (attached blocks, tools, sources, open files, identities, messages, archival
+//! memory and groups) behind one value, so callers don't need to remember which
+//! top-level API owns a given attach/detach/list endpoint.
+
+use crate::api::messages::MessageStream;
+use crate::api::{AgentFilesApi, AgentSourceApi};
+use crate::client::LettaClient;
+use crate::error::LettaResult;
+use crate::pagination::PaginatedStream;
+use crate::types::{
+    AgentState, ArchivalMemoryQueryParams, Block, CreateArchivalMemoryRequest,
+    CreateMessagesRequest, Group, Identity, LettaId, LettaMessageUnion, LettaResponse,
+    ListMessagesRequest, Memory, PaginationParams, Passage, Run, Tool, UpdateArchivalMemoryRequest,
+    UpdateIdentityRequest, UpdateMemoryBlockRequest, UpdateMessageRequest,
+};
+use futures::{stream, StreamExt, TryStreamExt};
+
+/// Handle for operations scoped to a single agent.
+///
+/// Obtain one with [`LettaClient::agent`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use letta::{LettaClient, LettaId};
+/// # use std::str::FromStr;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = LettaClient::local()?;
+/// let agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000")?;
+/// let tool_id = LettaId::from_str("tool-00000000-0000-0000-0000-000000000000")?;
+///
+/// let agent = client.agent(&agent_id);
+/// agent.tools().attach(&tool_id).await?;
+///
+/// for block in agent.blocks().list().await? {
+///     println!("{}: {}", block.label, block.value);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AgentHandle<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentHandle<'a> {
+    /// Create a new handle for the given agent.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// The ID of the agent this handle addresses.
+    pub fn id(&self) -> &LettaId {
+        &self.agent_id
+    }
+
+    /// Fetch the current state of the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn get(&self) -> LettaResult<AgentState> {
+        self.client.agents().get(&self.agent_id).await
+    }
+
+    /// Core memory blocks attached to the agent.
+    pub fn blocks(&self) -> AgentBlocksApi<'a> {
+        AgentBlocksApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Tools attached to the agent.
+    pub fn tools(&self) -> AgentToolsApi<'a> {
+        AgentToolsApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Sources attached to the agent.
+    pub fn sources(&self) -> AgentSourceApi<'a> {
+        AgentSourceApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Files from the agent's sources and their open/closed state.
+    pub fn files(&self) -> AgentFilesApi<'a> {
+        AgentFilesApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Identities associated with the agent.
+    pub fn identities(&self) -> AgentIdentitiesApi<'a> {
+        AgentIdentitiesApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Messages exchanged with the agent.
+    pub fn messages(&self) -> AgentMessagesApi<'a> {
+        AgentMessagesApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// The agent's archival memory.
+    pub fn archival(&self) -> AgentArchivalApi<'a> {
+        AgentArchivalApi::new(self.client, self.agent_id.clone())
+    }
+
+    /// Groups the agent belongs to.
+    pub fn groups(&self) -> AgentGroupsApi<'a> {
+        AgentGroupsApi::new(self.client, self.agent_id.clone())
+    }
+}
+
+/// Agent core memory block operations.
+#[derive(Debug)]
+pub struct AgentBlocksApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentBlocksApi<'a> {
+    /// Create a new agent blocks API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// Get the agent's full core memory, including file blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn memory(&self) -> LettaResult<Memory> {
+        self.client.memory().get_core_memory(&self.agent_id).await
+    }
+
+    /// List blocks attached to the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(&self) -> LettaResult<Vec<Block>> {
+        self.client
+            .memory()
+            .list_core_memory_blocks(&self.agent_id)
+            .await
+    }
+
+    /// Get an attached block by label.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn get(&self, label: &str) -> LettaResult<Block> {
+        self.client
+            .memory()
+            .get_core_memory_block(&self.agent_id, label)
+            .await
+    }
+
+    /// Update an attached block by label.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn update(
+        &self,
+        label: &str,
+        request: UpdateMemoryBlockRequest,
+    ) -> LettaResult<Block> {
+        self.client
+            .memory()
+            .update_core_memory_block(&self.agent_id, label, request)
+            .await
+    }
+
+    /// Attach an existing block to the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn attach(&self, block_id: &LettaId) -> LettaResult<AgentState> {
+        self.client
+            .memory()
+            .attach_memory_block(&self.agent_id, block_id)
+            .await
+    }
+
+    /// Detach a block from the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn detach(&self, block_id: &LettaId) -> LettaResult<AgentState> {
+        self.client
+            .memory()
+            .detach_memory_block(&self.agent_id, block_id)
+            .await
+    }
+}
+
+/// Agent tool operations.
+#[derive(Debug)]
+pub struct AgentToolsApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentToolsApi<'a> {
+    /// Create a new agent tools API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List tools attached to the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(&self) -> LettaResult<Vec<Tool>> {
+        self.client.memory().list_agent_tools(&self.agent_id).await
+    }
+
+    /// Attach a tool to the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn attach(&self, tool_id: &LettaId) -> LettaResult<AgentState> {
+        self.client
+            .memory()
+            .attach_tool_to_agent(&self.agent_id, tool_id)
+            .await
+    }
+
+    /// Detach a tool from the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn detach(&self, tool_id: &LettaId) -> LettaResult<AgentState> {
+        self.client
+            .memory()
+            .detach_tool_from_agent(&self.agent_id, tool_id)
+            .await
+    }
+}
+
+/// How many identities [`AgentIdentitiesApi::list`] fetches at once.
+const IDENTITY_FETCH_CONCURRENCY: usize = 4;
+
+/// Agent identity operations.
+///
+/// The server tracks the agent/identity relationship on the identity side, so
+/// attaching and detaching rewrite the identity's `agent_ids`.
+///
+/// # Concurrency
+///
+/// The server has no endpoint that adds or removes a single agent from an
+/// identity, so [`attach`](Self::attach) and [`detach`](Self::detach) read the
+/// identity, edit its `agent_ids` and write the whole list back. Two changes
+/// to the same identity made at the same time can overwrite each other, and
+/// one of them is lost. Serialize changes to a shared identity, or re-read it
+/// afterwards if that matters.
+#[derive(Debug)]
+pub struct AgentIdentitiesApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentIdentitiesApi<'a> {
+    /// Create a new agent identities API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List identities associated with the agent.
+    ///
+    /// The agent only records identity IDs, so this fetches each identity
+    /// separately: one request per identity, a few at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(&self) -> LettaResult<Vec<Identity>> {
+        let agent = self.client.agents().get(&self.agent_id).await?;
+        let identities = self.client.identities();
+        stream::iter(agent.identity_ids.unwrap_or_default())
+            .map(|identity_id| {
+                let identities = &identities;
+                async move { identities.get(&identity_id).await }
+            })
+            .buffered(IDENTITY_FETCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Associate an identity with the agent.
+    ///
+    /// Attaching an identity that is already associated is a no-op. This is a
+    /// read-modify-write of the identity; see the [type-level
+    /// notes](Self#concurrency) on concurrent changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn attach(&self, identity_id: &LettaId) -> LettaResult<Identity> {
+        let identity = self.client.identities().get(identity_id).await?;
+        let mut agent_ids = identity.agent_ids.clone().unwrap_or_default();
+        if agent_ids.contains(&self.agent_id) {
+            return Ok(identity);
+        }
+        agent_ids.push(self.agent_id.clone());
+        self.set_agent_ids(identity_id, agent_ids).await
+    }
+
+    /// Remove an identity's association with the agent.
+    ///
+    /// Detaching an identity that is not associated is a no-op. This is a
+    /// read-modify-write of the identity; see the [type-level
+    /// notes](Self#concurrency) on concurrent changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn detach(&self, identity_id: &LettaId) -> LettaResult<Identity> {
+        let identity = self.client.identities().get(identity_id).await?;
+        let mut agent_ids = identity.agent_ids.clone().unwrap_or_default();
+        if !agent_ids.contains(&self.agent_id) {
+            return Ok(identity);
+        }
+        agent_ids.retain(|id| id != &self.agent_id);
+        self.set_agent_ids(identity_id, agent_ids).await
+    }
+
+    async fn set_agent_ids(
+        &self,
+        identity_id: &LettaId,
+        agent_ids: Vec<LettaId>,
+    ) -> LettaResult<Identity> {
+        let update = UpdateIdentityRequest {
+            agent_ids: Some(agent_ids.iter().map(ToString::to_string).collect()),
+            ..Default::default()
+        };
+        self.client.identities().update(identity_id, update).await
+    }
+}
+
+/// Agent message operations.
+#[derive(Debug)]
+pub struct AgentMessagesApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentMessagesApi<'a> {
+    /// Create a new agent messages API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List messages for the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(
+        &self,
+        params: Option<ListMessagesRequest>,
+    ) -> LettaResult<Vec<LettaMessageUnion>> {
+        self.client.messages().list(&self.agent_id, params).await
+    }
+
+    /// Send messages to the agent and wait for the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn create(&self, request: CreateMessagesRequest) -> LettaResult<LettaResponse> {
+        self.client.messages().create(&self.agent_id, request).await
+    }
+
+    /// Send messages to the agent and stream the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn create_stream(
+        &self,
+        request: CreateMessagesRequest,
+        stream_tokens: bool,
+    ) -> LettaResult<MessageStream> {
+        self.client
+            .messages()
+            .create_stream(&self.agent_id, request, stream_tokens)
+            .await
+    }
+
+    /// Send messages to the agent for background processing.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn create_async(&self, request: CreateMessagesRequest) -> LettaResult<Run> {
+        self.client
+            .messages()
+            .create_async(&self.agent_id, request)
+            .await
+    }
+
+    /// Update one of the agent's messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn update(
+        &self,
+        message_id: &LettaId,
+        request: UpdateMessageRequest,
+    ) -> LettaResult<LettaMessageUnion> {
+        self.client
+            .messages()
+            .update(&self.agent_id, message_id, request)
+            .await
+    }
+
+    /// Reset the agent's message history.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn reset(
+        &self,
+        add_default_initial_messages: Option<bool>,
+    ) -> LettaResult<AgentState> {
+        self.client
+            .messages()
+            .reset(&self.agent_id, add_default_initial_messages)
+            .await
+    }
+
+    /// List the agent's messages with pagination support.
+    pub fn paginated(
+        &self,
+        params: Option<PaginationParams>,
+    ) -> PaginatedStream<LettaMessageUnion> {
+        self.client.messages().paginated(&self.agent_id, params)
+    }
+}
+
+/// Agent archival memory operations.
+#[derive(Debug)]
+pub struct AgentArchivalApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentArchivalApi<'a> {
+    /// Create a new agent archival memory API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List archival memory passages.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(
+        &self,
+        params: Option<ArchivalMemoryQueryParams>,
+    ) -> LettaResult<Vec<Passage>> {
+        self.client
+            .memory()
+            .list_archival_memory(&self.agent_id, params)
+            .await
+    }
+
+    /// Insert a passage into archival memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn create(&self, request: CreateArchivalMemoryRequest) -> LettaResult<Vec<Passage>> {
+        self.client
+            .memory()
+            .create_archival_memory(&self.agent_id, request)
+            .await
+    }
+
+    /// Update an archival memory passage.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn update(
+        &self,
+        memory_id: &LettaId,
+        request: UpdateArchivalMemoryRequest,
+    ) -> LettaResult<Vec<Passage>> {
+        self.client
+            .memory()
+            .update_archival_memory(&self.agent_id, memory_id, request)
+            .await
+    }
+
+    /// Delete an archival memory passage.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails.
+    pub async fn delete(&self, memory_id: &LettaId) -> LettaResult<serde_json::Value> {
+        self.client
+            .memory()
+            .delete_archival_memory(&self.agent_id, memory_id)
+            .await
+    }
+
+    /// List archival memory with pagination support.
+    pub fn paginated(&self, params: Option<PaginationParams>) -> PaginatedStream<Passage> {
+        self.client
+            .memory()
+            .archival_paginated(&self.agent_id, params)
+    }
+}
+
+/// Agent group membership operations.
+#[derive(Debug)]
+pub struct AgentGroupsApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentGroupsApi<'a> {
+    /// Create a new agent groups API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List groups the agent belongs to.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(&self) -> LettaResult<Vec<Group>> {
+        self.client.agents().list_groups(&self.agent_id).await
+    }
+}
+
+/// Convenience method for agent-scoped operations.
+impl LettaClient {
+    /// Get a handle for operations scoped to a single agent.
+    pub fn agent(&self, agent_id: &LettaId) -> AgentHandle<'_> {
+        AgentHandle::new(self, agent_id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ClientConfig;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_agent_handle_creation() {
+        let config = ClientConfig::new("http://localhost:8283").unwrap();
+        let client = LettaClient::new(config).unwrap();
+        let agent_id = LettaId::from_str("agent-550e8400-e29b-41d4-a716-446655440000").unwrap();
+
+        let handle = client.agent(&agent_id);
+        assert_eq!(handle.id(), &agent_id);
+
+        let _blocks = handle.blocks();
+        let _tools = handle.tools();
+        let _sources = handle.sources();
+        let _identities = handle.identities();
+        let _messages = handle.messages();
+        let _archival = handle.archival();
+        let _groups = handle.groups();
+    }
+}
@@ -4,8 +4,9 @@ use crate::client::LettaClient;
 use crate::error::LettaResult;
 use crate::pagination::{PaginatedStream, PaginationExt};
 use crate::types::{
-    AgentState, AgentsSearchRequest, AgentsSearchResponse, CreateAgentRequest, ImportAgentRequest,
-    LettaId, ListAgentsParams, PaginationParams,
+    AgentState, AgentsSearchRequest, AgentsSearchResponse, ContextUsage, ContextWindowOverview,
+    CreateAgentRequest, ImportAgentRequest, LettaId, ListAgentsParams, PaginationParams,
+    SummarizationOutcome, SummarizationPolicy,
 };
 use reqwest::multipart::{Form, Part};
 use std::path::Path;
@@ -136,6 +137,98 @@ impl<'a> AgentApi<'a> {
             .await
     }
 
+    /// Get a breakdown of what is in an agent's context window.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn get_context(&self, agent_id: &LettaId) -> LettaResult<ContextWindowOverview> {
+        self.client
+            .get(&format!("v1/agents/{}/context", agent_id))
+            .await
+    }
+
+    /// Measure how full an agent's context window is.
+    ///
+    /// Usage is measured against the agent's [`LLMConfig::context_window`](crate::types::agent::LLMConfig::context_window),
+    /// falling back to the size reported by the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn context_usage(&self, agent_id: &LettaId) -> LettaResult<ContextUsage> {
+        let agent = self.get(agent_id).await?;
+        let overview = self.get_context(agent_id).await?;
+        let context_window = agent.llm_config.and_then(|config| config.context_window);
+        Ok(ContextUsage::new(&overview, context_window))
+    }
+
+    /// Summarize an agent's conversation down to `target_messages` messages.
+    ///
+    /// Does nothing if the agent already has no more than `target_messages`
+    /// messages in context. Returns the context usage afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent whose conversation to summarize
+    /// * `target_messages` - Number of messages to keep in context
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails or if a response cannot be parsed.
+    pub async fn summarize_to(
+        &self,
+        agent_id: &LettaId,
+        target_messages: u32,
+    ) -> LettaResult<ContextUsage> {
+        let usage = self.context_usage(agent_id).await?;
+        if usage.message_count <= target_messages {
+            return Ok(usage);
+        }
+        self.summarize_agent_conversation(agent_id, target_messages)
+            .await?;
+        self.context_usage(agent_id).await
+    }
+
+    /// Summarize an agent's conversation if `policy` calls for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent
+    /// * `policy` - When to summarize, and how many messages to keep
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails or if a response cannot be parsed.
+    pub async fn apply_summarization_policy(
+        &self,
+        agent_id: &LettaId,
+        policy: &SummarizationPolicy,
+    ) -> LettaResult<SummarizationOutcome> {
+        let before = self.context_usage(agent_id).await?;
+        if !policy.should_summarize(&before) {
+            return Ok(SummarizationOutcome {
+                before,
+                after: None,
+            });
+        }
+        self.summarize_agent_conversation(agent_id, policy.target_messages)
+            .await?;
+        let after = self.context_usage(agent_id).await?;
+        Ok(SummarizationOutcome {
+            before,
+            after: Some(after),
+        })
+    }
+
     /// Get the count of all agents associated with a given user.
     ///
     /// # Errors
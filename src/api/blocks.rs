@@ -1,8 +1,54 @@
 //! Memory blocks API endpoints.
 
 use crate::client::LettaClient;
-use crate::error::LettaResult;
-use crate::types::{Block, CreateBlockRequest, LettaId, ListBlocksParams, UpdateBlockRequest};
+use crate::error::{LettaError, LettaResult};
+use crate::types::{
+    AgentState, Block, BlockFormat, BlockPrecondition, BulkReport, CreateBlockRequest, LettaId,
+    ListAgentsParams, ListBlocksParams, TypedBlock, UpdateBlockRequest,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+
+/// How many times [`BlocksApi::modify`] and
+/// [`MemoryApi::modify_core_memory_block`](crate::api::MemoryApi::modify_core_memory_block)
+/// try before giving up on a block that keeps changing.
+const MODIFY_ATTEMPTS: u32 = 5;
+
+/// Read-modify-write loop behind the block `modify` helpers.
+///
+/// `f` computes the new value from `block`, and `write` sends it on the
+/// condition that the block is unchanged. On a conflict `f` is called again
+/// with the block the server returned, up to [`MODIFY_ATTEMPTS`] times.
+pub(crate) async fn modify_block<F, W, Fut>(
+    mut block: Block,
+    mut f: F,
+    mut write: W,
+) -> LettaResult<Block>
+where
+    F: FnMut(&Block) -> LettaResult<String>,
+    W: FnMut(BlockPrecondition, String) -> Fut,
+    Fut: Future<Output = LettaResult<Block>>,
+{
+    let mut attempt = 1;
+    loop {
+        let value = f(&block)?;
+        if value == block.value {
+            return Ok(block);
+        }
+        block.check_limit(&value)?;
+        match write(BlockPrecondition::of(&block), value).await {
+            Err(LettaError::Conflict {
+                current: Some(current),
+                ..
+            }) if attempt < MODIFY_ATTEMPTS => {
+                block = *current;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
 
 /// Memory blocks API operations.
 #[derive(Debug)]
@@ -77,6 +123,171 @@ impl<'a> BlocksApi<'a> {
             .await
     }
 
+    /// Update a memory block only if it has not changed since it was read.
+    ///
+    /// The check happens client-side just before the update is sent, so an
+    /// edit landing between the check and the update can still be
+    /// overwritten; see [`BlockPrecondition`].
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to update
+    /// * `precondition` - What the block must still look like, e.g. [`BlockPrecondition::of`] the block as read
+    /// * `request` - The update request
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::Conflict`] with the current block if it no longer
+    /// matches `precondition`, or a [crate::error::LettaError] if a request
+    /// fails or a response cannot be parsed.
+    pub async fn update_if(
+        &self,
+        block_id: &LettaId,
+        precondition: &BlockPrecondition,
+        request: UpdateBlockRequest,
+    ) -> LettaResult<Block> {
+        precondition.check(self.get(block_id).await?)?;
+        self.update(block_id, request).await
+    }
+
+    /// Rewrite a memory block's value, retrying if it changes underneath.
+    ///
+    /// `f` is given the current value and returns the new one. If the block
+    /// changes between reading it and writing it back, `f` is called again
+    /// with the newer value, up to five times in all. Returning the value
+    /// unchanged skips the update, and a value over the block's `limit` is
+    /// rejected before it is sent. Each write goes through
+    /// [`update_if`](Self::update_if), with the same caveat about edits
+    /// landing between the check and the update.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use letta::{ClientConfig, LettaClient};
+    /// # use letta::types::LettaId;
+    /// # async fn example(client: LettaClient, block_id: LettaId) -> letta::LettaResult<()> {
+    /// client
+    ///     .blocks()
+    ///     .modify(&block_id, |value| format!("{}\nPrefers metric units.", value))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to modify
+    /// * `f` - Computes the new value from the current one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::Conflict`] if the block is still changing after
+    /// the last attempt, a validation error if the new value is over the
+    /// block's limit, or a [crate::error::LettaError] if a request fails or a
+    /// response cannot be parsed.
+    pub async fn modify<F>(&self, block_id: &LettaId, mut f: F) -> LettaResult<Block>
+    where
+        F: FnMut(&str) -> String,
+    {
+        self.try_modify(block_id, |block| Ok(f(&block.value))).await
+    }
+
+    /// [`modify`](Self::modify) with a fallible `f`, which is given the whole block.
+    async fn try_modify<F>(&self, block_id: &LettaId, f: F) -> LettaResult<Block>
+    where
+        F: FnMut(&Block) -> LettaResult<String>,
+    {
+        let block = self.get(block_id).await?;
+        modify_block(block, f, |precondition, value| async move {
+            let request = UpdateBlockRequest {
+                value: Some(value),
+                ..Default::default()
+            };
+            self.update_if(block_id, &precondition, request).await
+        })
+        .await
+    }
+
+    /// Get a memory block and parse its value as `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to retrieve
+    /// * `format` - The format the block's value is written in
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the
+    /// block's value cannot be parsed as `T`.
+    pub async fn get_typed<T: Serialize + DeserializeOwned>(
+        &self,
+        block_id: &LettaId,
+        format: BlockFormat,
+    ) -> LettaResult<TypedBlock<T>> {
+        TypedBlock::parse(self.get(block_id).await?, format)
+    }
+
+    /// Write a typed block's value back to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The typed block, which must have an ID
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the block has no ID, the
+    /// request fails, or the response cannot be parsed as `T`.
+    pub async fn update_typed<T: Serialize + DeserializeOwned>(
+        &self,
+        block: &TypedBlock<T>,
+    ) -> LettaResult<TypedBlock<T>> {
+        let block_id = block
+            .block()
+            .id
+            .as_ref()
+            .ok_or_else(|| LettaError::validation_field("block has no ID", "id"))?;
+        let request = UpdateBlockRequest {
+            value: Some(block.block().value.clone()),
+            ..Default::default()
+        };
+        TypedBlock::parse(self.update(block_id, request).await?, block.format())
+    }
+
+    /// Change a memory block's typed value, retrying if it changes underneath.
+    ///
+    /// Like [`modify`](Self::modify), but `f` edits the parsed value in
+    /// place.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to modify
+    /// * `format` - The format the block's value is written in
+    /// * `f` - Edits the current value
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] as [`modify`](Self::modify) does,
+    /// or if the block's value cannot be parsed as `T`.
+    pub async fn modify_typed<T, F>(
+        &self,
+        block_id: &LettaId,
+        format: BlockFormat,
+        mut f: F,
+    ) -> LettaResult<TypedBlock<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnMut(&mut T),
+    {
+        let block = self
+            .try_modify(block_id, |block| {
+                let mut data: T = format.parse(&block.value)?;
+                f(&mut data);
+                format.render(&data)
+            })
+            .await?;
+        TypedBlock::parse(block, format)
+    }
+
     /// Delete a memory block.
     ///
     /// # Arguments
@@ -92,6 +303,123 @@ impl<'a> BlocksApi<'a> {
             .await
     }
 
+    /// List the agents a memory block is attached to.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_agents(&self, block_id: &LettaId) -> LettaResult<Vec<AgentState>> {
+        self.client
+            .get(&format!("v1/blocks/{}/agents", block_id))
+            .await
+    }
+
+    /// Attach a memory block to many agents.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use letta::LettaClient;
+    /// # use letta::api::AgentSelection;
+    /// # use letta::types::LettaId;
+    /// # async fn example(client: LettaClient, block_id: LettaId) -> letta::LettaResult<()> {
+    /// let report = client
+    ///     .blocks()
+    ///     .attach_to_agents(&block_id, AgentSelection::any_tag(["support"]))
+    ///     .await?;
+    /// for failure in &report.failed {
+    ///     eprintln!("{}: {}", failure.item, failure.error);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to attach
+    /// * `agents` - The agents to attach it to, by ID or by tag
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if agents selected by tag cannot be
+    /// listed. Agents that fail to update are recorded in the [`BulkReport`].
+    pub async fn attach_to_agents(
+        &self,
+        block_id: &LettaId,
+        agents: impl Into<AgentSelection>,
+    ) -> LettaResult<BlockAgentsReport> {
+        let mut report = BlockAgentsReport::default();
+        for agent_id in self.resolve(agents.into()).await? {
+            let result = self
+                .client
+                .memory()
+                .attach_memory_block(&agent_id, block_id)
+                .await;
+            report.record(agent_id.to_string(), result.map(|_| agent_id));
+        }
+        Ok(report)
+    }
+
+    /// Detach a memory block from many agents.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_id` - The ID of the block to detach
+    /// * `agents` - The agents to detach it from, by ID or by tag
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if agents selected by tag cannot be
+    /// listed. Agents that fail to update are recorded in the [`BulkReport`].
+    pub async fn detach_from_agents(
+        &self,
+        block_id: &LettaId,
+        agents: impl Into<AgentSelection>,
+    ) -> LettaResult<BlockAgentsReport> {
+        let mut report = BlockAgentsReport::default();
+        for agent_id in self.resolve(agents.into()).await? {
+            let result = self
+                .client
+                .memory()
+                .detach_memory_block(&agent_id, block_id)
+                .await;
+            report.record(agent_id.to_string(), result.map(|_| agent_id));
+        }
+        Ok(report)
+    }
+
+    /// The IDs of the selected agents, listing every page of a tag filter.
+    async fn resolve(&self, selection: AgentSelection) -> LettaResult<Vec<LettaId>> {
+        let (tags, match_all) = match selection {
+            AgentSelection::Ids(ids) => return Ok(ids),
+            AgentSelection::Tags { tags, match_all } => (tags, match_all),
+        };
+
+        const PAGE_SIZE: u32 = 100;
+        let mut ids = Vec::new();
+        let mut after = None;
+        loop {
+            let params = ListAgentsParams {
+                tags: Some(tags.clone()),
+                match_all_tags: Some(match_all),
+                after,
+                limit: Some(PAGE_SIZE),
+                ..Default::default()
+            };
+            let page = self.client.agents().list(Some(params)).await?;
+            let done = page.len() < PAGE_SIZE as usize;
+            after = page.last().map(|agent| agent.id.to_string());
+            ids.extend(page.into_iter().map(|agent| agent.id));
+            if done || after.is_none() {
+                return Ok(ids);
+            }
+        }
+    }
+
     /// Get the count of memory blocks.
     ///
     /// # Errors
@@ -102,10 +430,59 @@ impl<'a> BlocksApi<'a> {
     }
 }
 
+/// Which agents a bulk block operation applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentSelection {
+    /// These agents.
+    Ids(Vec<LettaId>),
+    /// Agents with the given tags.
+    Tags {
+        /// Tags to filter by.
+        tags: Vec<String>,
+        /// Whether agents must have all of the tags, rather than any.
+        match_all: bool,
+    },
+}
+
+impl AgentSelection {
+    /// Agents with any of the given tags.
+    pub fn any_tag<S: Into<String>>(tags: impl IntoIterator<Item = S>) -> Self {
+        Self::Tags {
+            tags: tags.into_iter().map(Into::into).collect(),
+            match_all: false,
+        }
+    }
+
+    /// Agents with all of the given tags.
+    pub fn all_tags<S: Into<String>>(tags: impl IntoIterator<Item = S>) -> Self {
+        Self::Tags {
+            tags: tags.into_iter().map(Into::into).collect(),
+            match_all: true,
+        }
+    }
+}
+
+impl From<Vec<LettaId>> for AgentSelection {
+    fn from(ids: Vec<LettaId>) -> Self {
+        Self::Ids(ids)
+    }
+}
+
+impl From<&[LettaId]> for AgentSelection {
+    fn from(ids: &[LettaId]) -> Self {
+        Self::Ids(ids.to_vec())
+    }
+}
+
+/// Outcome of attaching a block to, or detaching it from, many agents:
+/// the agents that were updated and those that failed.
+pub type BlockAgentsReport = BulkReport<LettaId>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::client::ClientConfig;
+    use std::str::FromStr;
 
     #[test]
     fn test_blocks_api_creation() {
@@ -113,4 +490,28 @@ mod tests {
         let client = LettaClient::new(config).unwrap();
         let _api = BlocksApi::new(&client);
     }
+
+    #[test]
+    fn test_agent_selection() {
+        assert_eq!(
+            AgentSelection::any_tag(["support", "billing"]),
+            AgentSelection::Tags {
+                tags: vec!["support".to_string(), "billing".to_string()],
+                match_all: false,
+            }
+        );
+        assert_eq!(
+            AgentSelection::all_tags(vec!["support".to_string()]),
+            AgentSelection::Tags {
+                tags: vec!["support".to_string()],
+                match_all: true,
+            }
+        );
+
+        let ids = vec![LettaId::from_str("agent-00000000-0000-0000-0000-000000000001").unwrap()];
+        assert_eq!(
+            AgentSelection::from(ids.as_slice()),
+            AgentSelection::Ids(ids)
+        );
+    }
 }
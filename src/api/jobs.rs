@@ -2,7 +2,15 @@
 
 use crate::client::LettaClient;
 use crate::error::LettaResult;
-use crate::types::{Job, JobStatus, LettaId, Step, StepFeedback};
+use crate::pagination::PaginatedStream;
+use crate::types::{
+    Job, JobStatus, LettaId, ListJobsParams, ListStepsParams, ModelPricing, PaginationParams, Step,
+    StepFeedback, UsageReport,
+};
+use std::collections::HashMap;
+
+/// Page size used when scanning all jobs for one agent.
+const AGENT_JOBS_PAGE_SIZE: i32 = 100;
 
 /// Job API operations.
 #[derive(Debug)]
@@ -20,66 +28,108 @@ impl<'a> JobApi<'a> {
     ///
     /// # Arguments
     ///
-    /// * `status` - Optional status filter
-    /// * `limit` - Maximum number of jobs to return
-    /// * `source_id` - Optional source ID to filter jobs
+    /// * `params` - Optional query parameters for filtering and pagination. The
+    ///   agent, status, type and date filters are applied client-side to the one
+    ///   page the server returns; use [`paginated`](Self::paginated) to see every
+    ///   matching job.
     ///
     /// # Errors
     ///
-    ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn list(
-        &self,
-        status: Option<JobStatus>,
-        limit: Option<i32>,
-        source_id: Option<&LettaId>,
-    ) -> LettaResult<Vec<Job>> {
-        let mut params = Vec::new();
-        if let Some(s) = status {
-            params.push(("status", serde_json::to_string(&s)?));
-        }
-        if let Some(l) = limit {
-            params.push(("limit", l.to_string()));
-        }
-        if let Some(sid) = source_id {
-            params.push(("source_id", sid.to_string()));
-        }
-
-        if params.is_empty() {
-            self.client.get("v1/jobs").await
-        } else {
-            self.client.get_with_query("v1/jobs", &params).await
-        }
+    pub async fn list(&self, params: Option<ListJobsParams>) -> LettaResult<Vec<Job>> {
+        self.list_from("v1/jobs/", params.unwrap_or_default()).await
     }
 
     /// List active jobs.
     ///
     /// # Arguments
     ///
-    /// * `status` - Optional status filter
-    /// * `limit` - Maximum number of jobs to return
+    /// * `params` - Optional query parameters for filtering and pagination. The
+    ///   agent, status, type and date filters are applied client-side.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_active(&self, params: Option<ListJobsParams>) -> LettaResult<Vec<Job>> {
+        self.list_from("v1/jobs/active", params.unwrap_or_default())
+            .await
+    }
+
+    /// List jobs created for a specific agent.
+    ///
+    /// The server cannot filter jobs by agent, so this pages through every job
+    /// and keeps the agent's ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent whose jobs to list
+    /// * `statuses` - Optional statuses to restrict the listing to
     ///
     /// # Errors
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn list_active(
+    pub async fn list_for_agent(
         &self,
-        status: Option<JobStatus>,
-        limit: Option<i32>,
+        agent_id: &LettaId,
+        statuses: Option<Vec<JobStatus>>,
     ) -> LettaResult<Vec<Job>> {
-        let mut params = Vec::new();
-        if let Some(s) = status {
-            params.push(("status", serde_json::to_string(&s)?));
-        }
-        if let Some(l) = limit {
-            params.push(("limit", l.to_string()));
-        }
+        self.paginated(Some(ListJobsParams {
+            agent_id: Some(agent_id.clone()),
+            statuses,
+            limit: Some(AGENT_JOBS_PAGE_SIZE),
+            ..Default::default()
+        }))
+        .collect()
+        .await
+    }
+
+    /// List jobs with pagination support.
+    ///
+    /// Returns a stream that automatically fetches subsequent pages as needed.
+    /// Filters other than the pagination fields are kept for every page. The
+    /// client-side filters are applied to the streamed jobs, so the stream
+    /// keeps paging past jobs that don't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters for filtering and pagination
+    pub fn paginated(&self, params: Option<ListJobsParams>) -> PaginatedStream<Job> {
+        let client = self.client.clone();
+        let base_params = params.unwrap_or_default();
+        let filter = base_params.clone();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit.map(|l| l as u32),
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_string_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit.map(|l| l as i32);
+                }
 
-        if params.is_empty() {
-            self.client.get("v1/jobs/active").await
-        } else {
-            self.client.get_with_query("v1/jobs/active", &params).await
-        }
+                async move {
+                    client
+                        .get_with_query::<Vec<Job>, _>("v1/jobs/", &effective_params)
+                        .await
+                }
+            },
+            |job| job.id.as_ref().map(ToString::to_string).unwrap_or_default(),
+        )
+        .retain(move |job| filter.matches(job))
+    }
+
+    async fn list_from(&self, path: &str, params: ListJobsParams) -> LettaResult<Vec<Job>> {
+        let jobs: Vec<Job> = self.client.get_with_query(path, &params).await?;
+        Ok(jobs.into_iter().filter(|job| params.matches(job)).collect())
     }
 
     /// Get a specific job.
@@ -95,11 +145,29 @@ impl<'a> JobApi<'a> {
         self.client.get(&format!("v1/jobs/{}", job_id)).await
     }
 
-    /// Delete/cancel a job.
+    /// Cancel a running job.
+    ///
+    /// Cancellation is cooperative: the returned job reflects the state at the
+    /// time of the request and may still be `running` briefly.
+    ///
+    /// # Arguments
+    ///
+    /// * `job_id` - The ID of the job to cancel
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn cancel(&self, job_id: &LettaId) -> LettaResult<Job> {
+        self.client
+            .patch_no_body(&format!("v1/jobs/{}/cancel", job_id))
+            .await
+    }
+
+    /// Delete a job.
     ///
     /// # Arguments
     ///
-    /// * `job_id` - The ID of the job to delete/cancel
+    /// * `job_id` - The ID of the job to delete
     ///
     /// # Errors
     ///
@@ -130,15 +198,90 @@ impl<'a> StepApi<'a> {
     /// # Errors
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn list(
-        &self,
-        params: Option<crate::types::ListStepsParams>,
-    ) -> LettaResult<Vec<Step>> {
+    pub async fn list(&self, params: Option<ListStepsParams>) -> LettaResult<Vec<Step>> {
         self.client
             .get_with_query("v1/steps/", &params.unwrap_or_default())
             .await
     }
 
+    /// List steps with pagination support.
+    ///
+    /// Returns a stream that automatically fetches subsequent pages as needed.
+    /// Filters other than the pagination fields are kept for every page.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters for filtering and pagination
+    pub fn paginated(&self, params: Option<ListStepsParams>) -> PaginatedStream<Step> {
+        let client = self.client.clone();
+        let base_params = params.unwrap_or_default();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit.map(|l| l as u32),
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_id_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit.map(|l| l as i32);
+                }
+
+                async move { client.steps().list(Some(effective_params)).await }
+            },
+            |step| &step.id,
+        )
+    }
+
+    /// Build a usage and cost report over all steps matching the filters.
+    ///
+    /// Fetches every matching page, so narrow the query with `agent_id`,
+    /// `model` or a date range for large accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters selecting the steps to include
+    /// * `pricing` - Per-model pricing used to compute costs
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if any page request fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use letta::types::{ListStepsParams, ModelPricing};
+    /// # use std::collections::HashMap;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = letta::LettaClient::local()?;
+    /// let mut pricing = HashMap::new();
+    /// pricing.insert("gpt-4o-mini".to_string(), ModelPricing::per_million(0.15, 0.60));
+    ///
+    /// let report = client
+    ///     .steps()
+    ///     .usage(Some(ListStepsParams { model: Some("gpt-4o-mini".into()), ..Default::default() }), &pricing)
+    ///     .await?;
+    /// println!("{} steps, {} tokens, cost {:.4}",
+    ///     report.totals.step_count, report.totals.total_tokens, report.totals.cost);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn usage(
+        &self,
+        params: Option<ListStepsParams>,
+        pricing: &HashMap<String, ModelPricing>,
+    ) -> LettaResult<UsageReport> {
+        let steps = self.paginated(params).collect().await?;
+        Ok(UsageReport::from_steps(&steps, pricing))
+    }
+
     /// Get a specific step.
     ///
     /// # Arguments
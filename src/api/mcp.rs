@@ -0,0 +1,323 @@
+//! MCP (Model Context Protocol) server API endpoints.
+
+use crate::client::LettaClient;
+use crate::error::{ErrorBody, LettaError, LettaResult};
+use crate::types::tool::{
+    McpConnectEvent, McpServerConfig, McpTestResult, McpTool, TestMcpServerRequest, Tool,
+    UpdateMcpServerRequest,
+};
+use crate::types::BulkReport;
+use crate::utils::encode_path_segment;
+use eventsource_stream::Eventsource;
+use futures::{Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::pin::Pin;
+
+/// Stream of progress events while connecting to an MCP server.
+pub type McpConnectStream = Pin<Box<dyn Stream<Item = LettaResult<McpConnectEvent>> + Send>>;
+
+/// MCP server API operations.
+///
+/// Servers registered here make their tools available to import as Letta
+/// tools of type `external_mcp`.
+#[derive(Debug)]
+pub struct McpApi<'a> {
+    client: &'a LettaClient,
+}
+
+impl<'a> McpApi<'a> {
+    /// Create a new MCP API instance.
+    pub fn new(client: &'a LettaClient) -> Self {
+        Self { client }
+    }
+
+    /// List the configured MCP servers, keyed by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_servers(&self) -> LettaResult<HashMap<String, McpServerConfig>> {
+        self.client.get("v1/tools/mcp/servers").await
+    }
+
+    /// Add an MCP server.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The server configuration, e.g. a [`SseServerConfig`](crate::types::SseServerConfig)
+    ///
+    /// # Returns
+    ///
+    /// Returns the configurations of all servers.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn add_server(
+        &self,
+        config: impl Into<McpServerConfig>,
+    ) -> LettaResult<Vec<McpServerConfig>> {
+        self.client
+            .put("v1/tools/mcp/servers", &config.into())
+            .await
+    }
+
+    /// Update an MCP server's URL or token.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server to update
+    /// * `request` - The fields to change
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn update_server(
+        &self,
+        server_name: &str,
+        request: UpdateMcpServerRequest,
+    ) -> LettaResult<McpServerConfig> {
+        self.client
+            .patch(
+                &format!("v1/tools/mcp/servers/{}", encode_path_segment(server_name)),
+                &request,
+            )
+            .await
+    }
+
+    /// Remove an MCP server.
+    ///
+    /// Tools already imported from the server are not deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server to remove
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails.
+    pub async fn delete_server(&self, server_name: &str) -> LettaResult<()> {
+        self.client
+            .delete_no_response(&format!(
+                "v1/tools/mcp/servers/{}",
+                encode_path_segment(server_name)
+            ))
+            .await
+    }
+
+    /// List the tools a configured server offers.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_tools(&self, server_name: &str) -> LettaResult<Vec<McpTool>> {
+        self.client
+            .get(&format!(
+                "v1/tools/mcp/servers/{}/tools",
+                encode_path_segment(server_name)
+            ))
+            .await
+    }
+
+    /// Import one tool from a configured server as a Letta tool.
+    ///
+    /// Importing a tool again updates it.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server
+    /// * `tool_name` - The name of the MCP tool
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn import_tool(&self, server_name: &str, tool_name: &str) -> LettaResult<Tool> {
+        self.client
+            .post(
+                &format!(
+                    "v1/tools/mcp/servers/{}/{}",
+                    encode_path_segment(server_name),
+                    encode_path_segment(tool_name)
+                ),
+                &serde_json::json!({}),
+            )
+            .await
+    }
+
+    /// Import every tool a configured server offers.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the server's tools cannot be
+    /// listed. Tools that fail to import are recorded in the report instead.
+    pub async fn import_all(&self, server_name: &str) -> LettaResult<McpImportReport> {
+        let tools = self.list_tools(server_name).await?;
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_str()).collect();
+        Ok(self.import_tools(server_name, &names).await)
+    }
+
+    /// Import the named tools from a configured server.
+    ///
+    /// Tools that fail to import are recorded in the [`BulkReport`].
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - The name of the MCP server
+    /// * `tool_names` - The MCP tools to import
+    pub async fn import_tools<S: AsRef<str>>(
+        &self,
+        server_name: &str,
+        tool_names: &[S],
+    ) -> McpImportReport {
+        let mut report = McpImportReport::default();
+        for tool_name in tool_names {
+            let tool_name = tool_name.as_ref();
+            report.record(tool_name, self.import_tool(server_name, tool_name).await);
+        }
+        report
+    }
+
+    /// Test connecting to a server without adding it.
+    ///
+    /// When Letta reports that it could not connect to the MCP server, the
+    /// result is a failed [`McpTestResult`] rather than an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The server configuration to test
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] for any other failure, such as the
+    /// Letta server being unreachable, rejecting the credentials or the
+    /// configuration, or the response not parsing.
+    pub async fn test(&self, config: impl Into<McpServerConfig>) -> LettaResult<McpTestResult> {
+        let request = TestMcpServerRequest {
+            config: config.into(),
+        };
+        let result: LettaResult<McpTestResult> = self
+            .client
+            .post("v1/tools/mcp/servers/test", &request)
+            .await;
+        match result {
+            Err(e) => match connection_failure(&e) {
+                Some(message) => Ok(McpTestResult::failed(message)),
+                None => Err(e),
+            },
+            result => result,
+        }
+    }
+
+    /// Connect to a server, following its OAuth flow if it requires one.
+    ///
+    /// The stream reports each stage of the connection. When the server
+    /// requires OAuth, an [`AuthorizationUrl`](crate::types::McpConnectEventType::AuthorizationUrl)
+    /// event carries the URL to open; the stream then waits for the user to
+    /// authorize and ends with a `Success` or `Error` event. Add the server
+    /// with [`add_server`](Self::add_server) once it has connected.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The server configuration to connect to
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails. Errors while
+    /// reading the stream are yielded as stream items.
+    pub async fn connect(
+        &self,
+        config: impl Into<McpServerConfig>,
+    ) -> LettaResult<McpConnectStream> {
+        let url = self
+            .client
+            .base_url()
+            .join("v1/tools/mcp/servers/connect")?;
+
+        // Build headers
+        let mut headers = HeaderMap::new();
+        self.client.auth().apply_to_headers(&mut headers)?;
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        headers.insert("Accept", "text/event-stream".parse().unwrap());
+
+        let response = self
+            .client
+            .http()
+            .post(url)
+            .headers(headers)
+            .json(&config.into())
+            .send()
+            .await?;
+
+        // Check for HTTP errors
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await?;
+            return Err(LettaError::from_response(status, body));
+        }
+
+        let stream = response
+            .bytes_stream()
+            .eventsource()
+            .filter_map(|result| async move {
+                match result {
+                    Ok(event) => {
+                        // Skip events without data
+                        if event.data.is_empty() || event.data == "[DONE]" {
+                            return None;
+                        }
+                        Some(
+                            serde_json::from_str::<McpConnectEvent>(&event.data)
+                                .map_err(Into::into),
+                        )
+                    }
+                    Err(e) => Some(Err(LettaError::streaming(format!(
+                        "SSE stream error: {}",
+                        e
+                    )))),
+                }
+            });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Error codes the test endpoint uses when it cannot connect to the MCP server.
+const CONNECTION_ERROR_CODES: [&str; 2] = ["MCPServerConnectionError", "MCPTimeoutError"];
+
+/// The reason given by the test endpoint if `error` means the MCP server
+/// could not be connected to.
+///
+/// Letta reports these as `{"detail": {"code": ..., "message": ...}}`.
+fn connection_failure(error: &LettaError) -> Option<String> {
+    let LettaError::Api {
+        body: ErrorBody::Json(json),
+        ..
+    } = error
+    else {
+        return None;
+    };
+    let detail = json.get("detail")?;
+    let code = detail.get("code")?.as_str()?;
+    if !CONNECTION_ERROR_CODES.contains(&code) {
+        return None;
+    }
+    Some(
+        detail
+            .get("message")
+            .and_then(|message| message.as_str())
+            .map_or_else(|| error.to_string(), String::from),
+    )
+}
+
+/// Outcome of importing tools from an MCP server: the imported tools and
+/// the tool names that failed.
+pub type McpImportReport = BulkReport<Tool>;
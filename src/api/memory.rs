@@ -1,15 +1,21 @@
 //! Memory API endpoints.
 
+use crate::api::blocks::modify_block;
 use crate::client::LettaClient;
-use crate::error::LettaResult;
+use crate::error::{LettaError, LettaResult};
 use crate::pagination::PaginatedStream;
 use crate::types::{
     memory::{
-        ArchivalMemoryQueryParams, Block, CreateArchivalMemoryRequest, Memory, Passage,
-        UpdateArchivalMemoryRequest, UpdateMemoryBlockRequest,
+        ArchivalMemoryQueryParams, Block, BlockPrecondition, CreateArchivalMemoryRequest, Memory,
+        Passage, RecallMemoryEntry, RecallMemorySummary, UpdateArchivalMemoryRequest,
+        UpdateMemoryBlockRequest,
     },
-    LettaId, PaginationParams,
+    message::ListMessagesRequest,
+    BlockFormat, LettaId, PaginationParams, TypedBlock,
 };
+use crate::utils::encode_path_segment;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 /// Memory API operations.
 #[derive(Debug)]
@@ -56,6 +62,157 @@ impl<'a> MemoryApi<'a> {
         self.client.patch(&url, &request).await
     }
 
+    /// Update a core memory block only if it has not changed since it was read.
+    ///
+    /// The check happens client-side just before the update is sent, so an
+    /// edit landing between the check and the update can still be
+    /// overwritten; see [`BlockPrecondition`].
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent
+    /// * `block_label` - The label of the block to update
+    /// * `precondition` - What the block must still look like
+    /// * `request` - The update to apply
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::Conflict`] with the current block if it no longer
+    /// matches `precondition`, or a [crate::error::LettaError] if a request
+    /// fails or a response cannot be parsed.
+    pub async fn update_core_memory_block_if(
+        &self,
+        agent_id: &LettaId,
+        block_label: &str,
+        precondition: &BlockPrecondition,
+        request: UpdateMemoryBlockRequest,
+    ) -> LettaResult<Block> {
+        precondition.check(self.get_core_memory_block(agent_id, block_label).await?)?;
+        self.update_core_memory_block(agent_id, block_label, request)
+            .await
+    }
+
+    /// Rewrite a core memory block's value, retrying if it changes underneath.
+    ///
+    /// `f` is given the current value and returns the new one; see
+    /// [`BlocksApi::modify`](crate::api::BlocksApi::modify). Each write goes
+    /// through [`update_core_memory_block_if`](Self::update_core_memory_block_if),
+    /// with the same caveat about edits landing between the check and the
+    /// update.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::Conflict`] if the block is still changing after
+    /// the last attempt, a validation error if the new value is over the
+    /// block's limit, or a [crate::error::LettaError] if a request fails or a
+    /// response cannot be parsed.
+    pub async fn modify_core_memory_block<F>(
+        &self,
+        agent_id: &LettaId,
+        block_label: &str,
+        mut f: F,
+    ) -> LettaResult<Block>
+    where
+        F: FnMut(&str) -> String,
+    {
+        self.try_modify_core_memory_block(agent_id, block_label, |block| Ok(f(&block.value)))
+            .await
+    }
+
+    /// [`modify_core_memory_block`](Self::modify_core_memory_block) with a
+    /// fallible `f`, which is given the whole block.
+    async fn try_modify_core_memory_block<F>(
+        &self,
+        agent_id: &LettaId,
+        block_label: &str,
+        f: F,
+    ) -> LettaResult<Block>
+    where
+        F: FnMut(&Block) -> LettaResult<String>,
+    {
+        let block = self.get_core_memory_block(agent_id, block_label).await?;
+        modify_block(block, f, |precondition, value| async move {
+            let request = UpdateMemoryBlockRequest {
+                value: Some(value),
+                ..Default::default()
+            };
+            self.update_core_memory_block_if(agent_id, block_label, &precondition, request)
+                .await
+        })
+        .await
+    }
+
+    /// Get a core memory block and parse its value as `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the
+    /// block's value cannot be parsed as `T`.
+    pub async fn get_typed_core_memory_block<T: Serialize + DeserializeOwned>(
+        &self,
+        agent_id: &LettaId,
+        block_label: &str,
+        format: BlockFormat,
+    ) -> LettaResult<TypedBlock<T>> {
+        TypedBlock::parse(
+            self.get_core_memory_block(agent_id, block_label).await?,
+            format,
+        )
+    }
+
+    /// Write a typed block's value back to the agent's block with its label.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or the
+    /// response cannot be parsed as `T`.
+    pub async fn update_typed_core_memory_block<T: Serialize + DeserializeOwned>(
+        &self,
+        agent_id: &LettaId,
+        block: &TypedBlock<T>,
+    ) -> LettaResult<TypedBlock<T>> {
+        let request = UpdateMemoryBlockRequest {
+            value: Some(block.block().value.clone()),
+            ..Default::default()
+        };
+        let updated = self
+            .update_core_memory_block(agent_id, &block.block().label, request)
+            .await?;
+        TypedBlock::parse(updated, block.format())
+    }
+
+    /// Change a core memory block's typed value, retrying if it changes
+    /// underneath.
+    ///
+    /// `f` edits the parsed value in place; see
+    /// [`BlocksApi::modify_typed`](crate::api::BlocksApi::modify_typed).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] as
+    /// [`modify_core_memory_block`](Self::modify_core_memory_block) does, or
+    /// if the block's value cannot be parsed as `T`.
+    pub async fn modify_typed_core_memory_block<T, F>(
+        &self,
+        agent_id: &LettaId,
+        block_label: &str,
+        format: BlockFormat,
+        mut f: F,
+    ) -> LettaResult<TypedBlock<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnMut(&mut T),
+    {
+        let block = self
+            .try_modify_core_memory_block(agent_id, block_label, |block| {
+                let mut data: T = format.parse(&block.value)?;
+                f(&mut data);
+                format.render(&data)
+            })
+            .await?;
+        TypedBlock::parse(block, format)
+    }
+
     /// Attach a memory block to an agent.
     pub async fn attach_memory_block(
         &self,
@@ -85,20 +242,38 @@ impl<'a> MemoryApi<'a> {
     // Archival Memory API
 
     /// List archival memory passages for an agent.
+    ///
+    /// Tag, date and metadata filters in `params` are applied to the page
+    /// returned by the server; use
+    /// [`archival_query_paginated`](Self::archival_query_paginated) to filter
+    /// across pages.
     pub async fn list_archival_memory(
         &self,
         agent_id: &LettaId,
         params: Option<ArchivalMemoryQueryParams>,
+    ) -> LettaResult<Vec<Passage>> {
+        let params = params.unwrap_or_default();
+        let passages = self.list_archival_page(agent_id, &params).await?;
+        Ok(passages
+            .into_iter()
+            .filter(|passage| params.matches(passage))
+            .collect())
+    }
+
+    /// One page of archival memory, without the client-side filters.
+    async fn list_archival_page(
+        &self,
+        agent_id: &LettaId,
+        params: &ArchivalMemoryQueryParams,
     ) -> LettaResult<Vec<Passage>> {
         let url = format!("/v1/agents/{}/archival-memory", agent_id);
-        if let Some(params) = params {
-            let query = serde_urlencoded::to_string(&params)?;
-            if !query.is_empty() {
-                let url_with_query = format!("{}?{}", url, query);
-                return self.client.get(&url_with_query).await;
-            }
+        let query = serde_urlencoded::to_string(params)?;
+        if query.is_empty() {
+            self.client.get(&url).await
+        } else {
+            let url_with_query = format!("{}?{}", url, query);
+            self.client.get(&url_with_query).await
         }
-        self.client.get(&url).await
     }
 
     /// Create a new archival memory passage.
@@ -122,6 +297,27 @@ impl<'a> MemoryApi<'a> {
         self.client.patch(&url, &request).await
     }
 
+    /// Replace the tags of an archival memory passage.
+    ///
+    /// The server needs the passage's text and embedding on every update, so
+    /// this takes the passage as listed rather than its ID.
+    pub async fn set_archival_memory_tags(
+        &self,
+        agent_id: &LettaId,
+        passage: &Passage,
+        tags: Vec<String>,
+    ) -> LettaResult<Vec<Passage>> {
+        let mut request = UpdateArchivalMemoryRequest::from_passage(passage).ok_or_else(|| {
+            LettaError::validation_field(
+                "passage was listed without its embedding and cannot be updated",
+                "embedding",
+            )
+        })?;
+        request.tags = Some(tags);
+        self.update_archival_memory(agent_id, &passage.id, request)
+            .await
+    }
+
     /// Delete an archival memory passage.
     pub async fn delete_archival_memory(
         &self,
@@ -163,6 +359,75 @@ impl<'a> MemoryApi<'a> {
         self.client.patch_no_body(&url).await
     }
 
+    /// Set whether an agent must get approval before running a tool.
+    ///
+    /// Calls to such a tool stop the agent with
+    /// [`StopReasonType::RequiresApproval`](crate::types::StopReasonType::RequiresApproval)
+    /// instead of running it on the server, which is how client-executed
+    /// tools are implemented (see [`crate::tool::client`]).
+    pub async fn set_tool_requires_approval(
+        &self,
+        agent_id: &LettaId,
+        tool_name: &str,
+        requires_approval: bool,
+    ) -> LettaResult<crate::types::agent::AgentState> {
+        let url = format!(
+            "/v1/agents/{}/tools/approval/{}?requires_approval={}",
+            agent_id,
+            encode_path_segment(tool_name),
+            requires_approval
+        );
+        self.client.patch_no_body(&url).await
+    }
+
+    // Recall Memory API
+
+    /// List an agent's recall memory (message history) as flat entries.
+    pub async fn list_recall_memory(
+        &self,
+        agent_id: &LettaId,
+        params: Option<ListMessagesRequest>,
+    ) -> LettaResult<Vec<RecallMemoryEntry>> {
+        let messages = self.client.messages().list(agent_id, params).await?;
+        Ok(messages
+            .iter()
+            .map(|message| RecallMemoryEntry::from_message(agent_id, message))
+            .collect())
+    }
+
+    /// Get an agent's core memory with up to `limit` archival passages and
+    /// recall memory entries.
+    pub async fn get_recall_memory_summary(
+        &self,
+        agent_id: &LettaId,
+        limit: u32,
+    ) -> LettaResult<RecallMemorySummary> {
+        let core_memory = self.get_core_memory(agent_id).await?;
+        let archival_memory = self
+            .list_archival_memory(
+                agent_id,
+                Some(ArchivalMemoryQueryParams {
+                    limit: Some(limit),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        let recall_memory = self
+            .list_recall_memory(
+                agent_id,
+                Some(ListMessagesRequest {
+                    limit: Some(i32::try_from(limit).unwrap_or(i32::MAX)),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        Ok(RecallMemorySummary {
+            core_memory: Some(core_memory),
+            archival_memory,
+            recall_memory,
+        })
+    }
+
     /// List archival memory with pagination support.
     ///
     /// Returns a stream that automatically fetches subsequent pages as needed.
@@ -231,12 +496,86 @@ impl<'a> MemoryApi<'a> {
                 async move {
                     client
                         .memory()
-                        .list_archival_memory(&agent_id, Some(effective_params))
+                        .list_archival_page(&agent_id, &effective_params)
+                        .await
+                }
+            },
+            |passage| &passage.id,
+        )
+    }
+
+    /// Search or filter archival memory with pagination support.
+    ///
+    /// The search text is sent with every page request, and the tag, date and
+    /// metadata filters are applied to each passage as it arrives, so pages
+    /// with no matches don't end the stream early.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent whose archival memory to list
+    /// * `params` - Optional search, filter and pagination parameters
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use letta::{LettaClient, LettaId};
+    /// # use letta::types::ArchivalMemoryQueryParams;
+    /// # async fn example(client: &LettaClient, agent_id: &LettaId) -> letta::LettaResult<()> {
+    /// let tagged = client
+    ///     .memory()
+    ///     .archival_query_paginated(
+    ///         agent_id,
+    ///         Some(ArchivalMemoryQueryParams {
+    ///             tags: Some(vec!["meeting".to_string()]),
+    ///             limit: Some(100),
+    ///             ..Default::default()
+    ///         }),
+    ///     )
+    ///     .collect()
+    ///     .await?;
+    /// println!("{} meeting notes", tagged.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn archival_query_paginated(
+        &self,
+        agent_id: &LettaId,
+        params: Option<ArchivalMemoryQueryParams>,
+    ) -> PaginatedStream<Passage> {
+        let client = self.client.clone();
+        let agent_id = agent_id.clone();
+        let base_params = params.unwrap_or_default();
+        let filter = base_params.clone();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit,
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_id_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let agent_id = agent_id.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit;
+                }
+
+                async move {
+                    client
+                        .memory()
+                        .list_archival_page(&agent_id, &effective_params)
                         .await
                 }
             },
             |passage| &passage.id,
         )
+        .retain(move |passage| filter.matches(passage))
     }
 }
 
@@ -3,9 +3,12 @@
 use crate::client::LettaClient;
 use crate::error::{LettaError, LettaResult};
 use crate::pagination::PaginatedStream;
+use crate::tool::client::{pending_tool_calls, PendingToolCalls};
+use crate::tool::ClientToolRegistry;
 use crate::types::{
-    CreateMessagesRequest, LettaId, LettaMessageUnion, LettaResponse, LettaStopReason,
-    LettaUsageStatistics, ListMessagesRequest, PaginationParams,
+    ApprovalCreate, CreateMessagesRequest, LettaId, LettaMessageUnion, LettaResponse,
+    LettaStopReason, LettaUsageStatistics, ListMessagesRequest, PaginationParams, StopReasonType,
+    ToolCall,
 };
 use eventsource_stream::Eventsource;
 use futures::stream::{Stream, StreamExt};
@@ -173,6 +176,16 @@ impl<'a> MessageApi<'a> {
         agent_id: &LettaId,
         request: CreateMessagesRequest,
         stream_tokens: bool,
+    ) -> LettaResult<MessageStream> {
+        self.stream_body(agent_id, &request, stream_tokens).await
+    }
+
+    /// POST `body` to the streaming endpoint and parse the SSE events.
+    async fn stream_body<B: Serialize + Sync + ?Sized>(
+        &self,
+        agent_id: &LettaId,
+        body: &B,
+        stream_tokens: bool,
     ) -> LettaResult<MessageStream> {
         // Build the URL with streaming endpoint
         let url = self
@@ -199,7 +212,7 @@ impl<'a> MessageApi<'a> {
             .http()
             .post(url)
             .headers(headers)
-            .json(&request)
+            .json(body)
             .send()
             .await?;
 
@@ -298,6 +311,113 @@ impl<'a> MessageApi<'a> {
             .await
     }
 
+    /// Send messages to an agent, executing client-side tool calls locally.
+    ///
+    /// Whenever the agent stops with [`StopReasonType::RequiresApproval`], the
+    /// pending tool calls are run in order with the handlers in `tools`, and
+    /// their results are sent back as an [`ApprovalCreate`]. This repeats
+    /// until the agent stops for another reason, or the registry's round limit
+    /// is reached, in which case the agent is left waiting.
+    ///
+    /// If any pending call is for a tool without a handler in `tools`, such as
+    /// a server tool that needs a person's approval, nothing is answered: the
+    /// response is returned with [`StopReasonType::RequiresApproval`], and
+    /// [`pending_tool_calls`] lists the calls
+    /// still waiting.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent to send messages to
+    /// * `request` - The message creation request; its options are reused for every round trip
+    /// * `tools` - Handlers for the tools executed by the client
+    ///
+    /// # Returns
+    ///
+    /// A [`LettaResponse`] with the messages of every round trip, the final
+    /// stop reason and the combined usage.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails or if a response cannot be parsed.
+    /// Tool handler failures are reported to the agent instead.
+    pub async fn create_with_client_tools(
+        &self,
+        agent_id: &LettaId,
+        request: CreateMessagesRequest,
+        tools: &ClientToolRegistry,
+    ) -> LettaResult<LettaResponse> {
+        let mut response = self.create(agent_id, request.clone()).await?;
+        let mut pending = pending_tool_calls(&response.messages, tools);
+
+        let mut rounds = 0;
+        while response.stop_reason.stop_reason == StopReasonType::RequiresApproval
+            && !pending.is_empty()
+            && tools.handles_all(&pending)
+            && rounds < tools.round_limit()
+        {
+            rounds += 1;
+            let body = client_tool_returns(&request, tools, &pending).await?;
+            let next: LettaResponse = self
+                .client
+                .post(&format!("v1/agents/{}/messages", agent_id), &body)
+                .await?;
+
+            pending = pending_tool_calls(&next.messages, tools);
+            response.messages.extend(next.messages);
+            response.stop_reason = next.stop_reason;
+            add_usage(&mut response.usage, next.usage);
+        }
+
+        Ok(response)
+    }
+
+    /// Stream messages from an agent, executing client-side tool calls locally.
+    ///
+    /// The streaming counterpart of
+    /// [`create_with_client_tools`](Self::create_with_client_tools): when a
+    /// stream ends with [`StopReasonType::RequiresApproval`], the pending tool
+    /// calls are executed and a new stream is opened with their results. The
+    /// events of all streams are yielded in order. As there, the stream ends
+    /// without answering if a pending call is for a tool without a handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent to send messages to
+    /// * `request` - The message creation request; its options are reused for every round trip
+    /// * `stream_tokens` - Whether to stream individual tokens (true) or complete messages (false)
+    /// * `tools` - Handlers for the tools executed by the client
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the first request fails. Errors
+    /// from later round trips are yielded by the stream, which then ends.
+    pub async fn create_stream_with_client_tools(
+        &self,
+        agent_id: &LettaId,
+        request: CreateMessagesRequest,
+        stream_tokens: bool,
+        tools: ClientToolRegistry,
+    ) -> LettaResult<MessageStream> {
+        let current = self.stream_body(agent_id, &request, stream_tokens).await?;
+        let state = ClientToolStream {
+            client: self.client.clone(),
+            agent_id: agent_id.clone(),
+            request,
+            stream_tokens,
+            tools,
+            current,
+            pending: PendingToolCalls::new(stream_tokens),
+            stop_reason: None,
+            rounds: 0,
+            done: false,
+        };
+
+        Ok(Box::pin(futures::stream::unfold(
+            state,
+            ClientToolStream::next_event,
+        )))
+    }
+
     /// List messages with pagination support.
     ///
     /// Returns a stream that automatically fetches subsequent pages as needed.
@@ -378,11 +498,125 @@ impl<'a> MessageApi<'a> {
                 LettaMessageUnion::HiddenReasoningMessage(msg) => &msg.id,
                 LettaMessageUnion::ToolCallMessage(msg) => &msg.id,
                 LettaMessageUnion::ToolReturnMessage(msg) => &msg.id,
+                LettaMessageUnion::ApprovalRequestMessage(msg) => &msg.id,
             },
         )
     }
 }
 
+/// State of a [`MessageApi::create_stream_with_client_tools`] stream.
+struct ClientToolStream {
+    client: LettaClient,
+    agent_id: LettaId,
+    request: CreateMessagesRequest,
+    stream_tokens: bool,
+    tools: ClientToolRegistry,
+    current: MessageStream,
+    pending: PendingToolCalls,
+    stop_reason: Option<StopReasonType>,
+    rounds: usize,
+    done: bool,
+}
+
+impl ClientToolStream {
+    async fn next_event(mut self) -> Option<(LettaResult<StreamingEvent>, Self)> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            match self.current.next().await {
+                Some(Ok(event)) => {
+                    match &event {
+                        StreamingEvent::Message(message) => {
+                            self.pending.observe(message, &self.tools)
+                        }
+                        StreamingEvent::StopReason(reason) => {
+                            self.stop_reason = Some(reason.stop_reason)
+                        }
+                        StreamingEvent::Usage(_) => {}
+                    }
+                    return Some((Ok(event), self));
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some((Err(e), self));
+                }
+                None => {
+                    let pending = self.pending.take();
+                    let waiting = self.stop_reason.take() == Some(StopReasonType::RequiresApproval);
+                    if !waiting
+                        || pending.is_empty()
+                        || !self.tools.handles_all(&pending)
+                        || self.rounds >= self.tools.round_limit()
+                    {
+                        return None;
+                    }
+
+                    self.rounds += 1;
+                    let next = match client_tool_returns(&self.request, &self.tools, &pending).await
+                    {
+                        Ok(body) => {
+                            self.client
+                                .messages()
+                                .stream_body(&self.agent_id, &body, self.stream_tokens)
+                                .await
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match next {
+                        Ok(stream) => self.current = stream,
+                        Err(e) => {
+                            self.done = true;
+                            return Some((Err(e), self));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Execute `pending` and build a follow-up request carrying the results,
+/// keeping the original request's options.
+async fn client_tool_returns(
+    request: &CreateMessagesRequest,
+    tools: &ClientToolRegistry,
+    pending: &[ToolCall],
+) -> LettaResult<serde_json::Value> {
+    let mut approvals = Vec::with_capacity(pending.len());
+    for call in pending {
+        approvals.push(tools.execute(call).await);
+    }
+
+    let mut body = serde_json::to_value(request)?;
+    body["messages"] = serde_json::to_value([ApprovalCreate { approvals }])?;
+    Ok(body)
+}
+
+/// Add the usage of a follow-up response to the running total.
+fn add_usage(total: &mut LettaUsageStatistics, usage: LettaUsageStatistics) {
+    fn add(total: &mut Option<i32>, value: Option<i32>) {
+        if let Some(value) = value {
+            *total = Some(total.unwrap_or(0) + value);
+        }
+    }
+
+    add(&mut total.completion_tokens, usage.completion_tokens);
+    add(&mut total.prompt_tokens, usage.prompt_tokens);
+    add(&mut total.total_tokens, usage.total_tokens);
+    add(&mut total.step_count, usage.step_count);
+    if let Some(steps) = usage.steps_messages {
+        total
+            .steps_messages
+            .get_or_insert_with(Vec::new)
+            .extend(steps);
+    }
+    if let Some(run_ids) = usage.run_ids {
+        total.run_ids.get_or_insert_with(Vec::new).extend(run_ids);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,4 +628,37 @@ mod tests {
         let client = LettaClient::new(config).unwrap();
         let _api = MessageApi::new(&client);
     }
+
+    #[tokio::test]
+    async fn test_client_tool_returns() {
+        let tools =
+            ClientToolRegistry::new().register("lookup", |_| async { Ok::<_, String>("found") });
+        let request = CreateMessagesRequest {
+            messages: vec![crate::types::MessageCreate::user("Look it up")],
+            max_steps: Some(3),
+            ..Default::default()
+        };
+        let pending = vec![ToolCall {
+            name: "lookup".to_string(),
+            arguments: "{}".to_string(),
+            tool_call_id: "call-1".to_string(),
+        }];
+
+        let body = client_tool_returns(&request, &tools, &pending)
+            .await
+            .unwrap();
+        assert_eq!(body["max_steps"], 3);
+        assert_eq!(
+            body["messages"],
+            serde_json::json!([{
+                "type": "approval",
+                "approvals": [{
+                    "type": "tool",
+                    "tool_call_id": "call-1",
+                    "tool_return": "found",
+                    "status": "success"
+                }]
+            }])
+        );
+    }
 }
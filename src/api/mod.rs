@@ -3,6 +3,7 @@
 //! This module contains the implementation of all Letta API endpoints,
 //! organized by functional area.
 
+pub mod agent_handle;
 pub mod agents;
 pub mod batch;
 pub mod blocks;
@@ -10,6 +11,7 @@ pub mod groups;
 pub mod health;
 pub mod identities;
 pub mod jobs;
+pub mod mcp;
 pub mod memory;
 pub mod messages;
 pub mod misc;
@@ -17,6 +19,7 @@ pub mod models;
 pub mod projects;
 pub mod providers;
 pub mod runs;
+pub mod sleeptime;
 pub mod sources;
 pub mod tags;
 pub mod telemetry;
@@ -25,22 +28,28 @@ pub mod tools;
 pub mod voice;
 
 // Re-export API structs
+pub use agent_handle::{
+    AgentArchivalApi, AgentBlocksApi, AgentGroupsApi, AgentHandle, AgentIdentitiesApi,
+    AgentMessagesApi, AgentToolsApi,
+};
 pub use agents::AgentApi;
 pub use batch::BatchApi;
-pub use blocks::BlocksApi;
+pub use blocks::{AgentSelection, BlockAgentsReport, BlocksApi};
 pub use groups::GroupApi;
 pub use health::HealthApi;
 pub use identities::IdentitiesApi;
 pub use jobs::{JobApi, StepApi};
+pub use mcp::{McpApi, McpConnectStream, McpImportReport};
 pub use memory::MemoryApi;
 pub use messages::MessageApi;
 pub use models::ModelsApi;
 pub use projects::ProjectApi;
 pub use providers::ProvidersApi;
 pub use runs::RunApi;
-pub use sources::{AgentSourceApi, SourceApi};
+pub use sleeptime::SleeptimeApi;
+pub use sources::{AgentFilesApi, AgentSourceApi, SourceApi};
 pub use tags::TagsApi;
 pub use telemetry::TelemetryApi;
 pub use templates::TemplateApi;
 pub use tools::ToolApi;
-pub use voice::VoiceApi;
+pub use voice::{VoiceApi, VoiceChatCompletionStream};
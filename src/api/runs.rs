@@ -2,7 +2,11 @@
 
 use crate::client::LettaClient;
 use crate::error::LettaResult;
-use crate::types::{LettaId, LettaMessageUnion, Run, Step};
+use crate::pagination::PaginatedStream;
+use crate::types::{
+    LettaId, LettaMessageUnion, LettaUsageStatistics, ListRunMessagesParams, ListRunStepsParams,
+    ListRunsParams, PaginationParams, Run, Step,
+};
 
 /// Run API operations.
 #[derive(Debug)]
@@ -16,22 +20,90 @@ impl<'a> RunApi<'a> {
         Self { client }
     }
 
-    /// List all runs.
+    /// List runs.
     ///
     /// # Arguments
     ///
-    /// * `agent_ids` - The agent IDs associated with the run
+    /// * `params` - Optional query parameters for filtering and pagination. The
+    ///   status and date filters are applied client-side to the one page the
+    ///   server returns, so a filtered result may hold fewer than `limit` runs
+    ///   even when more match; use [`paginated`](Self::paginated) to see them
+    ///   all.
     ///
     /// # Errors
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn list(&self, agent_ids: &[LettaId]) -> LettaResult<Vec<Run>> {
-        let id_list = agent_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>();
+    pub async fn list(&self, params: Option<ListRunsParams>) -> LettaResult<Vec<Run>> {
+        let params = params.unwrap_or_default();
+        let runs = self.list_page(&params).await?;
+        Ok(runs.into_iter().filter(|run| params.matches(run)).collect())
+    }
+
+    async fn list_page(&self, params: &ListRunsParams) -> LettaResult<Vec<Run>> {
         self.client
-            .get_with_query(&format!("v1/runs"), &[("agent_ids", id_list.join(","))])
+            .get_with_query("v1/runs", &params.to_query())
             .await
     }
 
+    /// List runs with pagination support.
+    ///
+    /// Returns a stream that automatically fetches subsequent pages as needed.
+    /// Filters other than the pagination fields are kept for every page. The
+    /// client-side filters are applied to the streamed runs, so the stream
+    /// keeps paging past runs that don't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters for filtering and pagination
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use letta::{LettaClient, types::{JobStatus, ListRunsParams}};
+    /// # async fn example(client: &LettaClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let failed = client
+    ///     .runs()
+    ///     .paginated(Some(ListRunsParams {
+    ///         statuses: Some(vec![JobStatus::Failed]),
+    ///         limit: Some(50),
+    ///         ..Default::default()
+    ///     }))
+    ///     .collect()
+    ///     .await?;
+    /// println!("{} failed runs", failed.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginated(&self, params: Option<ListRunsParams>) -> PaginatedStream<Run> {
+        let client = self.client.clone();
+        let base_params = params.unwrap_or_default();
+        let filter = base_params.clone();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit.map(|l| l as u32),
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_id_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit.map(|l| l as i32);
+                }
+
+                async move { client.runs().list_page(&effective_params).await }
+            },
+            |run| &run.id,
+        )
+        .retain(move |run| filter.matches(run))
+    }
+
     /// Get a specific run.
     ///
     /// # Arguments
@@ -45,32 +117,162 @@ impl<'a> RunApi<'a> {
         self.client.get(&format!("v1/runs/{}", run_id)).await
     }
 
+    /// Get token usage for a run.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - The ID of the run whose usage to retrieve
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn usage(&self, run_id: &LettaId) -> LettaResult<LettaUsageStatistics> {
+        self.client.get(&format!("v1/runs/{}/usage", run_id)).await
+    }
+
     /// Get messages for a run.
     ///
     /// # Arguments
     ///
     /// * `run_id` - The ID of the run whose messages to retrieve
+    /// * `params` - Optional query parameters for filtering and pagination
     ///
     /// # Errors
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn get_messages(&self, run_id: &LettaId) -> LettaResult<Vec<LettaMessageUnion>> {
+    pub async fn get_messages(
+        &self,
+        run_id: &LettaId,
+        params: Option<ListRunMessagesParams>,
+    ) -> LettaResult<Vec<LettaMessageUnion>> {
         self.client
-            .get(&format!("v1/runs/{}/messages", run_id))
+            .get_with_query(
+                &format!("v1/runs/{}/messages", run_id),
+                &params.unwrap_or_default(),
+            )
             .await
     }
 
+    /// Get messages for a run with pagination support.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - The ID of the run whose messages to retrieve
+    /// * `params` - Optional query parameters for filtering and pagination
+    pub fn paginated_messages(
+        &self,
+        run_id: &LettaId,
+        params: Option<ListRunMessagesParams>,
+    ) -> PaginatedStream<LettaMessageUnion> {
+        let client = self.client.clone();
+        let run_id = run_id.clone();
+        let base_params = params.unwrap_or_default();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit.map(|l| l as u32),
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_id_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let run_id = run_id.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit.map(|l| l as i32);
+                }
+
+                async move {
+                    client
+                        .runs()
+                        .get_messages(&run_id, Some(effective_params))
+                        .await
+                }
+            },
+            |message| match message {
+                LettaMessageUnion::SystemMessage(msg) => &msg.id,
+                LettaMessageUnion::UserMessage(msg) => &msg.id,
+                LettaMessageUnion::AssistantMessage(msg) => &msg.id,
+                LettaMessageUnion::ReasoningMessage(msg) => &msg.id,
+                LettaMessageUnion::HiddenReasoningMessage(msg) => &msg.id,
+                LettaMessageUnion::ToolCallMessage(msg) => &msg.id,
+                LettaMessageUnion::ToolReturnMessage(msg) => &msg.id,
+                LettaMessageUnion::ApprovalRequestMessage(msg) => &msg.id,
+            },
+        )
+    }
+
     /// Get steps for a run.
     ///
     /// # Arguments
     ///
     /// * `run_id` - The ID of the run whose steps to retrieve
+    /// * `params` - Optional query parameters for pagination
     ///
     /// # Errors
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
-    pub async fn get_steps(&self, run_id: &LettaId) -> LettaResult<Vec<Step>> {
-        self.client.get(&format!("v1/runs/{}/steps", run_id)).await
+    pub async fn get_steps(
+        &self,
+        run_id: &LettaId,
+        params: Option<ListRunStepsParams>,
+    ) -> LettaResult<Vec<Step>> {
+        self.client
+            .get_with_query(
+                &format!("v1/runs/{}/steps", run_id),
+                &params.unwrap_or_default(),
+            )
+            .await
+    }
+
+    /// Get steps for a run with pagination support.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - The ID of the run whose steps to retrieve
+    /// * `params` - Optional query parameters for pagination
+    pub fn paginated_steps(
+        &self,
+        run_id: &LettaId,
+        params: Option<ListRunStepsParams>,
+    ) -> PaginatedStream<Step> {
+        let client = self.client.clone();
+        let run_id = run_id.clone();
+        let base_params = params.unwrap_or_default();
+        let initial = PaginationParams {
+            before: base_params.before.clone(),
+            after: base_params.after.clone(),
+            limit: base_params.limit.map(|l| l as u32),
+            ascending: None,
+        };
+
+        PaginatedStream::new_with_id_cursor(
+            Some(initial),
+            move |page_params| {
+                let client = client.clone();
+                let run_id = run_id.clone();
+                let mut effective_params = base_params.clone();
+
+                if let Some(p) = page_params {
+                    effective_params.before = p.before;
+                    effective_params.after = p.after;
+                    effective_params.limit = p.limit.map(|l| l as i32);
+                }
+
+                async move {
+                    client
+                        .runs()
+                        .get_steps(&run_id, Some(effective_params))
+                        .await
+                }
+            },
+            |step| &step.id,
+        )
     }
 
     /// List active runs for an agent.
@@ -83,12 +285,12 @@ impl<'a> RunApi<'a> {
     ///
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
     pub async fn list_active(&self, agent_ids: &[LettaId]) -> LettaResult<Vec<Run>> {
-        let id_list = agent_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>();
+        let params = ListRunsParams {
+            agent_ids: Some(agent_ids.to_vec()),
+            ..Default::default()
+        };
         self.client
-            .get_with_query(
-                &format!("v1/runs/active"),
-                &[("agent_ids", id_list.join(","))],
-            )
+            .get_with_query("v1/runs/active", &params.to_query())
             .await
     }
 }
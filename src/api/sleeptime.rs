@@ -0,0 +1,196 @@
+//! Sleep-time agent management.
+//!
+//! An agent created with
+//! [`enable_sleeptime`](crate::types::CreateAgentRequestBuilder::enable_sleeptime)
+//! gets a sleep-time group in which a companion agent periodically reviews
+//! the conversation and edits the primary agent's memory. These helpers find
+//! that group and agent from the primary agent's ID.
+
+use crate::client::LettaClient;
+use crate::error::{LettaError, LettaResult};
+use crate::types::{
+    Group, GroupUpdate, GroupUpdateManagerConfig, LettaId, LettaMessageUnion, ListRunsParams,
+    ManagerType, Run, SleeptimeActivity, SleeptimeManagerUpdate, SleeptimeMemoryEdit,
+};
+
+/// Sleep-time agent API operations.
+#[derive(Debug)]
+pub struct SleeptimeApi<'a> {
+    client: &'a LettaClient,
+}
+
+impl<'a> SleeptimeApi<'a> {
+    /// Create a new sleep-time API instance.
+    pub fn new(client: &'a LettaClient) -> Self {
+        Self { client }
+    }
+
+    /// Get the sleep-time group managed by a primary agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the primary agent
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails, if the response cannot be
+    /// parsed, or if the agent has no sleep-time group.
+    pub async fn get_group(&self, agent_id: &LettaId) -> LettaResult<Group> {
+        let agent = self.client.agents().get(agent_id).await?;
+        if let Some(group) = agent
+            .multi_agent_group
+            .and_then(|value| serde_json::from_value::<Group>(value).ok())
+            .filter(Group::is_sleeptime)
+        {
+            return Ok(group);
+        }
+
+        self.client
+            .agents()
+            .list_groups(agent_id)
+            .await?
+            .into_iter()
+            .find(|group| group.is_sleeptime() && group.manager_agent_id.as_ref() == Some(agent_id))
+            .ok_or_else(|| LettaError::not_found("sleeptime group", agent_id.to_string()))
+    }
+
+    /// Get the ID of a primary agent's sleep-time agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the primary agent
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails, if the response cannot be
+    /// parsed, or if the agent has no sleep-time agent.
+    pub async fn get_agent(&self, agent_id: &LettaId) -> LettaResult<LettaId> {
+        let group = self.get_group(agent_id).await?;
+        Self::sleeptime_agent_id(&group, agent_id)
+    }
+
+    /// Set how many primary agent turns pass between sleep-time agent runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the primary agent
+    /// * `frequency` - Number of turns between runs (at least 1)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if `frequency` is less than 1, if the agent has no
+    /// sleep-time group, if the group is a voice sleep-time group (which has no frequency), or
+    /// if the request fails.
+    pub async fn update_frequency(&self, agent_id: &LettaId, frequency: i32) -> LettaResult<Group> {
+        if frequency < 1 {
+            return Err(LettaError::validation_field(
+                "sleeptime agent frequency must be at least 1",
+                "sleeptime_agent_frequency",
+            ));
+        }
+        let group = self.get_group(agent_id).await?;
+        if group.manager_type != ManagerType::Sleeptime {
+            return Err(LettaError::validation_field(
+                format!("group {} has no sleeptime agent frequency", group.id),
+                "manager_type",
+            ));
+        }
+
+        let update = GroupUpdate {
+            manager_config: Some(GroupUpdateManagerConfig::Sleeptime(
+                SleeptimeManagerUpdate {
+                    manager_agent_id: None,
+                    sleeptime_agent_frequency: Some(frequency),
+                },
+            )),
+            ..Default::default()
+        };
+        self.client.groups().update(&group.id, update).await
+    }
+
+    /// List the most recent runs of a primary agent's sleep-time agent, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the primary agent
+    /// * `limit` - Maximum number of runs to return
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails, if the response cannot be
+    /// parsed, or if the agent has no sleep-time agent.
+    pub async fn list_runs(&self, agent_id: &LettaId, limit: i32) -> LettaResult<Vec<Run>> {
+        let sleeptime_agent_id = self.get_agent(agent_id).await?;
+        self.runs_of(&sleeptime_agent_id, limit).await
+    }
+
+    /// Get the sleep-time group, its agent's most recent runs and the memory
+    /// block edits made in them.
+    ///
+    /// Edits are read from the memory tool calls in each run's messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the primary agent
+    /// * `runs_limit` - Maximum number of runs to inspect
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails, if a response cannot be
+    /// parsed, or if the agent has no sleep-time agent.
+    pub async fn activity(
+        &self,
+        agent_id: &LettaId,
+        runs_limit: i32,
+    ) -> LettaResult<SleeptimeActivity> {
+        let group = self.get_group(agent_id).await?;
+        let sleeptime_agent_id = Self::sleeptime_agent_id(&group, agent_id)?;
+        let runs = self.runs_of(&sleeptime_agent_id, runs_limit).await?;
+
+        let mut edits = Vec::new();
+        for run in runs.iter().rev() {
+            let messages = self.client.runs().get_messages(&run.id, None).await?;
+            edits.extend(messages.iter().filter_map(|message| match message {
+                LettaMessageUnion::ToolCallMessage(call) => {
+                    SleeptimeMemoryEdit::from_tool_call(&run.id, call)
+                }
+                _ => None,
+            }));
+        }
+
+        Ok(SleeptimeActivity {
+            group,
+            sleeptime_agent_id,
+            runs,
+            edits,
+        })
+    }
+
+    fn sleeptime_agent_id(group: &Group, agent_id: &LettaId) -> LettaResult<LettaId> {
+        group
+            .worker_ids()
+            .find(|id| *id != agent_id)
+            .cloned()
+            .ok_or_else(|| LettaError::not_found("sleeptime agent", agent_id.to_string()))
+    }
+
+    async fn runs_of(&self, sleeptime_agent_id: &LettaId, limit: i32) -> LettaResult<Vec<Run>> {
+        self.client
+            .runs()
+            .list(Some(ListRunsParams {
+                agent_ids: Some(vec![sleeptime_agent_id.clone()]),
+                limit: Some(limit),
+                ascending: Some(false),
+                ..Default::default()
+            }))
+            .await
+    }
+}
+
+/// Convenience methods for sleep-time agent operations.
+impl LettaClient {
+    /// Get the sleep-time agent API for this client.
+    pub fn sleeptime(&self) -> SleeptimeApi<'_> {
+        SleeptimeApi::new(self)
+    }
+}
@@ -6,11 +6,13 @@ use crate::pagination::PaginatedStream;
 use crate::types::agent::AgentState;
 use crate::types::memory::Passage;
 use crate::types::source::{
-    CreateSourceRequest, FileMetadata, FileUploadResponse, GetFileParams, ListFilesParams,
-    ListPassagesParams, Source, UpdateSourceRequest,
+    AgentFile, AgentFileAttachment, CreateSourceRequest, FileMetadata, FileUploadResponse,
+    GetFileParams, ListAgentFilesParams, ListFilesParams, ListPassagesParams, PaginatedAgentFiles,
+    Source, UpdateSourceRequest,
 };
 use crate::types::{LettaId, PaginationParams};
 use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
 use reqwest::multipart::{Form, Part};
 use serde_json::Value;
 
@@ -143,6 +145,11 @@ impl<'a> SourceApi<'a> {
         AgentSourceApi::new(self.client, agent_id)
     }
 
+    /// Get agent files sub-API for a specific agent.
+    pub fn agent_files(&self, agent_id: LettaId) -> AgentFilesApi<'_> {
+        AgentFilesApi::new(self.client, agent_id)
+    }
+
     /// Get a paginated stream of files for a source.
     ///
     /// This method returns a [`PaginatedStream`] that automatically handles pagination
@@ -307,6 +314,161 @@ impl<'a> AgentSourceApi<'a> {
     }
 }
 
+/// How many file metadata requests [`AgentFilesApi::list`] makes at once.
+const FILE_FETCH_CONCURRENCY: usize = 4;
+
+/// Agent files sub-API operations.
+///
+/// Files from an agent's attached sources can be opened into (or closed out
+/// of) the agent's context window. The server limits how many files may be
+/// open at once and closes the least recently used ones to make room.
+#[derive(Debug)]
+pub struct AgentFilesApi<'a> {
+    client: &'a LettaClient,
+    agent_id: LettaId,
+}
+
+impl<'a> AgentFilesApi<'a> {
+    /// Create a new agent files API instance.
+    pub fn new(client: &'a LettaClient, agent_id: LettaId) -> Self {
+        Self { client, agent_id }
+    }
+
+    /// List files visible to the agent, with full file metadata.
+    ///
+    /// Fetches one page of attachments, then the metadata of each attached
+    /// file: one request per file, a few at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters for filtering and pagination
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list(&self, params: Option<ListAgentFilesParams>) -> LettaResult<Vec<AgentFile>> {
+        let page = self.list_attachments(params).await?;
+        let sources = self.client.sources();
+        stream::iter(page.files)
+            .map(|attachment| {
+                let sources = &sources;
+                async move {
+                    let file = sources
+                        .get_file(&attachment.source_id, &attachment.file_id, None)
+                        .await?;
+                    Ok(AgentFile::from_attachment(attachment, Some(file)))
+                }
+            })
+            .buffered(FILE_FETCH_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// List one page of file attachments for the agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional query parameters for filtering and pagination
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_attachments(
+        &self,
+        params: Option<ListAgentFilesParams>,
+    ) -> LettaResult<PaginatedAgentFiles> {
+        self.client
+            .get_with_query(
+                &format!("v1/agents/{}/files", self.agent_id),
+                &params.unwrap_or_default(),
+            )
+            .await
+    }
+
+    /// List the files currently open in the agent's context window.
+    ///
+    /// Follows the attachment cursor until the server reports no more pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn list_open(&self) -> LettaResult<Vec<AgentFileAttachment>> {
+        let mut files = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = self
+                .list_attachments(Some(ListAgentFilesParams {
+                    cursor,
+                    is_open: Some(true),
+                    ..Default::default()
+                }))
+                .await?;
+            files.extend(page.files);
+            match page.next_cursor {
+                Some(next) if page.has_more => cursor = Some(next),
+                _ => return Ok(files),
+            }
+        }
+    }
+
+    /// Open a file in the agent's context window.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The ID of the file to open
+    ///
+    /// # Returns
+    ///
+    /// The names of files the server closed to stay within the open-file limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn open(&self, file_id: &LettaId) -> LettaResult<Vec<String>> {
+        self.client
+            .patch_no_body(&format!(
+                "v1/agents/{}/files/{}/open",
+                self.agent_id, file_id
+            ))
+            .await
+    }
+
+    /// Close a file, removing it from the agent's context window.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The ID of the file to close
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails.
+    pub async fn close(&self, file_id: &LettaId) -> LettaResult<()> {
+        let _: Value = self
+            .client
+            .patch_no_body(&format!(
+                "v1/agents/{}/files/{}/close",
+                self.agent_id, file_id
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Close every file open in the agent's context window.
+    ///
+    /// # Returns
+    ///
+    /// The names of the files that were closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn close_all(&self) -> LettaResult<Vec<String>> {
+        self.client
+            .patch_no_body(&format!("v1/agents/{}/files/close-all", self.agent_id))
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,6 +482,16 @@ mod tests {
         let _api = SourceApi::new(&client);
     }
 
+    #[test]
+    fn test_agent_files_api_creation() {
+        let config = ClientConfig::new("http://localhost:8283").unwrap();
+        let client = LettaClient::new(config).unwrap();
+        let _api = AgentFilesApi::new(
+            &client,
+            LettaId::from_str("agent-550e8400-e29b-41d4-a716-446655440000").unwrap(),
+        );
+    }
+
     #[test]
     fn test_agent_source_api_creation() {
         let config = ClientConfig::new("http://localhost:8283").unwrap();
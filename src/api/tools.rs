@@ -4,11 +4,13 @@ use crate::client::LettaClient;
 use crate::error::LettaResult;
 use crate::pagination::PaginatedStream;
 use crate::types::tool::{
+    ActionModel, AppModel, ComposioActionQuery, ComposioAddReport, ComposioAppQuery,
     CreateToolRequest, ListToolsParams, McpServerConfig, McpTool, RunToolFromSourceRequest,
     RunToolFromSourceResponse, TestMcpServerRequest, Tool, UpdateMcpServerRequest,
     UpdateToolRequest,
 };
 use crate::types::{LettaId, PaginationParams};
+use crate::utils::encode_path_segment;
 
 /// Tool API operations.
 #[derive(Debug)]
@@ -133,7 +135,10 @@ impl<'a> ToolApi<'a> {
     /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
     pub async fn list_mcp_tools_by_server(&self, server_name: &str) -> LettaResult<Vec<McpTool>> {
         self.client
-            .get(&format!("v1/tools/mcp/servers/{}/tools", server_name))
+            .get(&format!(
+                "v1/tools/mcp/servers/{}/tools",
+                encode_path_segment(server_name)
+            ))
             .await
     }
 
@@ -150,7 +155,11 @@ impl<'a> ToolApi<'a> {
     pub async fn add_mcp_tool(&self, server_name: &str, tool_name: &str) -> LettaResult<Tool> {
         self.client
             .post(
-                &format!("v1/tools/mcp/servers/{}/{}", server_name, tool_name),
+                &format!(
+                    "v1/tools/mcp/servers/{}/{}",
+                    encode_path_segment(server_name),
+                    encode_path_segment(tool_name)
+                ),
                 &serde_json::json!({}),
             )
             .await
@@ -167,7 +176,10 @@ impl<'a> ToolApi<'a> {
     /// Returns a [crate::error::LettaError] if the request fails.
     pub async fn delete_mcp_server(&self, server_name: &str) -> LettaResult<()> {
         self.client
-            .delete_no_response(&format!("v1/tools/mcp/servers/{}", server_name))
+            .delete_no_response(&format!(
+                "v1/tools/mcp/servers/{}",
+                encode_path_segment(server_name)
+            ))
             .await
     }
 
@@ -187,7 +199,10 @@ impl<'a> ToolApi<'a> {
         request: UpdateMcpServerRequest,
     ) -> LettaResult<McpServerConfig> {
         self.client
-            .patch(&format!("v1/tools/mcp/servers/{}", server_name), &request)
+            .patch(
+                &format!("v1/tools/mcp/servers/{}", encode_path_segment(server_name)),
+                &request,
+            )
             .await
     }
 
@@ -335,6 +350,74 @@ impl<'a> ToolApi<'a> {
             .await
     }
 
+    /// Search Composio apps.
+    ///
+    /// Letta returns all apps at once, so the filter and page are applied
+    /// after fetching them.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Text, category and enabled filters, and the page to return
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn search_composio_apps(
+        &self,
+        query: &ComposioAppQuery,
+    ) -> LettaResult<Vec<AppModel>> {
+        let apps = self.list_composio_apps().await?;
+        Ok(apps
+            .into_iter()
+            .filter(|app| query.matches(app))
+            .skip(query.offset.unwrap_or(0))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    /// Search the actions of a Composio app by use case and tags.
+    ///
+    /// Letta returns all of an app's actions at once, so the search and page
+    /// are applied after fetching them.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_name` - The name of the Composio app
+    /// * `query` - Use-case text and tags to match, and the page to return
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn search_composio_actions(
+        &self,
+        app_name: &str,
+        query: &ComposioActionQuery,
+    ) -> LettaResult<Vec<ActionModel>> {
+        let actions = self.list_composio_actions(app_name).await?;
+        Ok(actions
+            .into_iter()
+            .filter(|action| query.matches(action))
+            .skip(query.offset.unwrap_or(0))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect())
+    }
+
+    /// Add several Composio actions as Letta tools.
+    ///
+    /// Actions that cannot be added are recorded in the [`ComposioAddReport`].
+    ///
+    /// # Arguments
+    ///
+    /// * `action_names` - The Composio actions to add
+    pub async fn add_composio_tools<S: AsRef<str>>(&self, action_names: &[S]) -> ComposioAddReport {
+        let mut report = ComposioAddReport::default();
+        for action_name in action_names {
+            let action_name = action_name.as_ref();
+            report.record(action_name, self.add_composio_tool(action_name).await);
+        }
+        report
+    }
+
     /// Upsert base tools.
     ///
     /// Adds or updates the default set of base tools in the Letta system.
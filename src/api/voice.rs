@@ -1,8 +1,18 @@
 //! Voice API endpoints (beta).
 
 use crate::client::LettaClient;
-use crate::error::LettaResult;
-use crate::types::{LettaId, VoiceChatCompletionRequest, VoiceChatCompletionResponse};
+use crate::error::{LettaError, LettaResult};
+use crate::types::{
+    ChatCompletionChunk, LettaId, VoiceChatCompletionRequest, VoiceChatCompletionResponse,
+};
+use eventsource_stream::Eventsource;
+use futures::stream::{Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use std::pin::Pin;
+
+/// Streaming response type for voice chat completions.
+pub type VoiceChatCompletionStream =
+    Pin<Box<dyn Stream<Item = LettaResult<ChatCompletionChunk>> + Send>>;
 
 /// Voice API operations (beta).
 #[derive(Debug)]
@@ -18,8 +28,10 @@ impl<'a> VoiceApi<'a> {
 
     /// Create voice chat completions for an agent.
     ///
-    /// **Note**: This is a beta endpoint and the exact request/response structure
-    /// is not well-documented. The endpoint accepts and returns generic JSON data.
+    /// The request is sent with `stream` set to `false`. The voice-beta endpoint
+    /// is designed for streaming consumption, so prefer
+    /// [`create_voice_chat_completions_stream`](Self::create_voice_chat_completions_stream)
+    /// for interactive use.
     ///
     /// # Arguments
     ///
@@ -33,28 +45,141 @@ impl<'a> VoiceApi<'a> {
     pub async fn create_voice_chat_completions(
         &self,
         agent_id: &LettaId,
-        request: VoiceChatCompletionRequest,
+        mut request: VoiceChatCompletionRequest,
         user_id: Option<&str>,
     ) -> LettaResult<VoiceChatCompletionResponse> {
         let path = format!("v1/voice-beta/{}/chat/completions", agent_id);
+        request.stream = Some(false);
 
         if let Some(user_id) = user_id {
-            // Use post_with_headers when we have a user_id
-            let mut headers = reqwest::header::HeaderMap::new();
-            headers.insert(
-                "user-id",
-                user_id.parse().map_err(|_| {
-                    crate::error::LettaError::validation("Invalid user-id header value")
-                })?,
-            );
-
             self.client
-                .post_with_headers(&path, &request, headers)
+                .post_with_headers(&path, &request, user_id_headers(user_id)?)
                 .await
         } else {
             self.client.post(&path, &request).await
         }
     }
+
+    /// Create voice chat completions for an agent and stream the response.
+    ///
+    /// The server replies with OpenAI-compatible `chat.completion.chunk` events
+    /// over SSE; each event is yielded as a [`ChatCompletionChunk`].
+    ///
+    /// # Arguments
+    ///
+    /// * `agent_id` - The ID of the agent for voice chat
+    /// * `request` - The voice chat completion request (`stream` is forced on)
+    /// * `user_id` - Optional user ID to include in the request headers
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails. Errors while
+    /// reading the stream are yielded as stream items.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use letta::types::{ChatCompletionMessage, VoiceChatCompletionRequest};
+    /// # use letta::LettaId;
+    /// # use std::str::FromStr;
+    /// # use futures::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = letta::LettaClient::local()?;
+    /// let agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000")?;
+    /// let request = VoiceChatCompletionRequest::builder()
+    ///     .model("gpt-4o-mini")
+    ///     .messages(vec![ChatCompletionMessage::user("Hello!")])
+    ///     .build();
+    ///
+    /// let mut stream = client
+    ///     .voice()
+    ///     .create_voice_chat_completions_stream(&agent_id, request, None)
+    ///     .await?;
+    ///
+    /// while let Some(chunk) = stream.next().await {
+    ///     if let Some(text) = chunk?.content() {
+    ///         print!("{}", text);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_voice_chat_completions_stream(
+        &self,
+        agent_id: &LettaId,
+        mut request: VoiceChatCompletionRequest,
+        user_id: Option<&str>,
+    ) -> LettaResult<VoiceChatCompletionStream> {
+        let url = self
+            .client
+            .base_url()
+            .join(&format!("v1/voice-beta/{}/chat/completions", agent_id))?;
+        request.stream = Some(true);
+
+        // Build headers
+        let mut headers = HeaderMap::new();
+        self.client.auth().apply_to_headers(&mut headers)?;
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        headers.insert("Accept", "text/event-stream".parse().unwrap());
+        if let Some(user_id) = user_id {
+            headers.extend(user_id_headers(user_id)?);
+        }
+
+        let response = self
+            .client
+            .http()
+            .post(url)
+            .headers(headers)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await?;
+            return Err(LettaError::from_response(status, body));
+        }
+
+        let stream = response
+            .bytes_stream()
+            .eventsource()
+            .filter_map(|result| async move {
+                match result {
+                    Ok(event) => {
+                        // Skip keep-alives and the terminal sentinel
+                        if event.data.is_empty() || event.data == "[DONE]" {
+                            return None;
+                        }
+
+                        match serde_json::from_str::<ChatCompletionChunk>(&event.data) {
+                            Ok(chunk) => Some(Ok(chunk)),
+                            Err(e) => {
+                                tracing::warn!("Failed to parse voice completion chunk: {}", e);
+                                None
+                            }
+                        }
+                    }
+                    Err(e) => Some(Err(LettaError::streaming(format!(
+                        "SSE stream error: {}",
+                        e
+                    )))),
+                }
+            });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Build the `user-id` header map for voice requests.
+fn user_id_headers(user_id: &str) -> LettaResult<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "user-id",
+        user_id
+            .parse()
+            .map_err(|_| LettaError::validation("Invalid user-id header value"))?,
+    );
+    Ok(headers)
 }
 
 /// Convenience method for voice operations.
@@ -76,4 +201,11 @@ mod tests {
         let client = LettaClient::new(config).unwrap();
         let _api = VoiceApi::new(&client);
     }
+
+    #[test]
+    fn test_user_id_headers() {
+        let headers = user_id_headers("user-123").unwrap();
+        assert_eq!(headers.get("user-id").unwrap(), "user-123");
+        assert!(user_id_headers("bad\nvalue").is_err());
+    }
 }
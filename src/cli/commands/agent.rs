@@ -1,5 +1,6 @@
 //! Agent command implementations.
 
+use crate::tool::ToolRuleSet;
 use crate::types::agent::{AgentType, CreateAgentRequest, ListAgentsParams};
 use crate::types::common::LettaId;
 use crate::LettaClient;
@@ -60,6 +61,28 @@ pub enum AgentCommand {
         #[arg(short = 'y', long)]
         yes: bool,
     },
+    /// Check an agent's tool rules against its tools
+    Rules {
+        /// Agent ID
+        id: String,
+        /// Output format (summary, dot, mermaid)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Show an agent's sleep-time agent and the memory blocks it edited
+    Sleeptime {
+        /// Primary agent ID
+        id: String,
+        /// Set the number of turns between sleep-time runs
+        #[arg(short = 'f', long)]
+        frequency: Option<i32>,
+        /// Number of recent runs to inspect
+        #[arg(short = 'n', long, default_value = "5")]
+        runs: i32,
+        /// Output format (json, pretty, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
 }
 
 /// Handle agent commands.
@@ -82,6 +105,13 @@ pub async fn handle(cmd: AgentCommand, client: &LettaClient) -> miette::Result<(
         }
         AgentCommand::Get { id, output } => get_agent(client, &id, &output).await,
         AgentCommand::Delete { id, yes } => delete_agent(client, &id, yes).await,
+        AgentCommand::Rules { id, output } => check_rules(client, &id, &output).await,
+        AgentCommand::Sleeptime {
+            id,
+            frequency,
+            runs,
+            output,
+        } => show_sleeptime(client, &id, frequency, runs, &output).await,
     }
 }
 
@@ -254,3 +284,114 @@ async fn delete_agent(client: &LettaClient, id: &str, yes: bool) -> miette::Resu
 
     Ok(())
 }
+
+async fn check_rules(client: &LettaClient, id: &str, output: &str) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(id).into_diagnostic()?;
+    let agent = client.agents().get(&agent_id).await?;
+    let rules = ToolRuleSet::new(agent.tool_rules.unwrap_or_default());
+
+    match output {
+        "dot" => {
+            print!("{}", rules.to_dot());
+            return Ok(());
+        }
+        "mermaid" => {
+            print!("{}", rules.to_mermaid());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let tools = client.memory().list_agent_tools(&agent_id).await?;
+    let tool_names: Vec<&str> = tools.iter().map(|tool| tool.name.as_str()).collect();
+    let diagnostics = rules.validate(&tool_names);
+
+    if diagnostics.is_empty() {
+        println!(
+            "{} tool rule(s) over {} tool(s) look coherent.",
+            rules.rules().len(),
+            tool_names.len()
+        );
+        return Ok(());
+    }
+
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    for diagnostic in diagnostics {
+        eprintln!("{:?}", miette::Report::new(diagnostic));
+    }
+    if errors > 0 {
+        return Err(miette!("{} problem(s) in the agent's tool rules", errors));
+    }
+    Ok(())
+}
+
+async fn show_sleeptime(
+    client: &LettaClient,
+    id: &str,
+    frequency: Option<i32>,
+    runs: i32,
+    output: &str,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(id).into_diagnostic()?;
+    if let Some(frequency) = frequency {
+        client
+            .sleeptime()
+            .update_frequency(&agent_id, frequency)
+            .await
+            .wrap_err("Failed to update sleeptime frequency")?;
+        if output == "summary" {
+            println!("Sleeptime frequency set to {} turn(s).", frequency);
+        }
+    }
+
+    let activity = client
+        .sleeptime()
+        .activity(&agent_id, runs)
+        .await
+        .wrap_err("Failed to get sleeptime activity")?;
+
+    match output {
+        "json" => println!("{}", serde_json::to_string(&activity).into_diagnostic()?),
+        "pretty" => println!(
+            "{}",
+            serde_json::to_string_pretty(&activity).into_diagnostic()?
+        ),
+        _ => {
+            let group = &activity.group;
+            println!("Sleeptime Group: {}", group.id);
+            println!("  Manager Type: {:?}", group.manager_type);
+            println!("  Sleeptime Agent: {}", activity.sleeptime_agent_id);
+            if let Some(frequency) = group.sleeptime_agent_frequency {
+                println!("  Frequency: every {} turn(s)", frequency);
+            }
+            if let Some(turns) = group.turns_counter {
+                println!("  Turns Counter: {}", turns);
+            }
+
+            println!("\nRecent Runs ({}):", activity.runs.len());
+            for run in &activity.runs {
+                let status = run
+                    .status
+                    .map_or_else(|| "unknown".to_string(), |s| format!("{:?}", s));
+                match run.created_at {
+                    Some(created_at) => println!("  {} [{}] {}", run.id, status, created_at),
+                    None => println!("  {} [{}]", run.id, status),
+                }
+            }
+
+            let labels = activity.modified_labels();
+            if labels.is_empty() {
+                println!("\nNo memory blocks modified in these runs.");
+            } else {
+                println!("\nModified Blocks: {}", labels.join(", "));
+                for edit in &activity.edits {
+                    println!(
+                        "  {} {} via {} (run {})",
+                        edit.date, edit.label, edit.tool_name, edit.run_id
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,280 @@
+//! Composio command implementations.
+
+use crate::types::tool::{ComposioActionQuery, ComposioAppQuery};
+use crate::LettaClient;
+use clap::Parser;
+use miette::{miette, IntoDiagnostic};
+
+/// Composio commands.
+#[derive(Parser, Debug)]
+pub enum ComposioCommand {
+    /// List Composio apps
+    Apps {
+        /// Words that must appear in the app's name or description
+        #[arg(short = 'q', long)]
+        query: Option<String>,
+        /// Only apps in this category
+        #[arg(short = 'c', long)]
+        category: Option<String>,
+        /// Only apps enabled for the server's Composio account
+        #[arg(short = 'e', long)]
+        enabled: bool,
+        /// Maximum number of apps to return
+        #[arg(short = 'l', long, default_value = "20")]
+        limit: usize,
+        /// Number of apps to skip
+        #[arg(long, default_value = "0")]
+        offset: usize,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Search an app's actions
+    Actions {
+        /// App name
+        app: String,
+        /// Words describing the use case
+        #[arg(short = 'q', long)]
+        query: Option<String>,
+        /// Tag the action must have; repeat for more
+        #[arg(short = 't', long = "tag")]
+        tags: Vec<String>,
+        /// Maximum number of actions to return
+        #[arg(short = 'l', long, default_value = "20")]
+        limit: usize,
+        /// Number of actions to skip
+        #[arg(long, default_value = "0")]
+        offset: usize,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Show an action's parameters
+    Show {
+        /// App name
+        app: String,
+        /// Action name
+        action: String,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Add actions as Letta tools
+    Add {
+        /// Action names
+        #[arg(required = true)]
+        actions: Vec<String>,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+}
+
+/// Handle Composio commands.
+pub async fn handle(cmd: ComposioCommand, client: &LettaClient) -> miette::Result<()> {
+    match cmd {
+        ComposioCommand::Apps {
+            query,
+            category,
+            enabled,
+            limit,
+            offset,
+            output,
+        } => {
+            let query = ComposioAppQuery {
+                query,
+                category,
+                enabled_only: enabled,
+                offset: Some(offset),
+                limit: Some(limit),
+            };
+            list_apps(client, &query, &output).await
+        }
+        ComposioCommand::Actions {
+            app,
+            query,
+            tags,
+            limit,
+            offset,
+            output,
+        } => {
+            let query = ComposioActionQuery {
+                query,
+                tags,
+                offset: Some(offset),
+                limit: Some(limit),
+            };
+            list_actions(client, &app, &query, &output).await
+        }
+        ComposioCommand::Show {
+            app,
+            action,
+            output,
+        } => show_action(client, &app, &action, &output).await,
+        ComposioCommand::Add { actions, output } => add_actions(client, &actions, &output).await,
+    }
+}
+
+async fn list_apps(
+    client: &LettaClient,
+    query: &ComposioAppQuery,
+    output: &str,
+) -> miette::Result<()> {
+    let apps = client.tools().search_composio_apps(query).await?;
+
+    if output == "json" {
+        println!("{}", serde_json::to_string_pretty(&apps).into_diagnostic()?);
+        return Ok(());
+    }
+
+    if apps.is_empty() {
+        println!("No apps found.");
+        return Ok(());
+    }
+
+    for app in &apps {
+        let mut flags = Vec::new();
+        if app.is_enabled() {
+            flags.push("enabled");
+        }
+        if !app.requires_auth() {
+            flags.push("no auth");
+        }
+        let flags = if flags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", flags.join(", "))
+        };
+        println!("{}{} - {}", app.key, flags, app.description);
+    }
+
+    Ok(())
+}
+
+async fn list_actions(
+    client: &LettaClient,
+    app: &str,
+    query: &ComposioActionQuery,
+    output: &str,
+) -> miette::Result<()> {
+    let actions = client.tools().search_composio_actions(app, query).await?;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&actions).into_diagnostic()?
+        );
+        return Ok(());
+    }
+
+    if actions.is_empty() {
+        println!("No actions found.");
+        return Ok(());
+    }
+
+    for action in &actions {
+        println!("{}", action.name);
+        println!("  {}", action.description);
+        if !action.tags.is_empty() {
+            println!("  Tags: {}", action.tags.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+async fn show_action(
+    client: &LettaClient,
+    app: &str,
+    action_name: &str,
+    output: &str,
+) -> miette::Result<()> {
+    let actions = client.tools().list_composio_actions(app).await?;
+    let action = actions
+        .into_iter()
+        .find(|action| action.name.eq_ignore_ascii_case(action_name))
+        .ok_or_else(|| miette!("No action '{}' in app '{}'", action_name, app))?;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&action).into_diagnostic()?
+        );
+        return Ok(());
+    }
+
+    println!("{}", action.name);
+    if let Some(display_name) = &action.display_name {
+        println!("  Display Name: {}", display_name);
+    }
+    println!("  Description: {}", action.description);
+    println!("  App: {}", action.app_name);
+    println!("  Version: {}", action.version);
+    if !action.tags.is_empty() {
+        println!("  Tags: {}", action.tags.join(", "));
+    }
+
+    println!("\nParameters:");
+    let parameters = action.parameters();
+    if parameters.is_empty() {
+        println!("  (none)");
+    }
+    for parameter in parameters {
+        println!(
+            "  {}: {}{}",
+            parameter.name,
+            parameter.param_type.as_deref().unwrap_or("any"),
+            if parameter.required {
+                " (required)"
+            } else {
+                ""
+            }
+        );
+        if let Some(description) = &parameter.description {
+            println!("      {}", description);
+        }
+        if let Some(default) = &parameter.default {
+            println!("      Default: {}", default);
+        }
+        if let Some(values) = &parameter.enum_values {
+            let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            println!("      One of: {}", values.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_actions(client: &LettaClient, actions: &[String], output: &str) -> miette::Result<()> {
+    let report = client.tools().add_composio_tools(actions).await;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).into_diagnostic()?
+        );
+    } else {
+        for tool in &report.succeeded {
+            println!(
+                "Added {} ({})",
+                tool.name,
+                tool.id
+                    .as_ref()
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "N/A".to_string())
+            );
+        }
+        for failure in &report.failed {
+            eprintln!("Failed to add {}: {}", failure.item, failure.error);
+        }
+    }
+
+    if !report.failed.is_empty() {
+        return Err(miette!(
+            "{} of {} action(s) could not be added",
+            report.failed.len(),
+            actions.len()
+        ));
+    }
+    Ok(())
+}
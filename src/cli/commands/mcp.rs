@@ -0,0 +1,400 @@
+//! MCP server command implementations.
+
+use crate::types::tool::{
+    McpConnectEventType, McpServerConfig, McpTool, SseServerConfig, StdioServerConfig,
+    StreamableHttpServerConfig,
+};
+use crate::LettaClient;
+use clap::{Args, Parser, ValueEnum};
+use futures::StreamExt;
+use miette::{miette, IntoDiagnostic};
+
+/// MCP server commands.
+#[derive(Parser, Debug)]
+pub enum McpCommand {
+    /// List configured MCP servers
+    List {
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Add an MCP server
+    Add {
+        /// Server to connect to
+        #[command(flatten)]
+        server: ServerArgs,
+        /// Connect first, following the server's OAuth flow if it requires one
+        #[arg(long)]
+        oauth: bool,
+        /// Import all of the server's tools once it is added
+        #[arg(short = 'i', long)]
+        import: bool,
+    },
+    /// Test connecting to an MCP server without adding it
+    Test {
+        /// Server to connect to
+        #[command(flatten)]
+        server: ServerArgs,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// List the tools a configured server offers
+    Tools {
+        /// Server name
+        server: String,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Import tools from a configured server as Letta tools
+    Import {
+        /// Server name
+        server: String,
+        /// Tools to import (all tools if none are given)
+        tools: Vec<String>,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Remove a configured MCP server
+    Remove {
+        /// Server name
+        server: String,
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+/// Transport for a server reached by URL.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Transport {
+    /// Server-sent events
+    Sse,
+    /// Streamable HTTP
+    Http,
+}
+
+/// How to reach an MCP server.
+#[derive(Args, Debug)]
+pub struct ServerArgs {
+    /// Server name
+    name: String,
+    /// Server URL (SSE or streamable HTTP)
+    #[arg(long, required_unless_present = "command", conflicts_with = "command")]
+    url: Option<String>,
+    /// Command to run (stdio)
+    #[arg(long)]
+    command: Option<String>,
+    /// Argument for the command; repeat for more
+    #[arg(long = "arg", requires = "command", allow_hyphen_values = true)]
+    args: Vec<String>,
+    /// Transport for --url
+    #[arg(short = 't', long, value_enum, default_value = "sse")]
+    transport: Transport,
+    /// Bearer token, sent as `Authorization: Bearer <token>`
+    #[arg(long, env = "LETTA_MCP_TOKEN", conflicts_with = "auth")]
+    bearer: Option<String>,
+    /// Authentication header, as "NAME: VALUE"
+    #[arg(long)]
+    auth: Option<String>,
+    /// Extra header, as "NAME: VALUE"; repeat for more
+    #[arg(short = 'H', long = "header")]
+    headers: Vec<String>,
+    /// Environment variable for the command, as KEY=VALUE; repeat for more
+    #[arg(short = 'e', long = "env", requires = "command")]
+    env: Vec<String>,
+}
+
+impl ServerArgs {
+    fn into_config(self) -> miette::Result<McpServerConfig> {
+        if let Some(command) = self.command {
+            let mut config = StdioServerConfig::new(self.name, command, self.args);
+            for var in &self.env {
+                let (key, value) = var.split_once('=').ok_or_else(|| {
+                    miette!("Invalid environment variable '{}', expected KEY=VALUE", var)
+                })?;
+                config = config.env(key, value);
+            }
+            return Ok(config.into());
+        }
+
+        let url = self.url.expect("clap requires --url without --command");
+        let auth = self.auth.as_deref().map(parse_header).transpose()?;
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| parse_header(header))
+            .collect::<miette::Result<Vec<_>>>()?;
+
+        // Both HTTP transports take the same authentication settings
+        macro_rules! http_config {
+            ($config:expr) => {{
+                let mut config = $config;
+                if let Some(token) = &self.bearer {
+                    config = config.bearer_token(token);
+                }
+                if let Some((name, value)) = auth {
+                    config = config.auth(name, value);
+                }
+                for (name, value) in headers {
+                    config = config.header(name, value);
+                }
+                config.into()
+            }};
+        }
+
+        Ok(match self.transport {
+            Transport::Sse => http_config!(SseServerConfig::new(self.name, url)),
+            Transport::Http => http_config!(StreamableHttpServerConfig::new(self.name, url)),
+        })
+    }
+}
+
+/// Split a "NAME: VALUE" header.
+fn parse_header(header: &str) -> miette::Result<(&str, &str)> {
+    header
+        .split_once(':')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or_else(|| miette!("Invalid header '{}', expected \"NAME: VALUE\"", header))
+}
+
+/// Handle MCP commands.
+pub async fn handle(cmd: McpCommand, client: &LettaClient) -> miette::Result<()> {
+    match cmd {
+        McpCommand::List { output } => list_servers(client, &output).await,
+        McpCommand::Add {
+            server,
+            oauth,
+            import,
+        } => add_server(client, server.into_config()?, oauth, import).await,
+        McpCommand::Test { server, output } => {
+            test_server(client, server.into_config()?, &output).await
+        }
+        McpCommand::Tools { server, output } => list_tools(client, &server, &output).await,
+        McpCommand::Import {
+            server,
+            tools,
+            output,
+        } => import_tools(client, &server, &tools, &output).await,
+        McpCommand::Remove { server, yes } => remove_server(client, &server, yes).await,
+    }
+}
+
+async fn list_servers(client: &LettaClient, output: &str) -> miette::Result<()> {
+    let servers = client.mcp().list_servers().await?;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&servers).into_diagnostic()?
+        );
+        return Ok(());
+    }
+
+    if servers.is_empty() {
+        println!("No MCP servers configured.");
+        return Ok(());
+    }
+
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+    for name in names {
+        match &servers[name] {
+            McpServerConfig::Sse(config) => println!("{} (sse) {}", name, config.server_url),
+            McpServerConfig::StreamableHttp(config) => {
+                println!("{} (http) {}", name, config.server_url)
+            }
+            McpServerConfig::Stdio(config) => {
+                println!(
+                    "{} (stdio) {} {}",
+                    name,
+                    config.command,
+                    config.args.join(" ")
+                )
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn add_server(
+    client: &LettaClient,
+    config: McpServerConfig,
+    oauth: bool,
+    import: bool,
+) -> miette::Result<()> {
+    let name = config.server_name().to_string();
+
+    if oauth {
+        let mut events = client.mcp().connect(config.clone()).await?;
+        let mut connected = false;
+        while let Some(event) = events.next().await {
+            let event = event?;
+            match event.event {
+                McpConnectEventType::AuthorizationUrl => {
+                    println!(
+                        "Open this URL to authorize:\n  {}",
+                        event.url.as_deref().unwrap_or_default()
+                    );
+                }
+                McpConnectEventType::Success => {
+                    connected = true;
+                    break;
+                }
+                McpConnectEventType::Error => {
+                    return Err(miette!(
+                        "Failed to connect to '{}': {}",
+                        name,
+                        event.message.as_deref().unwrap_or("unknown error")
+                    ));
+                }
+                _ => {
+                    if let Some(message) = &event.message {
+                        println!("{}", message);
+                    }
+                }
+            }
+        }
+        if !connected {
+            return Err(miette!(
+                "Connection to '{}' ended before it succeeded",
+                name
+            ));
+        }
+    }
+
+    client.mcp().add_server(config).await?;
+    println!("Added MCP server '{}'.", name);
+
+    if import {
+        import_tools(client, &name, &[], "summary").await?;
+    }
+
+    Ok(())
+}
+
+async fn test_server(
+    client: &LettaClient,
+    config: McpServerConfig,
+    output: &str,
+) -> miette::Result<()> {
+    let name = config.server_name().to_string();
+    let result = client.mcp().test(config).await?;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&result).into_diagnostic()?
+        );
+    } else if result.is_success() {
+        println!("Connected to '{}'.", name);
+        print_tools(&result.tools);
+    }
+
+    if !result.is_success() {
+        return Err(miette!(
+            "Failed to connect to '{}': {}",
+            name,
+            result.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+    Ok(())
+}
+
+async fn list_tools(client: &LettaClient, server: &str, output: &str) -> miette::Result<()> {
+    let tools = client.mcp().list_tools(server).await?;
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&tools).into_diagnostic()?
+        );
+    } else {
+        print_tools(&tools);
+    }
+
+    Ok(())
+}
+
+fn print_tools(tools: &[McpTool]) {
+    if tools.is_empty() {
+        println!("No tools found.");
+        return;
+    }
+
+    println!("Found {} tools:\n", tools.len());
+    for tool in tools {
+        match &tool.description {
+            Some(description) => println!("  {} - {}", tool.name, description),
+            None => println!("  {}", tool.name),
+        }
+    }
+}
+
+async fn import_tools(
+    client: &LettaClient,
+    server: &str,
+    tools: &[String],
+    output: &str,
+) -> miette::Result<()> {
+    let report = if tools.is_empty() {
+        client.mcp().import_all(server).await?
+    } else {
+        client.mcp().import_tools(server, tools).await
+    };
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).into_diagnostic()?
+        );
+    } else {
+        for tool in &report.succeeded {
+            println!(
+                "Imported {} ({})",
+                tool.name,
+                tool.id
+                    .as_ref()
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "N/A".to_string())
+            );
+        }
+        for failure in &report.failed {
+            eprintln!("Failed to import {}: {}", failure.item, failure.error);
+        }
+    }
+
+    if !report.failed.is_empty() {
+        return Err(miette!(
+            "{} of {} tool(s) failed to import",
+            report.failed.len(),
+            report.total()
+        ));
+    }
+    Ok(())
+}
+
+async fn remove_server(client: &LettaClient, server: &str, yes: bool) -> miette::Result<()> {
+    if !yes {
+        print!(
+            "Are you sure you want to remove MCP server '{}'? (y/N) ",
+            server
+        );
+        use std::io::Write;
+        std::io::stdout().flush().into_diagnostic()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).into_diagnostic()?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    client.mcp().delete_server(server).await?;
+    println!("Removed MCP server '{}'.", server);
+    Ok(())
+}
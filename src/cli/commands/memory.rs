@@ -1,12 +1,15 @@
 //! Memory command implementations.
 
+use crate::memory::{ArchivalExport, ArchivalImport};
 use crate::types::common::LettaId;
 use crate::types::memory::{
     ArchivalMemoryQueryParams, CreateArchivalMemoryRequest, UpdateMemoryBlockRequest,
 };
 use crate::LettaClient;
 use clap::Parser;
+use futures::TryStreamExt;
 use miette::{Context, IntoDiagnostic};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// Memory-related commands.
@@ -33,14 +36,24 @@ pub enum MemoryCommand {
         #[arg(short = 'v', long)]
         value: String,
     },
-    /// Search archival memory
+    /// List, search, export or import archival memory
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Archival {
+        /// Export or import instead of listing
+        #[command(subcommand)]
+        action: Option<ArchivalCommand>,
         /// Agent ID
-        #[arg(short = 'a', long)]
-        agent_id: String,
-        /// Query text to search for
+        #[arg(short = 'a', long, required = true)]
+        agent_id: Option<String>,
+        /// Query text to search for (lists all passages if omitted)
         #[arg(short = 'q', long)]
-        query: String,
+        query: Option<String>,
+        /// Only show passages with this tag (repeatable)
+        #[arg(short = 't', long = "tag")]
+        tags: Vec<String>,
+        /// Require all tags instead of any
+        #[arg(long)]
+        all_tags: bool,
         /// Maximum number of results
         #[arg(short = 'l', long, default_value = "10")]
         limit: u32,
@@ -55,6 +68,43 @@ pub enum MemoryCommand {
         agent_id: String,
         /// Text to add to archival memory
         text: String,
+        /// Tag to attach to the passage (repeatable)
+        #[arg(short = 't', long = "tag")]
+        tags: Vec<String>,
+    },
+}
+
+/// Archival memory subcommands.
+#[derive(Parser, Debug)]
+pub enum ArchivalCommand {
+    /// Write archival memory as JSON Lines
+    Export {
+        /// Agent ID
+        #[arg(short = 'a', long)]
+        agent_id: String,
+        /// Include embedding vectors (import ignores them and re-embeds the text)
+        #[arg(long)]
+        embeddings: bool,
+        /// File to write to (defaults to stdout)
+        #[arg(short = 'f', long)]
+        file: Option<PathBuf>,
+    },
+    /// Create passages from a JSON Lines export
+    Import {
+        /// Agent ID
+        #[arg(short = 'a', long)]
+        agent_id: String,
+        /// File to read from (defaults to stdin)
+        file: Option<PathBuf>,
+        /// Import passages even if the agent already has their text
+        #[arg(long)]
+        no_dedup: bool,
+        /// Number of passages to create at once
+        #[arg(short = 'c', long, default_value = "4")]
+        concurrency: usize,
+        /// Output format (json, pretty, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
     },
 }
 
@@ -68,13 +118,55 @@ pub async fn handle(cmd: MemoryCommand, client: &crate::LettaClient) -> miette::
             value,
         } => edit_memory_block(client, &agent_id, &block, &value, "summary").await,
         MemoryCommand::Archival {
+            action: Some(action),
+            ..
+        } => handle_archival(action, client).await,
+        MemoryCommand::Archival {
+            action: None,
             agent_id,
             query,
+            tags,
+            all_tags,
             limit,
             output,
-        } => list_archival_memory(client, &agent_id, Some(query), limit, &output).await,
-        MemoryCommand::Add { agent_id, text } => {
-            add_archival_memory(client, &agent_id, &text, "summary").await
+        } => {
+            let agent_id = agent_id.ok_or_else(|| miette::miette!("--agent-id is required"))?;
+            let params = ArchivalMemoryQueryParams {
+                search: query,
+                limit: Some(limit),
+                tags: (!tags.is_empty()).then_some(tags),
+                match_all_tags: Some(all_tags),
+                ..Default::default()
+            };
+            list_archival_memory(client, &agent_id, params, &output).await
+        }
+        MemoryCommand::Add {
+            agent_id,
+            text,
+            tags,
+        } => add_archival_memory(client, &agent_id, &text, tags, "summary").await,
+    }
+}
+
+/// Handle archival memory subcommands.
+async fn handle_archival(cmd: ArchivalCommand, client: &LettaClient) -> miette::Result<()> {
+    match cmd {
+        ArchivalCommand::Export {
+            agent_id,
+            embeddings,
+            file,
+        } => export_archival_memory(client, &agent_id, embeddings, file.as_deref()).await,
+        ArchivalCommand::Import {
+            agent_id,
+            file,
+            no_dedup,
+            concurrency,
+            output,
+        } => {
+            let import = ArchivalImport::new()
+                .dedup(!no_dedup)
+                .concurrency(concurrency);
+            import_archival_memory(client, &agent_id, import, file.as_deref(), &output).await
         }
     }
 }
@@ -156,21 +248,18 @@ async fn edit_memory_block(
 async fn list_archival_memory(
     client: &LettaClient,
     agent_id: &str,
-    query: Option<String>,
-    limit: u32,
+    params: ArchivalMemoryQueryParams,
     output: &str,
 ) -> miette::Result<()> {
     let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+    let limit = params.limit.unwrap_or(10) as usize;
 
-    let params = ArchivalMemoryQueryParams {
-        search: query,
-        limit: Some(limit),
-        ..Default::default()
-    };
-
+    // Filter across pages, so tag filters still find `limit` matches
     match client
         .memory()
-        .list_archival_memory(&agent_id, Some(params))
+        .archival_query_paginated(&agent_id, Some(params))
+        .take(limit)
+        .try_collect::<Vec<_>>()
         .await
     {
         Ok(passages) => match output {
@@ -199,6 +288,9 @@ async fn list_archival_memory(
                                 .unwrap_or_else(|| "unknown".to_string())
                         );
                         println!("   {}", passage.text);
+                        if let Some(tags) = passage.tags.as_ref().filter(|t| !t.is_empty()) {
+                            println!("   Tags: {}", tags.join(", "));
+                        }
                         if let Some(embed_vec) = &passage.embedding {
                             println!("   Embedding: {} dimensions", embed_vec.len());
                         }
@@ -217,13 +309,15 @@ async fn add_archival_memory(
     client: &LettaClient,
     agent_id: &str,
     text: &str,
+    tags: Vec<String>,
     output: &str,
 ) -> miette::Result<()> {
     let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
 
-    let request = CreateArchivalMemoryRequest {
-        text: text.to_string(),
-    };
+    let mut request = CreateArchivalMemoryRequest::new(text);
+    if !tags.is_empty() {
+        request = request.tags(tags);
+    }
 
     match client
         .memory()
@@ -262,3 +356,90 @@ async fn add_archival_memory(
 
     Ok(())
 }
+
+async fn export_archival_memory(
+    client: &LettaClient,
+    agent_id: &str,
+    embeddings: bool,
+    file: Option<&Path>,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+    let export = ArchivalExport::new().include_embeddings(embeddings);
+
+    let count = match file {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+            export
+                .write(client, &agent_id, std::io::BufWriter::new(file))
+                .await
+        }
+        None => export.write(client, &agent_id, std::io::stdout()).await,
+    }
+    .wrap_err("Failed to export archival memory")?;
+
+    eprintln!("Exported {} archival memory passages.", count);
+    Ok(())
+}
+
+async fn import_archival_memory(
+    client: &LettaClient,
+    agent_id: &str,
+    import: ArchivalImport,
+    file: Option<&Path>,
+    output: &str,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+
+    let report = match file {
+        Some(path) if path != Path::new("-") => {
+            let file = std::fs::File::open(path)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+            import
+                .read(client, &agent_id, std::io::BufReader::new(file))
+                .await
+        }
+        _ => {
+            import
+                .read(client, &agent_id, std::io::BufReader::new(std::io::stdin()))
+                .await
+        }
+    }
+    .wrap_err("Failed to import archival memory")?;
+
+    match output {
+        "json" => {
+            println!("{}", serde_json::to_string(&report).into_diagnostic()?);
+        }
+        "pretty" => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).into_diagnostic()?
+            );
+        }
+        _ => {
+            println!(
+                "Imported {} passages, skipped {} duplicates.",
+                report.passage_ids.len(),
+                report.skipped
+            );
+            if !report.failed.is_empty() {
+                println!("\n{} passages failed:", report.failed.len());
+                for failure in &report.failed {
+                    println!("  line {}: {}", failure.line, failure.error);
+                }
+            }
+        }
+    }
+
+    if report.is_complete() {
+        Ok(())
+    } else {
+        Err(miette::miette!(
+            "{} passages could not be imported",
+            report.failed.len()
+        ))
+    }
+}
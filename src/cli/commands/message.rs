@@ -312,6 +312,11 @@ async fn list_messages(
                                 println!("Hidden Reasoning [{}]", m.date);
                                 println!("   State: {:?}\n", m.state);
                             }
+                            LettaMessageUnion::ApprovalRequestMessage(m) => {
+                                println!("Approval Request [{}]", m.date);
+                                println!("   Tool: {}", m.tool_call.name);
+                                println!("   Args: {}\n", m.tool_call.arguments);
+                            }
                         }
                     }
                 }
@@ -1,6 +1,8 @@
 //! CLI command implementations.
 
 pub mod agent;
+pub mod composio;
+pub mod mcp;
 pub mod memory;
 pub mod message;
 pub mod sources;
@@ -3,7 +3,8 @@
 use crate::types::agent::EmbeddingConfig;
 use crate::types::common::LettaId;
 use crate::types::source::{
-    CreateSourceRequest, FileUploadResponse, ListFilesParams, ListPassagesParams,
+    CreateSourceRequest, FileUploadResponse, ListAgentFilesParams, ListFilesParams,
+    ListPassagesParams,
 };
 use crate::LettaClient;
 use clap::Parser;
@@ -60,6 +61,9 @@ pub enum SourcesCommand {
     /// Passage operations on a source
     #[command(subcommand)]
     Passages(PassagesCommand),
+    /// Open/close source files in an agent's context window
+    #[command(subcommand)]
+    AgentFiles(AgentFilesCommand),
 }
 
 /// Files subcommands.
@@ -128,6 +132,47 @@ pub enum PassagesCommand {
     },
 }
 
+/// Agent files subcommands.
+#[derive(Parser, Debug)]
+pub enum AgentFilesCommand {
+    /// List files visible to an agent
+    List {
+        /// Agent ID
+        agent_id: String,
+        /// Only show files that are currently open
+        #[arg(long, conflicts_with = "closed")]
+        open: bool,
+        /// Only show files that are currently closed
+        #[arg(long)]
+        closed: bool,
+        /// Maximum number of files to return
+        #[arg(short = 'l', long, default_value = "50")]
+        limit: u32,
+        /// Output format (json, pretty, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Open a file in an agent's context window
+    Open {
+        /// Agent ID
+        agent_id: String,
+        /// File ID
+        file_id: String,
+    },
+    /// Close a file in an agent's context window
+    Close {
+        /// Agent ID
+        agent_id: String,
+        /// File ID
+        file_id: String,
+    },
+    /// Close all files open in an agent's context window
+    CloseAll {
+        /// Agent ID
+        agent_id: String,
+    },
+}
+
 /// Handle sources commands.
 pub async fn handle(cmd: SourcesCommand, client: &crate::LettaClient) -> miette::Result<()> {
     match cmd {
@@ -153,6 +198,9 @@ pub async fn handle(cmd: SourcesCommand, client: &crate::LettaClient) -> miette:
         SourcesCommand::Delete { id, yes } => delete_source(client, &id, yes).await,
         SourcesCommand::Files(files_cmd) => handle_files(files_cmd, client).await,
         SourcesCommand::Passages(passages_cmd) => handle_passages(passages_cmd, client).await,
+        SourcesCommand::AgentFiles(agent_files_cmd) => {
+            handle_agent_files(agent_files_cmd, client).await
+        }
     }
 }
 
@@ -687,3 +735,162 @@ async fn list_passages(
 
     Ok(())
 }
+
+/// Handle agent files subcommands.
+async fn handle_agent_files(cmd: AgentFilesCommand, client: &LettaClient) -> miette::Result<()> {
+    match cmd {
+        AgentFilesCommand::List {
+            agent_id,
+            open,
+            closed,
+            limit,
+            output,
+        } => {
+            let is_open = if open {
+                Some(true)
+            } else if closed {
+                Some(false)
+            } else {
+                None
+            };
+            list_agent_files(client, &agent_id, is_open, limit, &output).await
+        }
+        AgentFilesCommand::Open { agent_id, file_id } => {
+            open_agent_file(client, &agent_id, &file_id).await
+        }
+        AgentFilesCommand::Close { agent_id, file_id } => {
+            close_agent_file(client, &agent_id, &file_id).await
+        }
+        AgentFilesCommand::CloseAll { agent_id } => close_all_agent_files(client, &agent_id).await,
+    }
+}
+
+async fn list_agent_files(
+    client: &LettaClient,
+    agent_id: &str,
+    is_open: Option<bool>,
+    limit: u32,
+    output: &str,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+
+    let params = ListAgentFilesParams {
+        limit: Some(limit as i32),
+        is_open,
+        ..Default::default()
+    };
+
+    let files = client
+        .sources()
+        .agent_files(agent_id)
+        .list(Some(params))
+        .await
+        .wrap_err("Failed to list agent files")?;
+
+    match output {
+        "json" => {
+            println!("{}", serde_json::to_string(&files).into_diagnostic()?);
+        }
+        "pretty" => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&files).into_diagnostic()?
+            );
+        }
+        _ => {
+            if files.is_empty() {
+                println!("No files visible to agent.");
+            } else {
+                let open_count = files.iter().filter(|f| f.is_open).count();
+                println!("Found {} files ({} open):\n", files.len(), open_count);
+                for file in files {
+                    let metadata = &file.metadata;
+                    println!(
+                        "[{}] {}",
+                        if file.is_open { "open" } else { "closed" },
+                        metadata.file_name.as_deref().unwrap_or("N/A")
+                    );
+                    println!(
+                        "  ID: {}",
+                        metadata
+                            .id
+                            .as_ref()
+                            .map(|id| id.to_string())
+                            .unwrap_or_else(|| "N/A".to_string())
+                    );
+                    println!("  Source ID: {}", metadata.source_id);
+                    if let Some(size) = metadata.file_size {
+                        println!("  Size: {} bytes", size);
+                    }
+                    if let Some(accessed) = &file.last_accessed_at {
+                        println!("  Last accessed: {}", accessed);
+                    }
+                    println!();
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn open_agent_file(
+    client: &LettaClient,
+    agent_id: &str,
+    file_id: &str,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+    let file_id = LettaId::from_str(file_id).into_diagnostic()?;
+
+    let closed = client
+        .sources()
+        .agent_files(agent_id)
+        .open(&file_id)
+        .await
+        .wrap_err("Failed to open file")?;
+
+    println!("File {} opened.", file_id);
+    if !closed.is_empty() {
+        println!("Closed to make room: {}", closed.join(", "));
+    }
+
+    Ok(())
+}
+
+async fn close_agent_file(
+    client: &LettaClient,
+    agent_id: &str,
+    file_id: &str,
+) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+    let file_id = LettaId::from_str(file_id).into_diagnostic()?;
+
+    client
+        .sources()
+        .agent_files(agent_id)
+        .close(&file_id)
+        .await
+        .wrap_err("Failed to close file")?;
+
+    println!("File {} closed.", file_id);
+    Ok(())
+}
+
+async fn close_all_agent_files(client: &LettaClient, agent_id: &str) -> miette::Result<()> {
+    let agent_id = LettaId::from_str(agent_id).into_diagnostic()?;
+
+    let closed = client
+        .sources()
+        .agent_files(agent_id)
+        .close_all()
+        .await
+        .wrap_err("Failed to close files")?;
+
+    if closed.is_empty() {
+        println!("No open files.");
+    } else {
+        println!("Closed {} files: {}", closed.len(), closed.join(", "));
+    }
+
+    Ok(())
+}
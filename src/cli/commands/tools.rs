@@ -1,10 +1,16 @@
 //! Tools command implementations.
 
+use crate::tool::{
+    self, LocalTool, SyncAction, ToolDiagnostic, ToolSync, ToolTestCase, ToolTester,
+};
 use crate::types::common::LettaId;
-use crate::types::tool::{CreateToolRequest, ListToolsParams, SourceType};
+use crate::types::tool::{
+    CreateToolRequest, ListToolsParams, RunToolFromSourceResponse, SourceType, ToolExecutionStatus,
+};
 use crate::LettaClient;
 use clap::Parser;
-use miette::{miette, Context, IntoDiagnostic};
+use miette::{miette, Context, IntoDiagnostic, NamedSource};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 /// Tools-related commands.
@@ -62,6 +68,53 @@ pub enum ToolsCommand {
         #[arg(short = 'y', long)]
         yes: bool,
     },
+    /// Run a tool on the server without creating it
+    Test {
+        /// Path to Python file containing the tool function
+        python: PathBuf,
+        /// Path to JSON file containing the function schema (defaults to `<name>_schema.json` next to the Python file)
+        #[arg(short = 's', long)]
+        schema: Option<PathBuf>,
+        /// Arguments as a JSON object
+        #[arg(short = 'a', long, conflicts_with = "cases")]
+        args: Option<String>,
+        /// JSON file with an array of test cases to run instead
+        #[arg(short = 'c', long)]
+        cases: Option<PathBuf>,
+        /// Environment variable for the tool, as KEY=VALUE
+        #[arg(short = 'e', long = "env")]
+        env: Vec<String>,
+        /// Pip requirement to install, as PACKAGE or PACKAGE==VERSION
+        #[arg(long = "pip")]
+        pip: Vec<String>,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
+    /// Composio apps and actions
+    #[command(subcommand)]
+    Composio(super::composio::ComposioCommand),
+    /// Sync a directory of tool files with the server
+    Sync {
+        /// Directory containing `<name>.py` files and their `<name>_schema.json` schemas
+        dir: PathBuf,
+        /// Show what would change without changing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+        /// Delete custom tools on the server that have no local file
+        /// (without --tag, every such custom tool; requires --yes)
+        #[arg(long)]
+        delete: bool,
+        /// Tag synced tools, and only delete tools with this tag
+        #[arg(short = 't', long)]
+        tag: Option<String>,
+        /// Skip confirmation prompt before deleting tools
+        #[arg(short = 'y', long)]
+        yes: bool,
+        /// Output format (json, summary)
+        #[arg(short = 'o', long, default_value = "summary")]
+        output: String,
+    },
 }
 
 /// Handle tools commands.
@@ -95,6 +148,24 @@ pub async fn handle(cmd: ToolsCommand, client: &crate::LettaClient) -> miette::R
         }
         ToolsCommand::Get { id, output } => get_tool(client, &id, &output).await,
         ToolsCommand::Delete { id, yes } => delete_tool(client, &id, yes).await,
+        ToolsCommand::Test {
+            python,
+            schema,
+            args,
+            cases,
+            env,
+            pip,
+            output,
+        } => test_tool(client, python, schema, args, cases, env, pip, &output).await,
+        ToolsCommand::Composio(cmd) => super::composio::handle(cmd, client).await,
+        ToolsCommand::Sync {
+            dir,
+            dry_run,
+            delete,
+            tag,
+            yes,
+            output,
+        } => sync_tools(client, dir, dry_run, delete, tag, yes, &output).await,
     }
 }
 
@@ -156,211 +227,36 @@ async fn list_tools(
     Ok(())
 }
 
-/// Validate that a JSON schema matches Letta's expected tool schema format
-fn validate_tool_schema(schema: &serde_json::Value) -> miette::Result<()> {
-    // Check required top-level fields
-    if !schema.is_object() {
-        return Err(miette!("Schema must be a JSON object"));
-    }
-
-    let obj = schema.as_object().unwrap();
-
-    // Required field: name
-    if !obj.contains_key("name") {
-        return Err(miette!(
-            r#"Schema missing required field 'name'. Example:
-{{
-  "name": "my_tool",
-  "description": "Tool description",
-  "parameters": {{ ... }}
-}}"#
-        ));
-    }
-
-    if !obj["name"].is_string() {
-        return Err(miette!("Field 'name' must be a string"));
-    }
-
-    // Required field: parameters
-    if !obj.contains_key("parameters") {
-        return Err(miette!(
-            r#"Schema missing required field 'parameters'. Example:
-{{
-  "name": "my_tool",
-  "description": "...",
-  "parameters": {{
-    "type": "object",
-    "properties": {{ ... }},
-    "required": [ ... ]
-  }}
-}}"#
-        ));
-    }
-
-    let params = &obj["parameters"];
-    if !params.is_object() {
-        return Err(miette!("Field 'parameters' must be a JSON object"));
-    }
-
-    // Validate parameters structure
-    let params_obj = params.as_object().unwrap();
-
-    // Check for 'type' field in parameters
-    if !params_obj.contains_key("type") {
-        return Err(miette!(
-            r#"Parameters missing 'type' field. Should be:
-"parameters": {{
-  "type": "object",
-  "properties": {{ ... }}
-}}"#
-        ));
-    }
-
-    if params_obj["type"] != "object" {
-        return Err(miette!(
-            r#"Parameters 'type' should be "object", got: {}"#,
-            params_obj["type"]
-        ));
-    }
-
-    // Check for 'properties' field
-    if !params_obj.contains_key("properties") {
+/// Print tool diagnostics with the Python source attached, failing on errors.
+fn report_diagnostics(
+    diagnostics: Vec<ToolDiagnostic>,
+    python_path: &str,
+    source_code: &str,
+) -> miette::Result<()> {
+    let errors = print_diagnostics(diagnostics, python_path, source_code);
+    if errors > 0 {
         return Err(miette!(
-            r#"Parameters missing 'properties' field. Example:
-"parameters": {{
-  "type": "object",
-  "properties": {{
-    "arg1": {{ "type": "string", "description": "..." }},
-    "arg2": {{ "type": "integer", "description": "..." }}
-  }}
-}}"#
+            "Tool validation failed with {} error(s); fix them before creating the tool",
+            errors
         ));
     }
-
-    let properties = &params_obj["properties"];
-    if !properties.is_object() {
-        return Err(miette!("Parameters 'properties' must be a JSON object"));
-    }
-
-    // Validate each property
-    let props_obj = properties.as_object().unwrap();
-    for (prop_name, prop_value) in props_obj {
-        if !prop_value.is_object() {
-            return Err(miette!(
-                "Property '{}' must be an object with 'type' and 'description' fields",
-                prop_name
-            ));
-        }
-
-        let prop_obj = prop_value.as_object().unwrap();
-        if !prop_obj.contains_key("type") {
-            return Err(miette!(
-                r#"Property '{}' missing 'type' field. Example:
-"{}" : {{
-  "type": "string",
-  "description": "Description of {}"
-}}"#,
-                prop_name,
-                prop_name,
-                prop_name
-            ));
-        }
-
-        if !prop_obj.contains_key("description") {
-            return Err(miette!(
-                r#"Property '{}' missing 'description' field. Properties should have:
-{{
-  "type": "...",
-  "description": "What this parameter does"
-}}"#,
-                prop_name
-            ));
-        }
-    }
-
-    // Warn about optional but recommended fields
-    if !obj.contains_key("description") {
-        eprintln!("Warning: Schema missing 'description' field. It's recommended to include a tool description.");
-    }
-
     Ok(())
 }
 
-/// Validate that Python source code has proper docstring formatting for Letta
-fn validate_python_docstring(source_code: &str, function_name: &str) -> miette::Result<()> {
-    // Quick and dirty docstring validation
-
-    // Check if there's a docstring at all (look for triple quotes after function def)
-    let has_docstring = source_code.contains(r#"""""#) || source_code.contains("'''");
-    if !has_docstring {
-        return Err(miette!(
-            r#"Function '{}' missing docstring. Letta requires a docstring with Args and Returns sections:
-
-def {}(...):
-    """
-    Brief description.
-    
-    Args:
-        param1: Description
-        param2: Description
-    
-    Returns:
-        Description of return value
-    """
-    ..."#,
-            function_name,
-            function_name
-        ));
-    }
-
-    // Extract docstring content (quick and dirty - find content between first set of triple quotes)
-    let docstring_content = if let Some(start) = source_code.find(r#"""""#) {
-        if let Some(end) = source_code[start + 3..].find(r#"""""#) {
-            &source_code[start + 3..start + 3 + end]
-        } else {
-            return Err(miette!(
-                "Malformed docstring - missing closing triple quotes"
-            ));
-        }
-    } else if let Some(start) = source_code.find("'''") {
-        if let Some(end) = source_code[start + 3..].find("'''") {
-            &source_code[start + 3..start + 3 + end]
-        } else {
-            return Err(miette!(
-                "Malformed docstring - missing closing triple quotes"
-            ));
-        }
-    } else {
-        return Err(miette!("Could not find docstring"));
-    };
-
-    // Check for Args section
-    let has_args_section = docstring_content.contains("Args:")
-        || docstring_content.contains("Arguments:")
-        || docstring_content.contains("Parameters:");
-
-    if !has_args_section {
-        return Err(miette!(
-            r#"Docstring missing 'Args:' section. Letta requires parameter documentation:
-
-    Args:
-        param_name: Description of parameter
-        another_param: Description
-
-Without this, you'll get: "Parameter 'X' in function '{}' lacks a description in the docstring""#,
-            function_name
-        ));
-    }
-
-    // Check for Returns section (warning only)
-    let has_returns_section =
-        docstring_content.contains("Returns:") || docstring_content.contains("Return:");
-
-    if !has_returns_section {
-        eprintln!("Warning: Docstring missing 'Returns:' section. It's recommended to document return values.");
+/// Print tool diagnostics with the Python source attached, returning the
+/// number of errors.
+fn print_diagnostics(
+    diagnostics: Vec<ToolDiagnostic>,
+    python_path: &str,
+    source_code: &str,
+) -> usize {
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    for diagnostic in diagnostics {
+        let report = miette::Report::new(diagnostic)
+            .with_source_code(NamedSource::new(python_path, source_code.to_string()));
+        eprintln!("{:?}", report);
     }
-
-    Ok(())
+    errors
 }
 
 async fn create_tool(
@@ -387,23 +283,11 @@ async fn create_tool(
         .into_diagnostic()
         .wrap_err("Failed to parse JSON schema")?;
 
-    // Validate the schema format
-    validate_tool_schema(&json_schema)?;
-
-    // Extract function name for validation
-    let function_name = json_schema
-        .get("name")
-        .and_then(|n| n.as_str())
-        .unwrap_or("unknown");
-
-    // Validate Python docstring
-    validate_python_docstring(&source_code, function_name)?;
-
-    // Extract name from schema if present
+    // Extract name from schema if present; validation reports it if missing
     let tool_name = json_schema
         .get("name")
         .and_then(|n| n.as_str())
-        .ok_or_else(|| miette!("Schema must contain a 'name' field"))?
+        .unwrap_or("unknown")
         .to_string();
 
     // Extract description from schema if not provided
@@ -442,6 +326,9 @@ async fn create_tool(
         ..Default::default()
     };
 
+    // Check the source and schemas agree before the server does
+    report_diagnostics(tool::validate(&request), python_path, &request.source_code)?;
+
     if output != "json" {
         println!("Creating tool '{}'...", tool_name);
     }
@@ -575,3 +462,222 @@ async fn delete_tool(client: &LettaClient, id: &str, yes: bool) -> miette::Resul
 
     Ok(())
 }
+
+#[allow(clippy::too_many_arguments)]
+async fn sync_tools(
+    client: &LettaClient,
+    dir: PathBuf,
+    dry_run: bool,
+    delete: bool,
+    tag: Option<String>,
+    yes: bool,
+    output: &str,
+) -> miette::Result<()> {
+    if delete && tag.is_none() && !yes && !dry_run {
+        return Err(miette!(
+            "--delete without --tag deletes every custom tool on the server that is not in {}; \
+             pass --tag to limit it to synced tools, or --yes to confirm",
+            dir.display()
+        ));
+    }
+
+    let mut sync = ToolSync::new(dir).delete_missing(delete);
+    if let Some(tag) = tag {
+        sync = sync.tag(tag);
+    }
+
+    let plan = sync.plan(client).await?;
+
+    let mut errors = 0;
+    for change in &plan.changes {
+        if let Some(local) = &change.local {
+            errors += print_diagnostics(
+                local.diagnostics.clone(),
+                &local.path.display().to_string(),
+                &local.request.source_code,
+            );
+        }
+    }
+
+    if output == "json" {
+        println!("{}", serde_json::to_string_pretty(&plan).into_diagnostic()?);
+    } else {
+        print!("{}", plan);
+    }
+
+    if errors > 0 {
+        return Err(miette!(
+            "Tool validation failed with {} error(s); fix them before syncing",
+            errors
+        ));
+    }
+    if dry_run || plan.is_empty() {
+        return Ok(());
+    }
+
+    let deletions: Vec<&str> = plan
+        .changes
+        .iter()
+        .filter(|change| change.action == SyncAction::Delete)
+        .map(|change| change.name.as_str())
+        .collect();
+    if !deletions.is_empty() && !yes {
+        println!("These tools will be deleted:");
+        for name in &deletions {
+            println!("  {}", name);
+        }
+        print!(
+            "Are you sure you want to delete {} tool(s)? (y/N) ",
+            deletions.len()
+        );
+        use std::io::Write;
+        std::io::stdout().flush().into_diagnostic()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).into_diagnostic()?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let report = plan.apply(client).await?;
+    if output != "json" {
+        println!(
+            "Created {}, updated {}, deleted {} tool(s)",
+            report.created.len(),
+            report.updated.len(),
+            report.deleted.len()
+        );
+        if !report.conflicts.is_empty() {
+            println!(
+                "Skipped {} tool(s) that exist on the server without the tag: {}",
+                report.conflicts.len(),
+                report.conflicts.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn test_tool(
+    client: &LettaClient,
+    python: PathBuf,
+    schema: Option<PathBuf>,
+    args: Option<String>,
+    cases: Option<PathBuf>,
+    env: Vec<String>,
+    pip: Vec<String>,
+    output: &str,
+) -> miette::Result<()> {
+    let local = LocalTool::from_file(&python)?;
+    let mut request = local.request;
+    if let Some(schema_path) = schema {
+        let content = std::fs::read_to_string(&schema_path)
+            .into_diagnostic()
+            .wrap_err(format!(
+                "Failed to read schema file: {}",
+                schema_path.display()
+            ))?;
+        let json_schema: serde_json::Value = serde_json::from_str(&content)
+            .into_diagnostic()
+            .wrap_err("Failed to parse JSON schema")?;
+        request.args_json_schema = json_schema.get("parameters").cloned();
+        request.json_schema = Some(json_schema);
+    }
+
+    let mut tester = ToolTester::from_request(&request);
+    for var in env {
+        let (key, value) = var
+            .split_once('=')
+            .ok_or_else(|| miette!("Invalid environment variable '{}', expected KEY=VALUE", var))?;
+        tester = tester.env_var(key, value);
+    }
+    for requirement in pip {
+        tester = match requirement.split_once("==") {
+            Some((package, version)) => tester.pip_requirement(package, Some(version.to_string())),
+            None => tester.pip_requirement(requirement, None),
+        };
+    }
+
+    let Some(cases_path) = cases else {
+        let args: serde_json::Value = match args {
+            Some(args) => serde_json::from_str(&args)
+                .into_diagnostic()
+                .wrap_err("Failed to parse --args as JSON")?,
+            None => serde_json::json!({}),
+        };
+
+        let response = tester.run(client, args).await?;
+        if output == "json" {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response).into_diagnostic()?
+            );
+        } else {
+            print_run(&response);
+        }
+
+        if response.status == ToolExecutionStatus::Error {
+            return Err(miette!("Tool '{}' failed", local.name));
+        }
+        return Ok(());
+    };
+
+    let cases = ToolTestCase::load(&cases_path)?;
+    let results = tester.run_cases(client, &cases).await;
+    let failed = results.iter().filter(|r| !r.passed()).count();
+
+    if output == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&results).into_diagnostic()?
+        );
+    } else {
+        for result in &results {
+            if result.passed() {
+                println!("ok      {}", result.case.label());
+                continue;
+            }
+            println!("FAILED  {}", result.case.label());
+            for failure in &result.failures {
+                println!("        {}", failure);
+            }
+            if let Some(stderr) = result.response.as_ref().and_then(|r| r.stderr.as_ref()) {
+                for line in stderr.iter().flat_map(|s| s.lines()) {
+                    println!("        | {}", line);
+                }
+            }
+        }
+        println!("\n{} passed, {} failed", results.len() - failed, failed);
+    }
+
+    if failed > 0 {
+        return Err(miette!(
+            "{} of {} test case(s) failed",
+            failed,
+            results.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Print a tool run's status, return value and output streams.
+fn print_run(response: &RunToolFromSourceResponse) {
+    println!("Status: {:?}", response.status);
+    println!("Return: {}", response.tool_return);
+
+    for (label, lines) in [("stdout", &response.stdout), ("stderr", &response.stderr)] {
+        let Some(lines) = lines.as_ref().filter(|lines| !lines.is_empty()) else {
+            continue;
+        };
+        println!("\n--- {} ---", label);
+        for line in lines {
+            print!("{}", line);
+            if !line.ends_with('\n') {
+                println!();
+            }
+        }
+    }
+}
@@ -45,6 +45,9 @@ pub enum Command {
     /// Tool operations
     #[command(subcommand)]
     Tools(commands::tools::ToolsCommand),
+    /// MCP server operations
+    #[command(subcommand)]
+    Mcp(commands::mcp::McpCommand),
     /// Source operations
     #[command(subcommand)]
     Sources(commands::sources::SourcesCommand),
@@ -90,6 +93,7 @@ pub async fn run() -> miette::Result<()> {
         Command::Message(message_cmd) => commands::message::handle(message_cmd, &client).await?,
         Command::Memory(memory_cmd) => commands::memory::handle(memory_cmd, &client).await?,
         Command::Tools(tools_cmd) => commands::tools::handle(tools_cmd, &client).await?,
+        Command::Mcp(mcp_cmd) => commands::mcp::handle(mcp_cmd, &client).await?,
         Command::Sources(sources_cmd) => commands::sources::handle(sources_cmd, &client).await?,
         Command::Health => {
             println!("Checking health...");
@@ -179,6 +179,11 @@ impl LettaClient {
         crate::api::ToolApi::new(self)
     }
 
+    /// Get the MCP server API.
+    pub fn mcp(&self) -> crate::api::McpApi<'_> {
+        crate::api::McpApi::new(self)
+    }
+
     /// Get the health API.
     pub fn health(&self) -> crate::api::HealthApi<'_> {
         crate::api::HealthApi::new(self)
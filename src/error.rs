@@ -3,6 +3,7 @@
 //! This module provides comprehensive error types with rich diagnostics
 //! via [`miette`] for excellent error reporting and debugging experience.
 
+use crate::types::memory::Block;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::path::Path;
@@ -265,6 +266,15 @@ pub enum LettaError {
         id: String,
     },
 
+    /// A conditional update was rejected because the resource changed.
+    #[error("Conflict: {message}")]
+    Conflict {
+        /// Conflict error message.
+        message: String,
+        /// The block as it is now, when a block update conflicted.
+        current: Option<Box<Block>>,
+    },
+
     /// Validation error for request parameters.
     #[error("Validation error: {message}")]
     Validation {
@@ -293,6 +303,7 @@ impl miette::Diagnostic for LettaError {
             Self::RequestTimeout { .. } => Some(Box::new("letta::timeout")),
             Self::RateLimit { .. } => Some(Box::new("letta::rate_limit")),
             Self::NotFound { .. } => Some(Box::new("letta::not_found")),
+            Self::Conflict { .. } => Some(Box::new("letta::conflict")),
             Self::Validation { .. } => Some(Box::new("letta::validation")),
         }
     }
@@ -340,6 +351,9 @@ impl miette::Diagnostic for LettaError {
             Self::RateLimit { retry_after: Some(seconds) } => Some(Box::new(format!(
                 "Wait {seconds} seconds before making another request."
             ))),
+            Self::Conflict { .. } => Some(Box::new(
+                "Re-read the resource and apply the change again, or use `modify` to retry automatically.",
+            )),
             Self::Validation { field: Some(field), .. } => Some(Box::new(format!(
                 "Check the '{field}' field value and ensure it meets the API requirements."
             ))),
@@ -590,6 +604,14 @@ impl LettaError {
         }
     }
 
+    /// Create a new conflict error.
+    pub fn conflict(message: impl Into<String>, current: Option<Block>) -> Self {
+        Self::Conflict {
+            message: message.into(),
+            current: current.map(Box::new),
+        }
+    }
+
     /// Create a new request timeout error.
     pub fn request_timeout(seconds: u64) -> Self {
         Self::RequestTimeout { seconds }
@@ -648,6 +670,11 @@ impl LettaError {
         )
     }
 
+    /// Check if this is a conflict from a conditional update.
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, Self::Conflict { .. })
+    }
+
     /// Check if this is a validation error.
     pub fn is_validation_error(&self) -> bool {
         match self {
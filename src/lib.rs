@@ -13,6 +13,11 @@
 //! - **Type Safety**: Comprehensive type definitions for all API requests/responses
 //! - **Flexible Configuration**: Support for cloud and local deployments
 //! - **Rich Error Handling**: Detailed error types
+//! - **Tool Authoring**: Generate tool source and schemas from Rust types
+//!   (`#[derive(LettaTool)]` with the `derive` feature)
+//! - **MCP**: Configure MCP servers, test connections and import their tools
+//!   (serve Rust functions as MCP tools with the `mcp-server` feature)
+//! - **Memory Helpers**: Block history and rollback, memory templates, bulk archival import/export
 //! - **Well Tested**: Extensive test coverage with integration tests
 //!
 //! ## Installation
@@ -228,9 +233,9 @@
 //!     println!("Updated memory block: {}", updated.id.as_ref().unwrap());
 //!
 //!     // Add to archival memory
-//!     let archival_request = CreateArchivalMemoryRequest {
-//!         text: "Important: Project deadline is next Friday".to_string(),
-//!     };
+//!     let archival_request = CreateArchivalMemoryRequest::new(
+//!         "Important: Project deadline is next Friday",
+//!     );
 //!     let memories = client
 //!         .memory()
 //!         .create_archival_memory(&agent_id, archival_request)
@@ -419,6 +424,7 @@
 //! ## API Sections
 //!
 //! - [`agents`](crate::api::agents) - Agent management and lifecycle
+//! - [`agent_handle`](crate::api::agent_handle) - Agent-scoped blocks, tools, sources and identities
 //! - [`messages`](crate::api::messages) - Real-time messaging with streaming
 //! - [`memory`](crate::api::memory) - Memory management (core, archival, blocks)
 //! - [`tools`](crate::api::tools) - Tool management and execution
@@ -454,9 +460,11 @@ pub mod auth;
 pub mod client;
 pub mod environment;
 pub mod error;
+pub mod memory;
 pub mod pagination;
 pub mod retry;
 pub mod streaming;
+pub mod tool;
 pub mod types;
 pub mod utils;
 
@@ -0,0 +1,331 @@
+//! Export and import of archival memory as JSON Lines.
+//!
+//! An [`ArchivalExport`] pages through an agent's passages and writes one
+//! [`PassageRecord`] per line as they arrive, so large archives are never
+//! held in memory. An [`ArchivalImport`] reads such a file back into another
+//! (or the same) agent, skipping passages whose text the agent already has.
+//!
+//! ```no_run
+//! # use letta::{LettaClient, LettaId};
+//! # use letta::memory::{ArchivalExport, ArchivalImport};
+//! # async fn example(source: LettaClient, target: LettaClient, from: LettaId, to: LettaId) -> letta::LettaResult<()> {
+//! let file = std::io::BufWriter::new(std::fs::File::create("passages.jsonl")?);
+//! let count = ArchivalExport::new().write(&source, &from, file).await?;
+//! println!("exported {} passages", count);
+//!
+//! let file = std::io::BufReader::new(std::fs::File::open("passages.jsonl")?);
+//! let report = ArchivalImport::new().read(&target, &to, file).await?;
+//! println!("imported {}, skipped {}", report.passage_ids.len(), report.skipped);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::ingest::{ArchivalIngest, DEFAULT_CONCURRENCY};
+use crate::client::LettaClient;
+use crate::error::{LettaError, LettaResult};
+use crate::types::agent::EmbeddingConfig;
+use crate::types::common::{LettaId, Metadata, Timestamp};
+use crate::types::memory::{content_hash, CreateArchivalMemoryRequest, Passage};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+/// One line of an archival memory export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassageRecord {
+    /// ID of the passage on the server it was exported from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<LettaId>,
+    /// Passage text.
+    pub text: String,
+    /// Passage tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Passage metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    /// When the passage was created. Not restored on import.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<Timestamp>,
+    /// When the passage was last updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<Timestamp>,
+    /// Embedding vector, only present when exported with embeddings. Not
+    /// used on import, where the target agent's model embeds the text again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    /// Configuration of the model that produced `embedding`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_config: Option<EmbeddingConfig>,
+}
+
+impl PassageRecord {
+    /// Stable hash of the passage text, used to detect duplicates.
+    pub fn text_hash(&self) -> String {
+        content_hash(&self.text)
+    }
+}
+
+impl From<Passage> for PassageRecord {
+    fn from(passage: Passage) -> Self {
+        Self {
+            id: Some(passage.id),
+            text: passage.text,
+            tags: passage.tags,
+            metadata: passage.metadata,
+            created_at: passage.created_at,
+            updated_at: passage.updated_at,
+            embedding: passage.embedding,
+            embedding_config: passage.embedding_config,
+        }
+    }
+}
+
+/// Writes an agent's archival memory as JSON Lines.
+#[derive(Debug, Clone, Default)]
+pub struct ArchivalExport {
+    include_embeddings: bool,
+}
+
+impl ArchivalExport {
+    /// Create an export that leaves out embedding vectors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also write each passage's embedding vector and config.
+    pub fn include_embeddings(mut self, include: bool) -> Self {
+        self.include_embeddings = include;
+        self
+    }
+
+    /// Write every passage of the agent to `writer`, one per line, and return
+    /// how many were written.
+    ///
+    /// `writer` is a blocking [`Write`], called on the async task between
+    /// page requests. Buffer it (for example with [`std::io::BufWriter`]) so
+    /// each line is not a separate system call, and avoid writers that can
+    /// block for long, such as pipes to slow consumers.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to read passages with
+    /// * `agent_id` - The agent whose archival memory to export
+    /// * `writer` - Where to write the lines
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails or if writing fails.
+    pub async fn write<W: Write>(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        mut writer: W,
+    ) -> LettaResult<usize> {
+        let mut passages = client.memory().archival_paginated(agent_id, None);
+        let mut count = 0;
+        while let Some(passage) = passages.next().await {
+            let mut record = PassageRecord::from(passage?);
+            if !self.include_embeddings {
+                record.embedding = None;
+                record.embedding_config = None;
+            }
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
+            count += 1;
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+}
+
+/// A passage from an import file that could not be created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFailure {
+    /// Line number in the import file, counting from 1.
+    pub line: usize,
+    /// Why the passage could not be created.
+    pub error: String,
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// IDs of the created passages, in file order.
+    pub passage_ids: Vec<LettaId>,
+    /// Number of passages skipped as duplicates.
+    pub skipped: usize,
+    /// Passages that could not be created.
+    pub failed: Vec<ImportFailure>,
+}
+
+impl ImportReport {
+    /// Whether every passage was created or skipped.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Reads JSON Lines written by [`ArchivalExport`] into an agent's archival
+/// memory.
+///
+/// Passages are created from their text, tags and metadata only. The
+/// record's `id`, `created_at`, `updated_at`, `embedding` and
+/// `embedding_config` are ignored: the server assigns new IDs and timestamps
+/// and computes embeddings with the target agent's model.
+#[derive(Debug, Clone)]
+pub struct ArchivalImport {
+    dedup: bool,
+    concurrency: usize,
+}
+
+impl Default for ArchivalImport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchivalImport {
+    /// Create an import that skips duplicate texts.
+    pub fn new() -> Self {
+        Self {
+            dedup: true,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Whether to skip passages whose text hash matches one already in the
+    /// agent's archival memory or earlier in the file.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set how many passages are created at once (at least 1).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Create the passages read from `reader` in the agent's archival memory.
+    ///
+    /// Blank lines are ignored, and records with blank text are reported as
+    /// failed. The whole file is parsed before anything is created, so a
+    /// malformed line aborts the import without side effects. Like
+    /// [`ArchivalExport::write`], `reader` is read with blocking calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to create passages with
+    /// * `agent_id` - The agent whose archival memory receives the passages
+    /// * `reader` - The JSON Lines to read
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if reading fails, if a line is not
+    /// a valid [`PassageRecord`], or if the agent's existing passages cannot be
+    /// listed for deduplication.
+    pub async fn read<R: BufRead>(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        reader: R,
+    ) -> LettaResult<ImportReport> {
+        let mut report = ImportReport::default();
+        let mut records = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: PassageRecord = serde_json::from_str(&line).map_err(|e| {
+                LettaError::validation(format!("invalid passage on line {}: {}", i + 1, e))
+            })?;
+            if record.text.trim().is_empty() {
+                report.failed.push(ImportFailure {
+                    line: i + 1,
+                    error: "passage text is empty".to_string(),
+                });
+                continue;
+            }
+            records.push((i + 1, record));
+        }
+
+        if self.dedup {
+            let mut seen = HashSet::new();
+            let mut existing = client.memory().archival_paginated(agent_id, None);
+            while let Some(passage) = existing.next().await {
+                seen.insert(content_hash(&passage?.text));
+            }
+            let before = records.len();
+            records.retain(|(_, record)| seen.insert(record.text_hash()));
+            report.skipped = before - records.len();
+        }
+
+        let lines: Vec<usize> = records.iter().map(|(line, _)| *line).collect();
+        let ingest = ArchivalIngest::new()
+            .concurrency(self.concurrency)
+            .run_requests(
+                client,
+                agent_id,
+                records
+                    .into_iter()
+                    .map(|(_, record)| CreateArchivalMemoryRequest {
+                        text: record.text,
+                        tags: record.tags,
+                        metadata: record.metadata,
+                    }),
+            )
+            .await;
+
+        report.passage_ids = ingest.passage_ids;
+        report
+            .failed
+            .extend(ingest.failed.into_iter().map(|f| ImportFailure {
+                line: lines[f.source_index],
+                error: f.error,
+            }));
+        report.failed.sort_by_key(|failure| failure.line);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_passage_record_roundtrip() {
+        let passage: Passage = serde_json::from_value(serde_json::json!({
+            "id": "passage-550e8400-e29b-41d4-a716-446655440000",
+            "agent_id": "agent-550e8400-e29b-41d4-a716-446655440000",
+            "text": "The user likes tea.",
+            "tags": ["drinks"],
+            "metadata": {"source": "notes"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "embedding": [0.5, 0.25],
+        }))
+        .unwrap();
+
+        let record = PassageRecord::from(passage);
+        assert_eq!(
+            record.id,
+            Some(LettaId::from_str("passage-550e8400-e29b-41d4-a716-446655440000").unwrap())
+        );
+        assert_eq!(record.embedding, Some(vec![0.5, 0.25]));
+        assert_eq!(record.tags, Some(vec!["drinks".to_string()]));
+
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(!line.contains('\n'));
+        let parsed: PassageRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), line);
+        assert_eq!(parsed.text_hash(), content_hash("The user likes tea."));
+
+        // Only text is required.
+        let minimal: PassageRecord = serde_json::from_str(r#"{"text":"hi"}"#).unwrap();
+        assert_eq!(minimal.id, None);
+        assert_eq!(serde_json::to_string(&minimal).unwrap(), r#"{"text":"hi"}"#);
+    }
+}
@@ -0,0 +1,571 @@
+//! Client-side history of memory block values.
+//!
+//! Letta only keeps a block's current value. A [`BlockHistory`] records
+//! snapshots of blocks in a [`BlockHistoryStore`] (one JSON file per block by
+//! default) whenever it is asked to: after listing an agent's blocks with
+//! [`snapshot_agent`](BlockHistory::snapshot_agent), or around an update made
+//! through [`update`](BlockHistory::update). Recorded versions can then be
+//! compared with [`diff`](BlockHistory::diff) and brought back with
+//! [`restore`](BlockHistory::restore).
+//!
+//! ```no_run
+//! # use letta::{LettaClient, LettaId};
+//! # use letta::memory::BlockHistory;
+//! # async fn example(client: LettaClient, agent_id: LettaId) -> letta::LettaResult<()> {
+//! let history = BlockHistory::open(".letta/history");
+//!
+//! // Run periodically: only blocks whose value changed get a new version.
+//! for snapshot in history.snapshot_agent(&client, &agent_id).await? {
+//!     if snapshot.version > 1 {
+//!         let diff = history.diff(&snapshot.block_id, snapshot.version - 1, snapshot.version)?;
+//!         println!("{} changed:\n{}", snapshot.label, diff);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::LettaClient;
+use crate::error::{LettaError, LettaResult};
+use crate::types::common::{LettaId, Timestamp};
+use crate::types::memory::{Block, UpdateBlockRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A recorded value of a memory block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockSnapshot {
+    /// The block's ID.
+    pub block_id: LettaId,
+    /// Version number, counting from 1 for the first snapshot of the block.
+    pub version: u32,
+    /// The block's label.
+    pub label: String,
+    /// The block's value.
+    pub value: String,
+    /// The block's `updated_at`, if the server reported it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<Timestamp>,
+    /// When the snapshot was recorded.
+    pub recorded_at: Timestamp,
+}
+
+/// Where a [`BlockHistory`] keeps its snapshots.
+pub trait BlockHistoryStore: Send + Sync {
+    /// All snapshots of a block, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the snapshots cannot be read.
+    fn load(&self, block_id: &LettaId) -> LettaResult<Vec<BlockSnapshot>>;
+
+    /// Add a snapshot after the block's existing ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the snapshot cannot be written.
+    fn append(&self, snapshot: &BlockSnapshot) -> LettaResult<()>;
+
+    /// The IDs of all blocks with snapshots.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the store cannot be read.
+    fn block_ids(&self) -> LettaResult<Vec<LettaId>>;
+}
+
+/// Stores each block's snapshots as a JSON array in `<dir>/<block_id>.json`.
+///
+/// Writes replace the file atomically, but two processes appending to the
+/// same block at once can lose a snapshot.
+#[derive(Debug)]
+pub struct JsonFileStore {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileStore {
+    /// Create a store in `dir`, which is created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The directory holding the snapshot files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, block_id: &LettaId) -> PathBuf {
+        self.dir.join(format!("{}.json", block_id))
+    }
+}
+
+impl BlockHistoryStore for JsonFileStore {
+    fn load(&self, block_id: &LettaId) -> LettaResult<Vec<BlockSnapshot>> {
+        match std::fs::read_to_string(self.path(block_id)) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn append(&self, snapshot: &BlockSnapshot) -> LettaResult<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut snapshots = self.load(&snapshot.block_id)?;
+        snapshots.push(snapshot.clone());
+
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(&snapshot.block_id);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&snapshots)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn block_ids(&self) -> LettaResult<Vec<LettaId>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut ids = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(id) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| LettaId::from_str(stem).ok())
+            {
+                ids.push(id);
+            }
+        }
+        ids.sort_by_key(|id| id.to_string());
+        Ok(ids)
+    }
+}
+
+/// Keeps snapshots in memory, e.g. for tests or short-lived processes.
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    snapshots: Mutex<HashMap<LettaId, Vec<BlockSnapshot>>>,
+}
+
+impl InMemoryStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlockHistoryStore for InMemoryStore {
+    fn load(&self, block_id: &LettaId) -> LettaResult<Vec<BlockSnapshot>> {
+        let snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(snapshots.get(block_id).cloned().unwrap_or_default())
+    }
+
+    fn append(&self, snapshot: &BlockSnapshot) -> LettaResult<()> {
+        let mut snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        snapshots
+            .entry(snapshot.block_id.clone())
+            .or_default()
+            .push(snapshot.clone());
+        Ok(())
+    }
+
+    fn block_ids(&self) -> LettaResult<Vec<LettaId>> {
+        let snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ids: Vec<LettaId> = snapshots.keys().cloned().collect();
+        ids.sort_by_key(|id| id.to_string());
+        Ok(ids)
+    }
+}
+
+/// A record of memory block values over time.
+#[derive(Debug)]
+pub struct BlockHistory<S = JsonFileStore> {
+    store: S,
+}
+
+impl BlockHistory<JsonFileStore> {
+    /// Keep history in JSON files under `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        Self::new(JsonFileStore::new(dir))
+    }
+}
+
+impl<S: BlockHistoryStore> BlockHistory<S> {
+    /// Keep history in `store`.
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// The underlying store.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Record a block's current value.
+    ///
+    /// Nothing is recorded if the value and label match the latest snapshot.
+    ///
+    /// # Returns
+    ///
+    /// Returns the new snapshot, or `None` if the block was unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the block has no ID or the
+    /// store fails.
+    pub fn record(&self, block: &Block) -> LettaResult<Option<BlockSnapshot>> {
+        let block_id = block
+            .id
+            .as_ref()
+            .ok_or_else(|| LettaError::validation_field("block has no ID", "id"))?;
+        let latest = self.store.load(block_id)?.pop();
+        if let Some(latest) = &latest {
+            if latest.value == block.value && latest.label == block.label {
+                return Ok(None);
+            }
+        }
+
+        let snapshot = BlockSnapshot {
+            block_id: block_id.clone(),
+            version: latest.map_or(1, |latest| latest.version + 1),
+            label: block.label.clone(),
+            value: block.value.clone(),
+            updated_at: block.updated_at,
+            recorded_at: chrono::Utc::now(),
+        };
+        self.store.append(&snapshot)?;
+        Ok(Some(snapshot))
+    }
+
+    /// All recorded versions of a block, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the store fails.
+    pub fn versions(&self, block_id: &LettaId) -> LettaResult<Vec<BlockSnapshot>> {
+        self.store.load(block_id)
+    }
+
+    /// One recorded version of a block.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::NotFound`] if the version was never recorded, or
+    /// a [crate::error::LettaError] if the store fails.
+    pub fn version(&self, block_id: &LettaId, version: u32) -> LettaResult<BlockSnapshot> {
+        self.store
+            .load(block_id)?
+            .into_iter()
+            .find(|snapshot| snapshot.version == version)
+            .ok_or_else(|| {
+                LettaError::not_found("Block version", format!("{} v{}", block_id, version))
+            })
+    }
+
+    /// The most recently recorded version of a block, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the store fails.
+    pub fn latest(&self, block_id: &LettaId) -> LettaResult<Option<BlockSnapshot>> {
+        Ok(self.store.load(block_id)?.pop())
+    }
+
+    /// Line diff between two recorded versions of a block.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::NotFound`] if either version was never recorded,
+    /// or a [crate::error::LettaError] if the store fails.
+    pub fn diff(&self, block_id: &LettaId, from: u32, to: u32) -> LettaResult<BlockDiff> {
+        let from = self.version(block_id, from)?;
+        let to = self.version(block_id, to)?;
+        Ok(BlockDiff::between(&from.value, &to.value))
+    }
+
+    /// Line diff from a block's latest recorded version to its value in `block`.
+    ///
+    /// A block that was never recorded diffs against an empty value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the block has no ID or the
+    /// store fails.
+    pub fn diff_latest(&self, block: &Block) -> LettaResult<BlockDiff> {
+        let block_id = block
+            .id
+            .as_ref()
+            .ok_or_else(|| LettaError::validation_field("block has no ID", "id"))?;
+        let latest = self.latest(block_id)?;
+        Ok(BlockDiff::between(
+            latest.as_ref().map_or("", |latest| latest.value.as_str()),
+            &block.value,
+        ))
+    }
+
+    /// Record every core memory block of an agent.
+    ///
+    /// # Returns
+    ///
+    /// Returns the snapshots of the blocks that changed since they were last
+    /// recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or the store
+    /// fails.
+    pub async fn snapshot_agent(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+    ) -> LettaResult<Vec<BlockSnapshot>> {
+        let blocks = client.memory().list_core_memory_blocks(agent_id).await?;
+        let mut recorded = Vec::new();
+        for block in &blocks {
+            recorded.extend(self.record(block)?);
+        }
+        Ok(recorded)
+    }
+
+    /// Update a block, recording its value before and after.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to update the block with
+    /// * `block_id` - The ID of the block to update
+    /// * `request` - The update request
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a request fails or the store
+    /// fails.
+    pub async fn update(
+        &self,
+        client: &LettaClient,
+        block_id: &LettaId,
+        request: UpdateBlockRequest,
+    ) -> LettaResult<Block> {
+        self.record(&client.blocks().get(block_id).await?)?;
+        let block = client.blocks().update(block_id, request).await?;
+        self.record(&block)?;
+        Ok(block)
+    }
+
+    /// Restore a block to a recorded version.
+    ///
+    /// The value being replaced and the restored value are both recorded, so
+    /// a restore can itself be undone.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::NotFound`] if the version was never recorded, or
+    /// a [crate::error::LettaError] if a request fails or the store fails.
+    pub async fn restore(
+        &self,
+        client: &LettaClient,
+        block_id: &LettaId,
+        version: u32,
+    ) -> LettaResult<Block> {
+        let snapshot = self.version(block_id, version)?;
+        let request = UpdateBlockRequest {
+            value: Some(snapshot.value),
+            ..Default::default()
+        };
+        self.update(client, block_id, request).await
+    }
+}
+
+/// One line of a [`BlockDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", content = "line", rename_all = "snake_case")]
+pub enum DiffLine {
+    /// A line in both versions.
+    Same(String),
+    /// A line only in the newer version.
+    Added(String),
+    /// A line only in the older version.
+    Removed(String),
+}
+
+/// Line-level difference between two block values.
+///
+/// Displays in unified diff style, with each line prefixed by ` `, `+` or `-`.
+///
+/// # Example
+/// ```
+/// # use letta::memory::BlockDiff;
+/// let diff = BlockDiff::between("Name: Alice\nLikes tea", "Name: Alice\nLikes coffee");
+/// assert_eq!(diff.to_string(), " Name: Alice\n-Likes tea\n+Likes coffee\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BlockDiff {
+    /// The lines of both versions, in order.
+    pub lines: Vec<DiffLine>,
+}
+
+impl BlockDiff {
+    /// Diff two values line by line.
+    pub fn between(old: &str, new: &str) -> Self {
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+
+        // Only the middle between a shared prefix and suffix needs the table.
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let a = &old[prefix..old.len() - suffix];
+        let b = &new[prefix..new.len() - suffix];
+
+        // lcs[i][j] is the longest common subsequence of a[i..] and b[j..].
+        let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i] == b[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut lines: Vec<DiffLine> = old[..prefix]
+            .iter()
+            .map(|line| DiffLine::Same(line.to_string()))
+            .collect();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                lines.push(DiffLine::Same(a[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                lines.push(DiffLine::Removed(a[i].to_string()));
+                i += 1;
+            } else {
+                lines.push(DiffLine::Added(b[j].to_string()));
+                j += 1;
+            }
+        }
+        lines.extend(
+            old[old.len() - suffix..]
+                .iter()
+                .map(|line| DiffLine::Same(line.to_string())),
+        );
+
+        Self { lines }
+    }
+
+    /// Whether the two values differ.
+    pub fn has_changes(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| !matches!(line, DiffLine::Same(_)))
+    }
+
+    /// Number of added lines.
+    pub fn added(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Added(_)))
+            .count()
+    }
+
+    /// Number of removed lines.
+    pub fn removed(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| matches!(line, DiffLine::Removed(_)))
+            .count()
+    }
+}
+
+impl fmt::Display for BlockDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                DiffLine::Same(text) => writeln!(f, " {}", text)?,
+                DiffLine::Added(text) => writeln!(f, "+{}", text)?,
+                DiffLine::Removed(text) => writeln!(f, "-{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(value: &str) -> Block {
+        let mut block = Block::persona(value);
+        block.id = Some(LettaId::from_str("block-550e8400-e29b-41d4-a716-446655440000").unwrap());
+        block
+    }
+
+    #[test]
+    fn test_block_diff() {
+        let diff = BlockDiff::between("a\nb\nc\nd\ne", "a\nc\nx\nd\ne\nf");
+        assert_eq!(diff.to_string(), " a\n-b\n c\n+x\n d\n e\n+f\n");
+        assert_eq!((diff.added(), diff.removed()), (2, 1));
+        assert!(diff.has_changes());
+
+        assert!(!BlockDiff::between("same\n", "same").has_changes());
+        assert_eq!(BlockDiff::between("", "new").to_string(), "+new\n");
+    }
+
+    #[test]
+    fn test_block_history_stores() {
+        let dir = std::env::temp_dir().join(format!("letta-block-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let memory = BlockHistory::new(InMemoryStore::new());
+        let files = BlockHistory::open(&dir);
+        for (value, recorded) in [("v1", true), ("v1", false), ("v2", true), ("v1", true)] {
+            let block = block(value);
+            assert_eq!(memory.record(&block).unwrap().is_some(), recorded);
+            assert_eq!(files.record(&block).unwrap().is_some(), recorded);
+        }
+
+        let block_id = block("").id.unwrap();
+        for versions in [
+            memory.versions(&block_id).unwrap(),
+            files.versions(&block_id).unwrap(),
+        ] {
+            let values: Vec<(u32, &str)> = versions
+                .iter()
+                .map(|s| (s.version, s.value.as_str()))
+                .collect();
+            assert_eq!(values, vec![(1, "v1"), (2, "v2"), (3, "v1")]);
+        }
+        assert_eq!(files.store().block_ids().unwrap(), vec![block_id.clone()]);
+
+        assert_eq!(
+            files.diff(&block_id, 1, 2).unwrap().to_string(),
+            "-v1\n+v2\n"
+        );
+        assert!(!files.diff_latest(&block("v1")).unwrap().has_changes());
+        assert!(matches!(
+            files.version(&block_id, 9),
+            Err(LettaError::NotFound { .. })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
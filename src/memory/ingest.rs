@@ -0,0 +1,409 @@
+//! Bulk loading of archival memory.
+//!
+//! [`MemoryApi::create_archival_memory`](crate::api::MemoryApi::create_archival_memory)
+//! inserts one passage per request. An [`ArchivalIngest`] takes any number of
+//! texts, optionally splits long ones into overlapping [`Chunking`] windows,
+//! and inserts the pieces with a bounded number of requests in flight. Each
+//! request goes through the client's usual retry handling, and a failed
+//! insert is recorded in the [`IngestReport`] instead of stopping the run.
+//!
+//! ```no_run
+//! # use letta::{LettaClient, LettaId};
+//! # use letta::memory::{ArchivalIngest, Chunking};
+//! # async fn example(client: LettaClient, agent_id: LettaId, notes: Vec<String>) {
+//! let report = ArchivalIngest::new()
+//!     .chunking(Chunking::new(2000, 200))
+//!     .concurrency(8)
+//!     .on_progress(|p| eprintln!("{}/{} inserted", p.done, p.total.unwrap_or(0)))
+//!     .run(&client, &agent_id, notes)
+//!     .await;
+//!
+//! println!("created {} passages", report.passage_ids.len());
+//! for failure in &report.failed {
+//!     eprintln!("text {} chunk {}: {}", failure.source_index, failure.chunk_index, failure.error);
+//! }
+//! # }
+//! ```
+
+use crate::client::LettaClient;
+use crate::types::common::{LettaId, Metadata};
+use crate::types::memory::CreateArchivalMemoryRequest;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Number of inserts [`ArchivalIngest`] runs at once unless configured.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How to split long texts into passages.
+///
+/// Sizes are counted in characters. A chunk ends at the last whitespace in
+/// the second half of its window when there is one, so words are rarely cut,
+/// and starts `overlap` characters before the previous chunk ended, moved
+/// forward to the next word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunking {
+    /// Maximum characters per chunk.
+    pub size: usize,
+    /// Characters repeated from the end of the previous chunk.
+    pub overlap: usize,
+}
+
+impl Chunking {
+    /// Create chunking options. `size` is raised to at least 1 and `overlap`
+    /// is capped below `size` when splitting.
+    pub fn new(size: usize, overlap: usize) -> Self {
+        Self { size, overlap }
+    }
+
+    /// Split `text` into trimmed, non-empty chunks.
+    pub fn split(&self, text: &str) -> Vec<String> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let len = chars.len();
+        let size = self.size.max(1);
+        let overlap = self.overlap.min(size - 1);
+        let byte_at = |i: usize| if i == len { text.len() } else { chars[i].0 };
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < len {
+            let mut end = (start + size).min(len);
+            if end < len {
+                if let Some(ws) = (start + size / 2..end)
+                    .rev()
+                    .find(|&i| chars[i].1.is_whitespace())
+                {
+                    end = ws + 1;
+                }
+            }
+
+            let chunk = text[byte_at(start)..byte_at(end)].trim();
+            if !chunk.is_empty() {
+                chunks.push(chunk.to_string());
+            }
+            if end == len {
+                break;
+            }
+
+            let mut next = end.saturating_sub(overlap).max(start + 1);
+            while next < end && !chars[next - 1].1.is_whitespace() {
+                next += 1;
+            }
+            start = next;
+        }
+        chunks
+    }
+}
+
+/// Progress of an ingest run, passed to the [`ArchivalIngest::on_progress`]
+/// callback after each insert finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngestProgress {
+    /// Inserts finished so far, including failed ones.
+    pub done: usize,
+    /// Inserts that failed so far.
+    pub failed: usize,
+    /// Total number of chunks, when known up front.
+    pub total: Option<usize>,
+}
+
+/// A chunk that could not be inserted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestFailure {
+    /// Position of the source text in the input.
+    pub source_index: usize,
+    /// Position of the chunk within its source text.
+    pub chunk_index: usize,
+    /// The chunk's text.
+    pub text: String,
+    /// Why the insert failed.
+    pub error: String,
+}
+
+/// Outcome of an ingest run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestReport {
+    /// IDs of the created passages, in input order.
+    pub passage_ids: Vec<LettaId>,
+    /// Chunks that could not be inserted, in input order.
+    pub failed: Vec<IngestFailure>,
+}
+
+impl IngestReport {
+    /// Whether every chunk was inserted.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+struct Chunk {
+    source_index: usize,
+    chunk_index: usize,
+    request: CreateArchivalMemoryRequest,
+}
+
+type ProgressFn = Box<dyn Fn(&IngestProgress) + Send + Sync>;
+
+/// Inserts many texts into an agent's archival memory.
+pub struct ArchivalIngest {
+    chunking: Option<Chunking>,
+    concurrency: usize,
+    tags: Option<Vec<String>>,
+    metadata: Option<Metadata>,
+    progress: Option<ProgressFn>,
+}
+
+impl fmt::Debug for ArchivalIngest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivalIngest")
+            .field("chunking", &self.chunking)
+            .field("concurrency", &self.concurrency)
+            .field("tags", &self.tags)
+            .field("metadata", &self.metadata)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl Default for ArchivalIngest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchivalIngest {
+    /// Create an ingest that inserts each text whole, [`DEFAULT_CONCURRENCY`]
+    /// at a time.
+    pub fn new() -> Self {
+        Self {
+            chunking: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            tags: None,
+            metadata: None,
+            progress: None,
+        }
+    }
+
+    /// Split texts before inserting them.
+    pub fn chunking(mut self, chunking: Chunking) -> Self {
+        self.chunking = Some(chunking);
+        self
+    }
+
+    /// Set how many inserts run at once (at least 1).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Attach tags to every passage created from plain texts.
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Attach metadata to every passage created from plain texts.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Call `f` after each insert finishes.
+    pub fn on_progress(mut self, f: impl Fn(&IngestProgress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Insert `texts` into the agent's archival memory.
+    ///
+    /// Blank texts and chunks are skipped. Since the texts are chunked up
+    /// front, progress reports include the total.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to insert with
+    /// * `agent_id` - The agent whose archival memory receives the passages
+    /// * `texts` - The texts to insert
+    pub async fn run<I, T>(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        texts: I,
+    ) -> IngestReport
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let requests = texts.into_iter().map(|text| self.request(text.into()));
+        self.run_requests(client, agent_id, requests).await
+    }
+
+    /// Insert passages with their own tags and metadata into the agent's
+    /// archival memory.
+    ///
+    /// Each chunk of a request's text keeps the request's tags and metadata;
+    /// the tags and metadata set on the ingest are not applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to insert with
+    /// * `agent_id` - The agent whose archival memory receives the passages
+    /// * `requests` - The passages to insert
+    pub async fn run_requests<I>(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        requests: I,
+    ) -> IngestReport
+    where
+        I: IntoIterator<Item = CreateArchivalMemoryRequest>,
+    {
+        let chunks: Vec<Chunk> = requests
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, request)| self.split(i, request))
+            .collect();
+        let total = chunks.len();
+        self.insert(client, agent_id, stream::iter(chunks), Some(total))
+            .await
+    }
+
+    /// Insert texts from a stream into the agent's archival memory.
+    ///
+    /// Texts are chunked as they arrive, so progress reports have no total.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to insert with
+    /// * `agent_id` - The agent whose archival memory receives the passages
+    /// * `texts` - The texts to insert
+    pub async fn run_stream<S, T>(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        texts: S,
+    ) -> IngestReport
+    where
+        S: Stream<Item = T>,
+        T: Into<String>,
+    {
+        let chunks = texts
+            .enumerate()
+            .flat_map(|(i, text)| stream::iter(self.split(i, self.request(text.into()))));
+        self.insert(client, agent_id, chunks, None).await
+    }
+
+    fn request(&self, text: String) -> CreateArchivalMemoryRequest {
+        CreateArchivalMemoryRequest {
+            text,
+            tags: self.tags.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    fn split(&self, source_index: usize, request: CreateArchivalMemoryRequest) -> Vec<Chunk> {
+        let pieces = match &self.chunking {
+            Some(chunking) => chunking.split(&request.text),
+            None if request.text.trim().is_empty() => Vec::new(),
+            None => vec![request.text.clone()],
+        };
+        pieces
+            .into_iter()
+            .enumerate()
+            .map(|(chunk_index, text)| Chunk {
+                source_index,
+                chunk_index,
+                request: CreateArchivalMemoryRequest {
+                    text,
+                    ..request.clone()
+                },
+            })
+            .collect()
+    }
+
+    async fn insert(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        chunks: impl Stream<Item = Chunk>,
+        total: Option<usize>,
+    ) -> IngestReport {
+        let results = chunks
+            .map(|chunk| async move {
+                let result = client
+                    .memory()
+                    .create_archival_memory(agent_id, chunk.request.clone())
+                    .await;
+                (chunk, result)
+            })
+            .buffer_unordered(self.concurrency);
+        futures::pin_mut!(results);
+
+        let mut progress = IngestProgress {
+            done: 0,
+            failed: 0,
+            total,
+        };
+        let mut created = Vec::new();
+        let mut report = IngestReport::default();
+        while let Some((chunk, result)) = results.next().await {
+            progress.done += 1;
+            match result {
+                Ok(passages) => created.push((
+                    (chunk.source_index, chunk.chunk_index),
+                    passages.into_iter().map(|p| p.id).collect::<Vec<_>>(),
+                )),
+                Err(e) => {
+                    progress.failed += 1;
+                    report.failed.push(IngestFailure {
+                        source_index: chunk.source_index,
+                        chunk_index: chunk.chunk_index,
+                        text: chunk.request.text,
+                        error: e.to_string(),
+                    });
+                }
+            }
+            if let Some(f) = &self.progress {
+                f(&progress);
+            }
+        }
+
+        created.sort_by_key(|(position, _)| *position);
+        report.passage_ids = created.into_iter().flat_map(|(_, ids)| ids).collect();
+        report
+            .failed
+            .sort_by_key(|f| (f.source_index, f.chunk_index));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunking_split() {
+        let chunks = Chunking::new(16, 6).split("the quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            chunks,
+            [
+                "the quick brown",
+                "brown fox jumps",
+                "jumps over the",
+                "the lazy dog"
+            ]
+        );
+        assert!(chunks.iter().all(|c| c.chars().count() <= 16));
+
+        assert_eq!(Chunking::new(100, 10).split("  short  "), ["short"]);
+        assert!(Chunking::new(10, 2).split(" \n ").is_empty());
+
+        // Words longer than the window are cut, without overlap mid-word.
+        let chunks = Chunking::new(4, 10).split("ééééééééé");
+        assert_eq!(chunks, ["éééé", "éééé", "é"]);
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers for managing agent memory.
+//!
+//! The [`history`] module keeps a client-side record of memory block values,
+//! so an unexpected edit to a block can be inspected with a line diff and
+//! rolled back. The [`ingest`] module loads many texts into archival memory
+//! at once, and [`archive`] moves archival memory between agents or servers
+//! as JSON Lines. [`template`] renders `{{variable}}` placeholders in memory
+//! blocks before an agent is created.
+
+pub mod archive;
+pub mod history;
+pub mod ingest;
+pub mod template;
+
+pub use archive::{ArchivalExport, ArchivalImport, ImportFailure, ImportReport, PassageRecord};
+pub use history::{
+    BlockDiff, BlockHistory, BlockHistoryStore, BlockSnapshot, DiffLine, InMemoryStore,
+    JsonFileStore,
+};
+pub use ingest::{ArchivalIngest, Chunking, IngestFailure, IngestProgress, IngestReport};
+pub use template::MemoryTemplate;
@@ -0,0 +1,274 @@
+//! Client-side rendering of `{{variable}}` placeholders in memory blocks.
+//!
+//! Agents created from templates get their memory variables filled in by the
+//! server, and a typo in a variable name only shows up as a literal
+//! `{{persona_name}}` in the agent's memory. A [`MemoryTemplate`] parses the
+//! block values up front, lists the variables they need, and renders them
+//! strictly: a missing or unused variable is an error, and so is a rendered
+//! value over its block's [`limit`](Block::limit).
+//!
+//! ```
+//! # use letta::memory::MemoryTemplate;
+//! # use letta::types::{Block, CreateAgentRequest};
+//! # use std::collections::HashMap;
+//! # fn example() -> letta::LettaResult<()> {
+//! let template = MemoryTemplate::parse(vec![
+//!     Block::persona("I am {{agent_name}}, a support agent for {{company}}."),
+//!     Block::human("The user's name is {{user_name}}."),
+//! ])?;
+//! assert_eq!(template.variables(), ["agent_name", "company", "user_name"]);
+//!
+//! let vars = HashMap::from([
+//!     ("agent_name".to_string(), "Sam".to_string()),
+//!     ("company".to_string(), "Acme".to_string()),
+//!     ("user_name".to_string(), "Ada".to_string()),
+//! ]);
+//! let request = template
+//!     .apply(CreateAgentRequest::builder().name("support"), &vars)?
+//!     .build();
+//! assert_eq!(
+//!     request.memory_blocks.unwrap()[0].value,
+//!     "I am Sam, a support agent for Acme."
+//! );
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+
+use crate::error::{LettaError, LettaResult};
+use crate::types::agent::CreateAgentRequestBuilder;
+use crate::types::memory::Block;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+}
+
+/// A block whose value has been parsed into text and variables.
+#[derive(Debug, Clone)]
+struct BlockTemplate {
+    block: Block,
+    segments: Vec<Segment>,
+}
+
+impl BlockTemplate {
+    fn parse(block: Block) -> LettaResult<Self> {
+        let mut segments = Vec::new();
+        let mut rest = block.value.as_str();
+        while let Some(open) = rest.find("{{") {
+            if open > 0 {
+                segments.push(Segment::Text(rest[..open].to_string()));
+            }
+            let after = &rest[open + 2..];
+            let close = after.find("}}").ok_or_else(|| {
+                LettaError::validation_field(
+                    format!("block '{}' has an unclosed '{{{{'", block.label),
+                    "value",
+                )
+            })?;
+            let name = after[..close].trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(LettaError::validation_field(
+                    format!(
+                        "block '{}' has an invalid variable '{{{{{}}}}}'",
+                        block.label,
+                        &after[..close]
+                    ),
+                    "value",
+                ));
+            }
+            segments.push(Segment::Variable(name.to_string()));
+            rest = &after[close + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        Ok(Self { block, segments })
+    }
+
+    fn variables(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.as_str()),
+            Segment::Text(_) => None,
+        })
+    }
+
+    fn render(&self, vars: &HashMap<String, String>) -> Option<String> {
+        let mut value = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => value.push_str(text),
+                Segment::Variable(name) => value.push_str(vars.get(name)?),
+            }
+        }
+        Some(value)
+    }
+}
+
+/// Memory blocks containing `{{variable}}` placeholders.
+#[derive(Debug, Clone)]
+pub struct MemoryTemplate {
+    blocks: Vec<BlockTemplate>,
+}
+
+impl MemoryTemplate {
+    /// Parse the values of `blocks` as templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a block has an unclosed `{{` or a
+    /// placeholder that is not a plain variable name.
+    pub fn parse(blocks: impl IntoIterator<Item = Block>) -> LettaResult<Self> {
+        let blocks = blocks
+            .into_iter()
+            .map(BlockTemplate::parse)
+            .collect::<LettaResult<_>>()?;
+        Ok(Self { blocks })
+    }
+
+    /// The template blocks, with their values unrendered.
+    pub fn blocks(&self) -> impl Iterator<Item = &Block> {
+        self.blocks.iter().map(|template| &template.block)
+    }
+
+    /// The variables used by any block, sorted and without duplicates.
+    pub fn variables(&self) -> Vec<&str> {
+        let names: BTreeSet<&str> = self.blocks.iter().flat_map(|b| b.variables()).collect();
+        names.into_iter().collect()
+    }
+
+    /// The variables used by the block with `label`, in order of appearance.
+    pub fn block_variables(&self, label: &str) -> Vec<&str> {
+        self.blocks
+            .iter()
+            .filter(|template| template.block.label == label)
+            .flat_map(|template| template.variables())
+            .collect()
+    }
+
+    /// Render every block with `vars`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if a variable used by a block is missing
+    /// from `vars`, if `vars` has a variable no block uses, or if a rendered
+    /// value is longer than its block's limit.
+    pub fn render(&self, vars: &HashMap<String, String>) -> LettaResult<Vec<Block>> {
+        let used = self.variables();
+        let missing: Vec<&str> = used
+            .iter()
+            .copied()
+            .filter(|name| !vars.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(LettaError::validation_field(
+                format!("missing memory variables: {}", missing.join(", ")),
+                "memory_variables",
+            ));
+        }
+        let mut unused: Vec<&str> = vars
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !used.contains(name))
+            .collect();
+        if !unused.is_empty() {
+            unused.sort_unstable();
+            return Err(LettaError::validation_field(
+                format!("unused memory variables: {}", unused.join(", ")),
+                "memory_variables",
+            ));
+        }
+
+        self.blocks
+            .iter()
+            .map(|template| {
+                let value = template
+                    .render(vars)
+                    .expect("all variables were checked above");
+                template.block.check_limit(&value)?;
+                Ok(Block {
+                    value,
+                    ..template.block.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Render every block with `vars` and add them to an agent request.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`render`](Self::render).
+    pub fn apply(
+        &self,
+        builder: CreateAgentRequestBuilder,
+        vars: &HashMap<String, String>,
+    ) -> LettaResult<CreateAgentRequestBuilder> {
+        Ok(self
+            .render(vars)?
+            .into_iter()
+            .fold(builder, |builder, block| builder.memory_block(block)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_memory_template_parse() {
+        let template = MemoryTemplate::parse(vec![
+            Block::persona("{{ name }} helps {{user}}; {{name}} is kind. {not a var}"),
+            Block::human("Plain text"),
+        ])
+        .unwrap();
+        assert_eq!(template.variables(), ["name", "user"]);
+        assert_eq!(
+            template.block_variables("persona"),
+            ["name", "user", "name"]
+        );
+        assert!(template.block_variables("human").is_empty());
+
+        for bad in ["Hi {{name", "Hi {{}}", "Hi {{ name | upper }}"] {
+            assert!(
+                MemoryTemplate::parse(vec![Block::persona(bad)]).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_template_render() {
+        let mut persona = Block::persona("I am {{name}}.");
+        persona.limit = Some(12);
+        let template = MemoryTemplate::parse(vec![persona, Block::human("{{user}}")]).unwrap();
+
+        let blocks = template
+            .render(&vars(&[("name", "Sam"), ("user", "Ada")]))
+            .unwrap();
+        assert_eq!(blocks[0].value, "I am Sam.");
+        assert_eq!(blocks[1].value, "Ada");
+
+        let err = template.render(&vars(&[("name", "Sam")])).unwrap_err();
+        assert!(err.to_string().contains("missing memory variables: user"));
+
+        let err = template
+            .render(&vars(&[("name", "Sam"), ("user", "Ada"), ("x", "")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("unused memory variables: x"));
+
+        let err = template
+            .render(&vars(&[("name", "Samantha"), ("user", "Ada")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("over its limit of 12"));
+    }
+}
@@ -109,6 +109,25 @@ impl<T> PaginatedStream<T> {
         })
     }
 
+    /// Keep only the items matching a predicate, as a [`PaginatedStream`].
+    ///
+    /// Filtering happens after pagination, so pages that the predicate
+    /// empties out don't end the stream early. Use this for client-side
+    /// filters on list endpoints.
+    pub fn retain<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&T) -> bool + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        let inner = self.inner.filter(move |result| {
+            let keep = result.as_ref().map_or(true, &predicate);
+            async move { keep }
+        });
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+
     /// Map items to a different type.
     pub fn map<U, F>(self, f: F) -> impl Stream<Item = LettaResult<U>>
     where
@@ -403,6 +422,50 @@ mod tests {
         assert!(collected.iter().all(|item| item.name.starts_with("Item")));
     }
 
+    #[tokio::test]
+    async fn test_paginated_stream_retain_across_pages() {
+        let item = |n: u32, name: &str| TestItem {
+            id: LettaId::from_str(&format!("test-00000000-0000-0000-0000-00000000000{}", n))
+                .unwrap(),
+            name: name.to_string(),
+        };
+        let pages = vec![
+            vec![item(1, "Skip"), item(2, "Skip")],
+            vec![item(3, "Keep"), item(4, "Skip")],
+            vec![item(5, "Keep")],
+        ];
+
+        let fetch_fn = move |params: Option<PaginationParams>| {
+            let page = match params.and_then(|p| p.after).as_deref() {
+                None => 0,
+                Some("test-00000000-0000-0000-0000-000000000002") => 1,
+                Some(_) => 2,
+            };
+            let items = pages[page].clone();
+            async move { Ok(items) }
+        };
+
+        let initial = PaginationParams {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let collected =
+            PaginatedStream::new_with_id_cursor(Some(initial), fetch_fn, |item| &item.id)
+                .retain(|item| item.name == "Keep")
+                .collect()
+                .await
+                .unwrap();
+
+        let ids: Vec<String> = collected.iter().map(|item| item.id.to_string()).collect();
+        assert_eq!(
+            ids,
+            [
+                "test-00000000-0000-0000-0000-000000000003",
+                "test-00000000-0000-0000-0000-000000000005"
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_paginated_stream_with_take() {
         let items = vec![
@@ -76,16 +76,14 @@ pub mod memory {
 
     /// Create a test archival memory request.
     pub fn test_archival_memory(text: impl Into<String>) -> CreateArchivalMemoryRequest {
-        CreateArchivalMemoryRequest { text: text.into() }
+        CreateArchivalMemoryRequest::new(text)
     }
 
     /// Create multiple test archival memories.
     pub fn test_archival_memories(texts: Vec<&str>) -> Vec<CreateArchivalMemoryRequest> {
         texts
             .into_iter()
-            .map(|text| CreateArchivalMemoryRequest {
-                text: text.to_string(),
-            })
+            .map(CreateArchivalMemoryRequest::new)
             .collect()
     }
 }
@@ -0,0 +1,640 @@
+//! Generate Python tool source and schemas from a typed description.
+//!
+//! A [`ToolDefinition`] describes a tool's name, parameters, return value and
+//! function body. From it the generator produces the Python function (with a
+//! Google-style docstring that Letta's schema extractor accepts), the OpenAI
+//! function schema and the arguments schema, and bundles them into a
+//! [`CreateToolRequest`].
+//!
+//! # Example
+//!
+//! ```
+//! use letta::tool::{ParamType, ToolDefinition, ToolParam};
+//!
+//! let tool = ToolDefinition::new("roll_dice", "Roll a number of dice.")
+//!     .param(ToolParam::new("sides", ParamType::Integer, "Number of sides per die"))
+//!     .param(ToolParam::optional("count", ParamType::Integer, "How many dice to roll").default_value(1))
+//!     .returns(ParamType::String, "The rolled values, comma separated")
+//!     .body(
+//!         "import random\n\
+//!          return ', '.join(str(random.randint(1, sides)) for _ in range(count))",
+//!     );
+//!
+//! let request = tool.to_create_request()?;
+//! assert!(request.source_code.starts_with("def roll_dice(sides: int, count: int = 1) -> str:"));
+//! assert_eq!(request.args_json_schema.unwrap()["required"][0], "sides");
+//! # Ok::<(), letta::LettaError>(())
+//! ```
+
+use crate::error::{LettaError, LettaResult};
+use crate::types::tool::{CreateToolRequest, PipRequirement, SourceType};
+use serde_json::{json, Map, Value};
+
+/// Python keywords that cannot be used as function or parameter names.
+pub(super) const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Type of a tool parameter or return value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamType {
+    /// Python `str`, JSON Schema `string`.
+    String,
+    /// Python `int`, JSON Schema `integer`.
+    Integer,
+    /// Python `float`, JSON Schema `number`.
+    Number,
+    /// Python `bool`, JSON Schema `boolean`.
+    Boolean,
+    /// Python `list[T]`, JSON Schema `array` of the inner type.
+    Array(Box<ParamType>),
+    /// Python `dict`, JSON Schema `object`.
+    Object,
+    /// Python `str` restricted to the given values, JSON Schema `string` with `enum`.
+    Enum(Vec<String>),
+}
+
+impl ParamType {
+    /// Convenience constructor for [`ParamType::Array`].
+    pub fn array(items: ParamType) -> Self {
+        Self::Array(Box::new(items))
+    }
+
+    /// The Python type annotation for this type.
+    pub fn python_annotation(&self) -> String {
+        match self {
+            Self::String | Self::Enum(_) => "str".to_string(),
+            Self::Integer => "int".to_string(),
+            Self::Number => "float".to_string(),
+            Self::Boolean => "bool".to_string(),
+            Self::Array(items) => format!("list[{}]", items.python_annotation()),
+            Self::Object => "dict".to_string(),
+        }
+    }
+
+    /// The JSON Schema for this type, without a description.
+    pub fn json_schema(&self) -> Value {
+        match self {
+            Self::String => json!({"type": "string"}),
+            Self::Integer => json!({"type": "integer"}),
+            Self::Number => json!({"type": "number"}),
+            Self::Boolean => json!({"type": "boolean"}),
+            Self::Array(items) => json!({"type": "array", "items": items.json_schema()}),
+            Self::Object => json!({"type": "object"}),
+            Self::Enum(values) => json!({"type": "string", "enum": values}),
+        }
+    }
+}
+
+/// A single tool parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolParam {
+    /// Parameter name (must be a valid Python identifier).
+    pub name: String,
+    /// Parameter type.
+    pub ty: ParamType,
+    /// Description shown to the model.
+    pub description: String,
+    /// Whether the model must supply this parameter.
+    pub required: bool,
+    /// Python default for optional parameters (`None` when unset).
+    pub default: Option<Value>,
+}
+
+impl ToolParam {
+    /// Create a required parameter.
+    pub fn new(name: impl Into<String>, ty: ParamType, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            description: description.into(),
+            required: true,
+            default: None,
+        }
+    }
+
+    /// Create an optional parameter that defaults to Python `None`.
+    pub fn optional(
+        name: impl Into<String>,
+        ty: ParamType,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            required: false,
+            ..Self::new(name, ty, description)
+        }
+    }
+
+    /// Set the default value, making the parameter optional.
+    pub fn default_value(mut self, value: impl Into<Value>) -> Self {
+        self.required = false;
+        self.default = Some(value.into());
+        self
+    }
+
+    /// The JSON Schema for this parameter, including its description.
+    pub fn json_schema(&self) -> Value {
+        let mut schema = self.ty.json_schema();
+        schema["description"] = Value::String(single_line(&self.description));
+        schema
+    }
+}
+
+/// Documentation of a tool's return value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolReturns {
+    /// Return type.
+    pub ty: ParamType,
+    /// Description of the returned value.
+    pub description: String,
+}
+
+/// Typed description of a Python tool.
+///
+/// Build one with [`ToolDefinition::new`] and the chained setters, then call
+/// [`to_create_request`](Self::to_create_request).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ToolDefinition {
+    /// Function name (must be a valid Python identifier).
+    pub name: String,
+    /// Tool description; the first line of the docstring.
+    pub description: String,
+    /// Parameters, in declaration order.
+    pub parameters: Vec<ToolParam>,
+    /// Return value documentation.
+    pub returns: Option<ToolReturns>,
+    /// Function body, without indentation.
+    pub body: String,
+    /// Tags to attach to the tool.
+    pub tags: Vec<String>,
+    /// Return character limit.
+    pub return_char_limit: Option<u32>,
+    /// Pip requirements for the tool's sandbox.
+    pub pip_requirements: Vec<PipRequirement>,
+}
+
+impl ToolDefinition {
+    /// Create a definition with a name and description.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a parameter.
+    pub fn param(mut self, param: ToolParam) -> Self {
+        self.parameters.push(param);
+        self
+    }
+
+    /// Document the return value.
+    pub fn returns(mut self, ty: ParamType, description: impl Into<String>) -> Self {
+        self.returns = Some(ToolReturns {
+            ty,
+            description: description.into(),
+        });
+        self
+    }
+
+    /// Set the function body. Leading indentation common to all lines is removed.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Add a tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Set the return character limit.
+    pub fn return_char_limit(mut self, limit: u32) -> Self {
+        self.return_char_limit = Some(limit);
+        self
+    }
+
+    /// Add a pip requirement.
+    pub fn pip_requirement(mut self, package: impl Into<String>, version: Option<String>) -> Self {
+        self.pip_requirements.push(PipRequirement {
+            package: package.into(),
+            version,
+        });
+        self
+    }
+
+    /// Check that the definition can be turned into a valid Python tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LettaError::Validation`] naming the offending field if a
+    /// name is not a usable Python identifier, a parameter is duplicated, a
+    /// description is empty, or the body is empty.
+    pub fn validate(&self) -> LettaResult<()> {
+        check_identifier(&self.name, "name")?;
+        if self.description.trim().is_empty() {
+            return Err(LettaError::validation_field(
+                "Tool description cannot be empty",
+                "description",
+            ));
+        }
+
+        for (i, param) in self.parameters.iter().enumerate() {
+            let field = format!("parameters.{}", param.name);
+            check_identifier(&param.name, &field)?;
+            if self.parameters[..i].iter().any(|p| p.name == param.name) {
+                return Err(LettaError::validation_field(
+                    format!("Duplicate parameter '{}'", param.name),
+                    field,
+                ));
+            }
+            if param.description.trim().is_empty() {
+                return Err(LettaError::validation_field(
+                    format!(
+                        "Parameter '{}' needs a description; Letta rejects undocumented parameters",
+                        param.name
+                    ),
+                    field,
+                ));
+            }
+            if param.required && param.default.is_some() {
+                return Err(LettaError::validation_field(
+                    format!("Required parameter '{}' cannot have a default", param.name),
+                    field,
+                ));
+            }
+        }
+
+        if self.body.trim().is_empty() {
+            return Err(LettaError::validation_field(
+                "Tool body cannot be empty",
+                "body",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Generate the Python function source, including its docstring.
+    ///
+    /// Required parameters are emitted before optional ones, as Python
+    /// requires, keeping their relative order otherwise.
+    pub fn source_code(&self) -> String {
+        let params = self
+            .ordered_parameters()
+            .map(|p| {
+                let annotation = p.ty.python_annotation();
+                match (&p.default, p.required) {
+                    (_, true) => format!("{}: {}", p.name, annotation),
+                    (Some(default), false) => {
+                        format!("{}: {} = {}", p.name, annotation, python_literal(default))
+                    }
+                    (None, false) => format!("{}: {} = None", p.name, annotation),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut source = format!("def {}({})", self.name, params);
+        if let Some(returns) = &self.returns {
+            source.push_str(" -> ");
+            source.push_str(&returns.ty.python_annotation());
+        }
+        source.push_str(":\n");
+
+        source.push_str("    \"\"\"\n");
+        for line in self.description.trim().lines() {
+            push_indented(&mut source, 1, &escape_docstring(line.trim_end()));
+        }
+
+        if !self.parameters.is_empty() {
+            source.push('\n');
+            push_indented(&mut source, 1, "Args:");
+            for p in self.ordered_parameters() {
+                push_indented(
+                    &mut source,
+                    2,
+                    &format!(
+                        "{} ({}): {}",
+                        p.name,
+                        p.ty.python_annotation(),
+                        escape_docstring(&single_line(&p.description))
+                    ),
+                );
+            }
+        }
+
+        if let Some(returns) = &self.returns {
+            source.push('\n');
+            push_indented(&mut source, 1, "Returns:");
+            push_indented(
+                &mut source,
+                2,
+                &format!(
+                    "{}: {}",
+                    returns.ty.python_annotation(),
+                    escape_docstring(&single_line(&returns.description))
+                ),
+            );
+        }
+        source.push_str("    \"\"\"\n");
+
+        for line in dedent(&self.body).lines() {
+            if line.trim().is_empty() {
+                source.push('\n');
+            } else {
+                push_indented(&mut source, 1, line);
+            }
+        }
+
+        source
+    }
+
+    /// Generate the arguments schema (`args_json_schema`).
+    pub fn args_json_schema(&self) -> Value {
+        let properties: Map<String, Value> = self
+            .parameters
+            .iter()
+            .map(|p| (p.name.clone(), p.json_schema()))
+            .collect();
+        let required: Vec<&str> = self
+            .parameters
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| p.name.as_str())
+            .collect();
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    /// Generate the OpenAI-style function schema (`json_schema`).
+    pub fn json_schema(&self) -> Value {
+        json!({
+            "name": self.name,
+            "description": single_line(&self.description),
+            "parameters": self.args_json_schema(),
+        })
+    }
+
+    /// Validate the definition and build a [`CreateToolRequest`] from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LettaError::Validation`] if [`validate`](Self::validate) fails.
+    pub fn to_create_request(&self) -> LettaResult<CreateToolRequest> {
+        self.validate()?;
+
+        Ok(CreateToolRequest {
+            description: Some(self.description.trim().to_string()),
+            tags: (!self.tags.is_empty()).then(|| self.tags.clone()),
+            source_code: self.source_code(),
+            source_type: Some(SourceType::Python),
+            json_schema: Some(self.json_schema()),
+            args_json_schema: Some(self.args_json_schema()),
+            return_char_limit: self.return_char_limit,
+            pip_requirements: (!self.pip_requirements.is_empty())
+                .then(|| self.pip_requirements.clone()),
+        })
+    }
+
+    fn ordered_parameters(&self) -> impl Iterator<Item = &ToolParam> {
+        let required = self.parameters.iter().filter(|p| p.required);
+        let optional = self.parameters.iter().filter(|p| !p.required);
+        required.chain(optional)
+    }
+}
+
+/// Check that a name is a valid, non-keyword Python identifier.
+fn check_identifier(name: &str, field: &str) -> LettaResult<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+
+    if !valid {
+        return Err(LettaError::validation_field(
+            format!("'{}' is not a valid Python identifier", name),
+            field,
+        ));
+    }
+    if PYTHON_KEYWORDS.contains(&name) {
+        return Err(LettaError::validation_field(
+            format!("'{}' is a reserved Python keyword", name),
+            field,
+        ));
+    }
+    Ok(())
+}
+
+/// Render a JSON value as a Python literal.
+fn python_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        // JSON string escapes are valid in Python string literals
+        Value::String(s) => Value::String(s.clone()).to_string(),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(python_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{}: {}", Value::String(k.clone()), python_literal(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Collapse a possibly multi-line description onto one line.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escape text for a `"""` docstring, so backslashes and embedded triple
+/// quotes read back as written instead of ending or breaking the string.
+fn escape_docstring(text: &str) -> String {
+    text.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")
+}
+
+/// Drop leading and trailing blank lines and remove the indentation shared
+/// by all remaining non-blank lines.
+fn dedent(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|l| !l.trim().is_empty());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty());
+    let (Some(start), Some(end)) = (start, end) else {
+        return String::new();
+    };
+    let lines = &lines[start..=end];
+
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_else(|| l.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn push_indented(out: &mut String, level: usize, line: &str) {
+    for _ in 0..level {
+        out.push_str("    ");
+    }
+    out.push_str(line);
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_tool() -> ToolDefinition {
+        ToolDefinition::new(
+            "search_notes",
+            "Search the user's notes.\nMatches are case-insensitive.",
+        )
+        .param(ToolParam::optional("limit", ParamType::Integer, "Maximum results").default_value(5))
+        .param(ToolParam::new(
+            "query",
+            ParamType::String,
+            "Text to search for",
+        ))
+        .param(ToolParam::optional(
+            "tags",
+            ParamType::array(ParamType::String),
+            "Only search notes with these tags",
+        ))
+        .returns(ParamType::String, "Matching notes, one per line")
+        .body(
+            "
+                results = []
+                return '\\n'.join(results[:limit])
+                ",
+        )
+    }
+
+    #[test]
+    fn test_source_code_generation() {
+        let source = search_tool().source_code();
+        let expected = r#"def search_notes(query: str, limit: int = 5, tags: list[str] = None) -> str:
+    """
+    Search the user's notes.
+    Matches are case-insensitive.
+
+    Args:
+        query (str): Text to search for
+        limit (int): Maximum results
+        tags (list[str]): Only search notes with these tags
+
+    Returns:
+        str: Matching notes, one per line
+    """
+    results = []
+    return '\n'.join(results[:limit])
+"#;
+        assert_eq!(source, expected);
+    }
+
+    #[test]
+    fn test_docstring_escaping() {
+        let tool = ToolDefinition::new("quote", r#"Wrap text in """quotes"""."#)
+            .param(ToolParam::new(
+                "path",
+                ParamType::String,
+                r"A Windows path like C:\",
+            ))
+            .returns(ParamType::String, r#"The text, ending in ""#)
+            .body("return path");
+        let source = tool.source_code();
+        assert!(source.contains(r#"    Wrap text in \"\"\"quotes\"\"\"."#));
+        assert!(source.contains(r"        path (str): A Windows path like C:\\"));
+        assert!(source.contains(r#"        str: The text, ending in ""#));
+        // The only unescaped triple quotes are the docstring's own.
+        assert_eq!(source.replace(r#"\""#, "").matches("\"\"\"").count(), 2);
+    }
+
+    #[test]
+    fn test_schema_generation() {
+        let tool = search_tool();
+        let args = tool.args_json_schema();
+        assert_eq!(args["required"], json!(["query"]));
+        assert_eq!(args["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(args["properties"]["limit"]["type"], "integer");
+
+        let schema = tool.json_schema();
+        assert_eq!(schema["name"], "search_notes");
+        assert_eq!(
+            schema["description"],
+            "Search the user's notes. Matches are case-insensitive."
+        );
+        assert_eq!(schema["parameters"], args);
+
+        let request = tool.to_create_request().unwrap();
+        assert_eq!(request.source_type, Some(SourceType::Python));
+        assert_eq!(request.args_json_schema, Some(args));
+        assert!(request.tags.is_none());
+    }
+
+    #[test]
+    fn test_enum_and_literals() {
+        let param = ToolParam::optional(
+            "unit",
+            ParamType::Enum(vec!["c".into(), "f".into()]),
+            "Temperature unit",
+        )
+        .default_value("c");
+        assert_eq!(param.json_schema()["enum"], json!(["c", "f"]));
+
+        assert_eq!(python_literal(&json!(null)), "None");
+        assert_eq!(python_literal(&json!([true, 1.5])), "[True, 1.5]");
+        assert_eq!(python_literal(&json!({"a": "x\"y"})), r#"{"a": "x\"y"}"#);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(search_tool().validate().is_ok());
+
+        let err = ToolDefinition::new("class", "Bad name")
+            .body("pass")
+            .validate();
+        assert!(matches!(err, Err(LettaError::Validation { field: Some(f), .. }) if f == "name"));
+
+        let err = ToolDefinition::new("tool", "Duplicate params")
+            .param(ToolParam::new("x", ParamType::Integer, "first"))
+            .param(ToolParam::new("x", ParamType::Integer, "second"))
+            .body("pass")
+            .validate();
+        assert!(err.is_err());
+
+        let err = ToolDefinition::new("tool", "Undocumented param")
+            .param(ToolParam::new("x", ParamType::Integer, " "))
+            .body("pass")
+            .to_create_request();
+        assert!(err.is_err());
+
+        assert!(ToolDefinition::new("tool", "No body").validate().is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Serve Rust functions to Letta agents as an MCP server.
+//!
+//! An [`McpBridge`] hosts a minimal [streamable HTTP] MCP server in the
+//! calling process, answering `tools/list` and `tools/call` from registered
+//! async Rust handlers. Once the bridge is [serving](McpBridge::serve), it
+//! can register itself with the Letta server and attach its tools to an
+//! agent, which then calls them like any other tool — no Python required.
+//!
+//! ```no_run
+//! # #[cfg(feature = "derive")]
+//! # async fn example(client: &letta::LettaClient, agent_id: &letta::LettaId) -> letta::LettaResult<()> {
+//! use letta::tool::{LettaTool, McpBridge};
+//! use serde::Deserialize;
+//!
+//! /// Look up a customer's order history.
+//! #[derive(LettaTool, Deserialize)]
+//! struct OrderHistory {
+//!     /// The customer's email address.
+//!     email: String,
+//! }
+//!
+//! let server = McpBridge::new("orders")
+//!     .bearer_token("a-long-random-secret")
+//!     .tool(|args: OrderHistory| async move {
+//!         Ok::<_, std::io::Error>(format!("{} has no orders yet", args.email))
+//!     })
+//!     .serve("0.0.0.0:8765")
+//!     .await?;
+//!
+//! // The Letta server must be able to reach this URL
+//! let report = server
+//!     .attach(client, agent_id, Some("http://host.docker.internal:8765/mcp"))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The server stops when the [`McpBridgeServer`] is dropped or
+//! [shut down](McpBridgeServer::shutdown). Handlers run on the Tokio runtime,
+//! and their errors are returned to the agent as failed tool calls.
+//!
+//! Only the streamable HTTP transport is supported: requests are answered
+//! with a single JSON response, and `GET /mcp` (the optional server-to-client
+//! event stream) returns `405 Method Not Allowed`. Register the bridge with
+//! Letta as a streamable HTTP server, not an SSE one.
+//!
+//! [streamable HTTP]: https://modelcontextprotocol.io/specification/2025-06-18/basic/transports#streamable-http
+
+use super::client::ClientToolRegistry;
+use super::typed::LettaTool;
+use crate::api::McpImportReport;
+use crate::client::LettaClient;
+use crate::error::LettaResult;
+use crate::types::tool::{McpTool, StreamableHttpServerConfig};
+use crate::types::LettaId;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures::channel::oneshot;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+/// MCP protocol versions the bridge can speak, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// A set of Rust tool handlers to serve over MCP.
+#[derive(Clone)]
+pub struct McpBridge {
+    name: String,
+    token: Option<String>,
+    tools: Vec<McpTool>,
+    handlers: ClientToolRegistry,
+}
+
+impl fmt::Debug for McpBridge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpBridge")
+            .field("name", &self.name)
+            .field("tools", &self.tool_names())
+            .field("authenticated", &self.token.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpBridge {
+    /// Create a bridge that Letta will know as the MCP server `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            token: None,
+            tools: Vec::new(),
+            handlers: ClientToolRegistry::new(),
+        }
+    }
+
+    /// Require `Authorization: Bearer <token>` on every request.
+    ///
+    /// Recommended whenever the bridge listens on a non-loopback address,
+    /// since anyone who can reach it can call its tools.
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Serve a [`LettaTool`], with its name, description and input schema
+    /// taken from the tool definition.
+    ///
+    /// Arguments that do not match `A` are returned to the agent as a failed
+    /// call without running the handler.
+    pub fn tool<A, F, Fut, T, E>(self, handler: F) -> Self
+    where
+        A: LettaTool + DeserializeOwned + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Into<String>,
+        E: fmt::Display,
+    {
+        let definition = A::tool_definition();
+        let tool = McpTool {
+            name: definition.name.clone(),
+            description: Some(definition.description.clone()),
+            input_schema: definition.args_json_schema(),
+            annotations: None,
+        };
+        let mut bridge = self.add_tool(tool);
+        bridge.handlers = bridge.handlers.register_typed(definition.name, handler);
+        bridge
+    }
+
+    /// Serve a tool described by a JSON schema, with a handler that receives
+    /// the raw JSON arguments.
+    pub fn tool_with_schema<F, Fut, T, E>(
+        self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        input_schema: Value,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Into<String>,
+        E: fmt::Display,
+    {
+        let name = name.into();
+        let tool = McpTool {
+            name: name.clone(),
+            description: Some(description.into()),
+            input_schema,
+            annotations: None,
+        };
+        let mut bridge = self.add_tool(tool);
+        bridge.handlers = bridge.handlers.register(name, handler);
+        bridge
+    }
+
+    fn add_tool(mut self, tool: McpTool) -> Self {
+        self.tools.retain(|existing| existing.name != tool.name);
+        self.tools.push(tool);
+        self
+    }
+
+    /// Names of the served tools.
+    pub fn tool_names(&self) -> Vec<&str> {
+        self.tools.iter().map(|tool| tool.name.as_str()).collect()
+    }
+
+    /// Start serving on `addr` (e.g. `"127.0.0.1:0"` for any free port).
+    ///
+    /// The server runs in a background task until the returned
+    /// [`McpBridgeServer`] is dropped or shut down.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the address cannot be bound.
+    pub async fn serve(self, addr: impl ToSocketAddrs) -> LettaResult<McpBridgeServer> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let (shutdown, stopped) = oneshot::channel::<()>();
+
+        let name = self.name.clone();
+        let token = self.token.clone();
+        let tool_names = self.tool_names().into_iter().map(String::from).collect();
+
+        let app = Router::new()
+            .route("/mcp", post(handle_post).get(handle_get))
+            .with_state(Arc::new(self));
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    // Resolves on shutdown() or when the server handle is dropped
+                    let _ = stopped.await;
+                })
+                .await;
+        });
+
+        Ok(McpBridgeServer {
+            name,
+            token,
+            tool_names,
+            local_addr,
+            shutdown: Some(shutdown),
+            task: Some(task),
+        })
+    }
+
+    /// Handle one JSON-RPC message, returning the response unless it is a
+    /// notification.
+    async fn handle_message(&self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses from the client (we never send requests) are ignored
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(rpc_error(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "Missing method",
+            ));
+        };
+        // Notifications (no id) never get a response
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => {
+                let requested = params.get("protocolVersion").and_then(Value::as_str);
+                let version = requested
+                    .filter(|version| PROTOCOL_VERSIONS.contains(version))
+                    .unwrap_or(PROTOCOL_VERSIONS[0]);
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": {"tools": {"listChanged": false}},
+                    "serverInfo": {"name": self.name, "version": env!("CARGO_PKG_VERSION")},
+                })
+            }
+            "ping" => json!({}),
+            "tools/list" => json!({"tools": self.tools}),
+            "tools/call" => {
+                let Some(name) = params.get("name").and_then(Value::as_str) else {
+                    return Some(rpc_error(id, INVALID_PARAMS, "Missing tool name"));
+                };
+                if !self.handlers.contains(name) {
+                    return Some(rpc_error(
+                        id,
+                        INVALID_PARAMS,
+                        &format!("Unknown tool: {}", name),
+                    ));
+                }
+                let args = params
+                    .get("arguments")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+                let (text, is_error) = match self.handlers.call(name, args).await {
+                    Ok(output) => (output, false),
+                    Err(message) => (message, true),
+                };
+                json!({
+                    "content": [{"type": "text", "text": text}],
+                    "isError": is_error,
+                })
+            }
+            _ => {
+                return Some(rpc_error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Method not found: {}", method),
+                ))
+            }
+        };
+
+        Some(json!({"jsonrpc": "2.0", "id": id, "result": result}))
+    }
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message},
+    })
+}
+
+/// Compare two byte strings without exiting early on the first mismatch,
+/// so response timing does not reveal how much of a token was correct.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// No server-initiated event stream is offered.
+async fn handle_get() -> Response {
+    (StatusCode::METHOD_NOT_ALLOWED, [(header::ALLOW, "POST")]).into_response()
+}
+
+async fn handle_post(
+    State(bridge): State<Arc<McpBridge>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(token) = &bridge.token {
+        let expected = format!("Bearer {}", token);
+        let authorized = headers
+            .get(header::AUTHORIZATION)
+            .is_some_and(|value| constant_time_eq(value.as_bytes(), expected.as_bytes()));
+        if !authorized {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
+
+    let message: Value = match serde_json::from_slice(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = rpc_error(Value::Null, PARSE_ERROR, &e.to_string());
+            return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+        }
+    };
+
+    let response = match message {
+        Value::Array(batch) => {
+            let mut responses = Vec::new();
+            for message in batch {
+                responses.extend(bridge.handle_message(message).await);
+            }
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        message => bridge.handle_message(message).await,
+    };
+
+    match response {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+/// A running [`McpBridge`].
+///
+/// Dropping this stops the server.
+#[derive(Debug)]
+pub struct McpBridgeServer {
+    name: String,
+    token: Option<String>,
+    tool_names: Vec<String>,
+    local_addr: SocketAddr,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl McpBridgeServer {
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// The MCP endpoint URL on the listening address.
+    ///
+    /// Only reachable by a Letta server on the same host (or network, for a
+    /// non-loopback address); pass the externally visible URL to
+    /// [`register`](Self::register) otherwise.
+    pub fn url(&self) -> String {
+        format!("http://{}/mcp", self.local_addr)
+    }
+
+    /// The configuration that tells Letta how to reach the bridge at `url`.
+    pub fn config(&self, url: impl Into<String>) -> StreamableHttpServerConfig {
+        let config = StreamableHttpServerConfig::new(&self.name, url);
+        match &self.token {
+            Some(token) => config.bearer_token(token),
+            None => config,
+        }
+    }
+
+    /// Add the bridge to the Letta server as an MCP server and import its
+    /// tools.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The Letta client
+    /// * `url` - URL the Letta server reaches the bridge at; defaults to [`url`](Self::url)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the server cannot be added.
+    /// Tools that fail to import are recorded in the report instead.
+    pub async fn register(
+        &self,
+        client: &LettaClient,
+        url: Option<&str>,
+    ) -> LettaResult<McpImportReport> {
+        let url = url.map_or_else(|| self.url(), String::from);
+        client.mcp().add_server(self.config(url)).await?;
+        Ok(client
+            .mcp()
+            .import_tools(&self.name, &self.tool_names)
+            .await)
+    }
+
+    /// [Register](Self::register) the bridge and attach its tools to an agent.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The Letta client
+    /// * `agent_id` - The agent to attach the tools to
+    /// * `url` - URL the Letta server reaches the bridge at; defaults to [`url`](Self::url)
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the server cannot be added or
+    /// a tool cannot be attached.
+    pub async fn attach(
+        &self,
+        client: &LettaClient,
+        agent_id: &LettaId,
+        url: Option<&str>,
+    ) -> LettaResult<McpImportReport> {
+        let report = self.register(client, url).await?;
+        for tool in &report.succeeded {
+            if let Some(tool_id) = &tool.id {
+                client
+                    .memory()
+                    .attach_tool_to_agent(agent_id, tool_id)
+                    .await?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Stop the server and wait for in-flight requests to finish.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bridge() -> McpBridge {
+        McpBridge::new("test").tool_with_schema(
+            "shout",
+            "Shout some text.",
+            json!({"type": "object", "properties": {"text": {"type": "string"}}}),
+            |args: Value| async move {
+                match args["text"].as_str() {
+                    Some(text) => Ok(text.to_uppercase()),
+                    None => Err("text is required"),
+                }
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_handle_message() {
+        let bridge = bridge();
+
+        let init = bridge
+            .handle_message(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": {"protocolVersion": "2024-11-05", "capabilities": {}}
+            }))
+            .await
+            .unwrap();
+        assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(init["result"]["serverInfo"]["name"], "test");
+
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(bridge.handle_message(notification).await.is_none());
+
+        let list = bridge
+            .handle_message(json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+            .await
+            .unwrap();
+        assert_eq!(list["result"]["tools"][0]["name"], "shout");
+        assert_eq!(list["result"]["tools"][0]["inputSchema"]["type"], "object");
+
+        let call = |arguments: Value| {
+            bridge.handle_message(json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                "params": {"name": "shout", "arguments": arguments}
+            }))
+        };
+        let ok = call(json!({"text": "hi"})).await.unwrap();
+        assert_eq!(ok["result"]["content"][0]["text"], "HI");
+        assert_eq!(ok["result"]["isError"], false);
+        let failed = call(json!({})).await.unwrap();
+        assert_eq!(failed["result"]["isError"], true);
+
+        let unknown = bridge
+            .handle_message(json!({"jsonrpc": "2.0", "id": 4, "method": "resources/list"}))
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let server = bridge()
+            .bearer_token("secret")
+            .serve("127.0.0.1:0")
+            .await
+            .unwrap();
+        let http = reqwest::Client::new();
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+
+        let unauthorized = http.post(server.url()).json(&request).send().await.unwrap();
+        assert_eq!(unauthorized.status(), 401);
+        let wrong_token = http
+            .post(server.url())
+            .bearer_auth("secreT")
+            .json(&request)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(wrong_token.status(), 401);
+
+        let stream = http.get(server.url()).send().await.unwrap();
+        assert_eq!(stream.status(), 405);
+
+        let response: Value = http
+            .post(server.url())
+            .bearer_auth("secret")
+            .json(&request)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["tools"][0]["name"], "shout");
+
+        let config = server.config(server.url());
+        assert_eq!(config.auth_token.as_deref(), Some("Bearer secret"));
+
+        server.shutdown().await;
+    }
+}
@@ -0,0 +1,430 @@
+//! Tools executed by the client rather than the Letta server.
+//!
+//! Some tools need to run inside the calling process, for example because
+//! they read local state. Such tools are registered on the server like any
+//! other tool (their body is never run, see
+//! [`LettaTool::create_request_skeleton`]) and marked as requiring approval
+//! with [`MemoryApi::set_tool_requires_approval`], so the agent stops with
+//! [`StopReasonType::RequiresApproval`] when it calls them. A [`ClientToolRegistry`] maps tool names to async Rust handlers, and
+//! [`MessageApi::create_with_client_tools`] runs them and sends the results
+//! back until the agent finishes its turn. If the agent also waits on a tool
+//! that has no handler, for example a server tool that needs a person's
+//! approval, the loop stops and leaves that request to the caller (see
+//! [`pending_tool_calls`]).
+//!
+//! ```no_run
+//! # use letta::tool::ClientToolRegistry;
+//! # use letta::types::{CreateMessagesRequest, MessageCreate};
+//! # use letta::LettaId;
+//! # use std::str::FromStr;
+//! # async fn example(client: &letta::LettaClient) -> letta::LettaResult<()> {
+//! let tools = ClientToolRegistry::new().register("read_clipboard", |_args| async {
+//!     Ok::<_, std::io::Error>("clipboard contents".to_string())
+//! });
+//!
+//! let agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000").unwrap();
+//! let response = client
+//!     .messages()
+//!     .create_with_client_tools(
+//!         &agent_id,
+//!         CreateMessagesRequest {
+//!             messages: vec![MessageCreate::user("What's on my clipboard?")],
+//!             ..Default::default()
+//!         },
+//!         &tools,
+//!     )
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`StopReasonType::RequiresApproval`]: crate::types::StopReasonType::RequiresApproval
+//! [`MessageApi::create_with_client_tools`]: crate::api::MessageApi::create_with_client_tools
+//! [`MemoryApi::set_tool_requires_approval`]: crate::api::MemoryApi::set_tool_requires_approval
+
+use super::typed::LettaTool;
+use crate::types::{LettaMessageUnion, ToolCall, ToolReturnApproval};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Default limit on tool-execution round trips per request.
+const DEFAULT_MAX_ROUNDS: usize = 16;
+
+/// Future returned by a client tool handler.
+pub type ClientToolFuture = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+type Handler = Arc<dyn Fn(Value) -> ClientToolFuture + Send + Sync>;
+
+/// Async Rust handlers for tools executed by the client.
+///
+/// Handlers receive the tool call arguments and return the tool's output,
+/// which is sent to the agent as a successful tool return; an `Err` is sent as
+/// a failed tool return with the error message, so the agent can react to it.
+#[derive(Clone)]
+pub struct ClientToolRegistry {
+    handlers: HashMap<String, Handler>,
+    max_rounds: usize,
+}
+
+impl Default for ClientToolRegistry {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            max_rounds: DEFAULT_MAX_ROUNDS,
+        }
+    }
+}
+
+impl fmt::Debug for ClientToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.names();
+        names.sort_unstable();
+        f.debug_struct("ClientToolRegistry")
+            .field("tools", &names)
+            .field("max_rounds", &self.max_rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientToolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler that receives the raw JSON arguments.
+    ///
+    /// Replaces any handler previously registered under `name`.
+    pub fn register<F, Fut, T, E>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Into<String>,
+        E: fmt::Display,
+    {
+        let handler: Handler = Arc::new(move |args| {
+            let future = handler(args);
+            Box::pin(async move { future.await.map(Into::into).map_err(|e| e.to_string()) })
+        });
+        self.handlers.insert(name.into(), handler);
+        self
+    }
+
+    /// Register a handler whose arguments are deserialized into `A`.
+    ///
+    /// Arguments that do not match `A` are reported to the agent as a failed
+    /// tool call without running the handler.
+    pub fn register_typed<A, F, Fut, T, E>(self, name: impl Into<String>, handler: F) -> Self
+    where
+        A: DeserializeOwned + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Into<String>,
+        E: fmt::Display,
+    {
+        let handler = Arc::new(handler);
+        self.register(name, move |args: Value| {
+            let handler = Arc::clone(&handler);
+            async move {
+                let args = serde_json::from_value::<A>(args)
+                    .map_err(|e| format!("Invalid arguments: {}", e))?;
+                handler(args)
+                    .await
+                    .map(Into::into)
+                    .map_err(|e| e.to_string())
+            }
+        })
+    }
+
+    /// Register a handler for a [`LettaTool`], named after the tool.
+    pub fn register_tool<A, F, Fut, T, E>(self, handler: F) -> Self
+    where
+        A: LettaTool + DeserializeOwned + Send + 'static,
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+        T: Into<String>,
+        E: fmt::Display,
+    {
+        self.register_typed(A::tool_name(), handler)
+    }
+
+    /// Limit the number of tool-execution round trips per request.
+    ///
+    /// Guards against agents that keep calling client tools; defaults to 16.
+    pub fn max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// The configured round-trip limit.
+    pub fn round_limit(&self) -> usize {
+        self.max_rounds
+    }
+
+    /// Whether a handler is registered for `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Whether every call in `calls` has a registered handler.
+    ///
+    /// The client-tool loops only answer a round when this holds; a call to
+    /// any other tool is an approval request meant for a person.
+    pub fn handles_all(&self, calls: &[ToolCall]) -> bool {
+        calls.iter().all(|call| self.contains(&call.name))
+    }
+
+    /// Names of the registered tools.
+    pub fn names(&self) -> Vec<&str> {
+        self.handlers.keys().map(String::as_str).collect()
+    }
+
+    /// Run the handler for a tool call and build the result to send back.
+    ///
+    /// Unknown tools, malformed arguments and handler errors all produce a
+    /// failed tool return rather than an error, so the agent is never left
+    /// waiting on a call. Check [`handles_all`](Self::handles_all) first to
+    /// avoid rejecting approval requests that a person should answer.
+    pub async fn execute(&self, call: &ToolCall) -> ToolReturnApproval {
+        let Some(handler) = self.handlers.get(&call.name) else {
+            return ToolReturnApproval::error(
+                &call.tool_call_id,
+                format!(
+                    "No client-side handler is registered for tool '{}'",
+                    call.name
+                ),
+            );
+        };
+
+        let args = if call.arguments.trim().is_empty() {
+            Value::Object(Default::default())
+        } else {
+            match serde_json::from_str(&call.arguments) {
+                Ok(args) => args,
+                Err(e) => {
+                    return ToolReturnApproval::error(
+                        &call.tool_call_id,
+                        format!("Invalid arguments: {}", e),
+                    )
+                }
+            }
+        };
+
+        match handler(args).await {
+            Ok(output) => ToolReturnApproval::success(&call.tool_call_id, output),
+            Err(message) => ToolReturnApproval::error(&call.tool_call_id, message),
+        }
+    }
+
+    /// Run the handler for `name` with already-parsed arguments.
+    #[cfg(feature = "mcp-server")]
+    pub(crate) async fn call(&self, name: &str, args: Value) -> Result<String, String> {
+        match self.handlers.get(name) {
+            Some(handler) => handler(args).await,
+            None => Err(format!("No handler is registered for tool '{}'", name)),
+        }
+    }
+}
+
+/// Tool calls in `messages` that are waiting on the client.
+///
+/// These are approval requests for any tool, plus calls to tools in
+/// `registry` that have no matching tool return. Approval requests for tools
+/// without a handler are included so the caller can answer them.
+///
+/// `messages` are complete messages, as in a [`LettaResponse`]; a later
+/// message for the same call replaces the earlier one.
+///
+/// [`LettaResponse`]: crate::types::LettaResponse
+pub fn pending_tool_calls<'a>(
+    messages: impl IntoIterator<Item = &'a LettaMessageUnion>,
+    registry: &ClientToolRegistry,
+) -> Vec<ToolCall> {
+    let mut pending = PendingToolCalls::new(false);
+    for message in messages {
+        pending.observe(message, registry);
+    }
+    pending.take()
+}
+
+/// Accumulates pending tool calls across messages or stream events.
+#[derive(Debug, Default)]
+pub(crate) struct PendingToolCalls {
+    calls: Vec<ToolCall>,
+    answered: Vec<String>,
+    deltas: bool,
+}
+
+impl PendingToolCalls {
+    /// Create an accumulator; with `deltas`, messages are token-streamed
+    /// fragments whose arguments are appended to the call seen so far.
+    pub(crate) fn new(deltas: bool) -> Self {
+        Self {
+            deltas,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn observe(&mut self, message: &LettaMessageUnion, registry: &ClientToolRegistry) {
+        let call = match message {
+            LettaMessageUnion::ApprovalRequestMessage(m) => &m.tool_call,
+            LettaMessageUnion::ToolCallMessage(m) if registry.contains(&m.tool_call.name) => {
+                &m.tool_call
+            }
+            LettaMessageUnion::ToolReturnMessage(m) => {
+                self.answered.push(m.tool_call_id.clone());
+                return;
+            }
+            _ => return,
+        };
+
+        match self
+            .calls
+            .iter_mut()
+            .find(|c| c.tool_call_id == call.tool_call_id)
+        {
+            Some(existing) => {
+                if existing.name.is_empty() {
+                    existing.name.clone_from(&call.name);
+                }
+                if self.deltas {
+                    existing.arguments.push_str(&call.arguments);
+                } else {
+                    existing.arguments.clone_from(&call.arguments);
+                }
+            }
+            None => self.calls.push(call.clone()),
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<ToolCall> {
+        let answered = std::mem::take(&mut self.answered);
+        std::mem::take(&mut self.calls)
+            .into_iter()
+            .filter(|call| !answered.contains(&call.tool_call_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, arguments: &str) -> ToolCall {
+        ToolCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            tool_call_id: format!("call-{}", name),
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Add {
+        a: i64,
+        b: i64,
+    }
+
+    #[tokio::test]
+    async fn test_execute() {
+        let tools = ClientToolRegistry::new()
+            .register("echo", |args: Value| async move {
+                Ok::<_, String>(args["text"].as_str().unwrap_or_default().to_string())
+            })
+            .register_typed("add", |args: Add| async move {
+                Ok::<_, String>((args.a + args.b).to_string())
+            })
+            .register("fail", |_| async { Err::<String, _>("disk full") });
+
+        let result = tools.execute(&call("echo", r#"{"text": "hi"}"#)).await;
+        assert_eq!(result.status, crate::types::ToolReturnStatus::Success);
+        assert_eq!(result.tool_return, "hi");
+        assert_eq!(result.tool_call_id, "call-echo");
+
+        let result = tools.execute(&call("add", r#"{"a": 2, "b": 3}"#)).await;
+        assert_eq!(result.tool_return, "5");
+
+        for (name, arguments, expected) in [
+            ("add", r#"{"a": "x"}"#, "Invalid arguments"),
+            ("fail", "", "disk full"),
+            ("missing", "{}", "No client-side handler"),
+        ] {
+            let result = tools.execute(&call(name, arguments)).await;
+            assert_eq!(result.status, crate::types::ToolReturnStatus::Error);
+            assert!(result.tool_return.contains(expected), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_pending_tool_calls() {
+        let messages: Vec<LettaMessageUnion> = serde_json::from_value(serde_json::json!([
+            {
+                "message_type": "tool_call_message",
+                "id": "message-00000000-0000-0000-0000-000000000001",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": "local", "arguments": "{}", "tool_call_id": "call-1"}
+            },
+            {
+                "message_type": "tool_return_message",
+                "id": "message-00000000-0000-0000-0000-000000000002",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_return": "ok",
+                "status": "success",
+                "tool_call_id": "call-1"
+            },
+            {
+                "message_type": "approval_request_message",
+                "id": "message-00000000-0000-0000-0000-000000000003",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": "local", "arguments": "{\"path\": \"/tmp\"}", "tool_call_id": "call-2"}
+            },
+            {
+                "message_type": "approval_request_message",
+                "id": "message-00000000-0000-0000-0000-000000000003",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": "local", "arguments": "{\"path\": \"/tmp\"}", "tool_call_id": "call-2"}
+            },
+            {
+                "message_type": "tool_call_message",
+                "id": "message-00000000-0000-0000-0000-000000000004",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": "web_search", "arguments": "{}", "tool_call_id": "call-3"}
+            }
+        ]))
+        .unwrap();
+
+        let tools = ClientToolRegistry::new().register("local", |_| async { Ok::<_, String>("") });
+        let pending = pending_tool_calls(&messages, &tools);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tool_call_id, "call-2");
+        assert_eq!(pending[0].name, "local");
+        assert_eq!(pending[0].arguments, "{\"path\": \"/tmp\"}");
+    }
+
+    #[test]
+    fn test_pending_tool_call_deltas() {
+        let fragment = |name: &str, arguments: &str| -> LettaMessageUnion {
+            serde_json::from_value(serde_json::json!({
+                "message_type": "approval_request_message",
+                "id": "message-00000000-0000-0000-0000-000000000001",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": name, "arguments": arguments, "tool_call_id": "call-1"}
+            }))
+            .unwrap()
+        };
+
+        let tools = ClientToolRegistry::new();
+        let mut pending = PendingToolCalls::new(true);
+        for (name, arguments) in [("local", "{\"a\": {\"b\": 1"), ("", "}"), ("", "}")] {
+            pending.observe(&fragment(name, arguments), &tools);
+        }
+        let calls = pending.take();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "local");
+        assert_eq!(calls[0].arguments, "{\"a\": {\"b\": 1}}");
+    }
+}
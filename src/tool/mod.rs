@@ -0,0 +1,44 @@
+//! Helpers for authoring custom tools.
+//!
+//! Letta tools are Python functions whose docstring and JSON schemas must
+//! agree with each other. The [`authoring`] module generates all three from a
+//! single typed [`ToolDefinition`], so they cannot drift apart, and the
+//! [`typed`] module derives that definition from a Rust argument struct. For
+//! tools written by hand, the [`validate`](mod@validate) module checks that
+//! they agree before the server does. Tools that must run in the calling
+//! process are handled by a [`ClientToolRegistry`] from the [`client`] module,
+//! and a directory of tool files is kept in step with the server by
+//! [`ToolSync`]. The [`testing`] module runs a tool on the server with
+//! sample arguments before it is created, and a [`ToolRuleSet`] checks an
+//! agent's tool rules before the agent is. With the `mcp-server` feature, the
+//! [`bridge`] module serves Rust handlers to agents as an MCP server.
+
+pub mod authoring;
+#[cfg(feature = "mcp-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mcp-server")))]
+pub mod bridge;
+pub mod client;
+pub mod rules;
+pub mod sync;
+pub mod testing;
+pub mod typed;
+pub mod validate;
+
+pub use authoring::{ParamType, ToolDefinition, ToolParam, ToolReturns};
+#[cfg(feature = "mcp-server")]
+pub use bridge::{McpBridge, McpBridgeServer};
+pub use client::{pending_tool_calls, ClientToolFuture, ClientToolRegistry};
+pub use rules::{RuleDiagnostic, ToolRuleSet};
+pub use sync::{
+    source_hash, tool_hash, LocalTool, SyncAction, SyncChange, SyncPlan, SyncReport, ToolSync,
+};
+pub use testing::{ToolTestCase, ToolTestResult, ToolTester};
+pub use typed::{LettaTool, ToolParamType};
+pub use validate::{validate, DiagnosticLocation, ToolDiagnostic};
+
+/// Derive [`LettaTool`] from a struct's fields and doc comments.
+///
+/// See the [`typed`] module for the supported attributes.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use letta_derive::LettaTool;
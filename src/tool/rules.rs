@@ -0,0 +1,738 @@
+//! Check an agent's tool rules for coherence and draw them as a graph.
+//!
+//! Tool rules constrain the order in which an agent may call its tools:
+//! `init` rules pick the first tool, `child` and `conditional` rules restrict
+//! what may follow a tool, `parent` rules gate a tool behind another, and
+//! `exit_loop` rules end the agent's step. The server accepts rules that
+//! cannot all hold, and the mistake only shows up as an agent that loops until
+//! it hits its step limit or never calls a tool. A [`ToolRuleSet`] finds these
+//! problems before the agent is created:
+//!
+//! ```
+//! use letta::tool::ToolRuleSet;
+//! use letta::types::ToolRule;
+//!
+//! let rules = ToolRuleSet::new(vec![
+//!     ToolRule::init("search"),
+//!     ToolRule::child("search", "summarize"),
+//!     ToolRule::child("summarize", "search"),
+//! ]);
+//!
+//! let diagnostics = rules.validate(&["search", "summarize", "send_message"]);
+//! assert!(diagnostics.iter().any(|d| d.code == "letta::tool_rules::no_exit"));
+//!
+//! // Render the graph for review, e.g. with `dot -Tsvg`.
+//! println!("{}", rules.to_dot());
+//! ```
+
+use crate::types::agent::ToolRule;
+use miette::Severity;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
+
+/// A set of tool rules for one agent.
+#[derive(Debug, Clone, Default)]
+pub struct ToolRuleSet {
+    rules: Vec<ToolRule>,
+}
+
+impl ToolRuleSet {
+    /// Create a rule set from an agent's tool rules.
+    pub fn new(rules: impl IntoIterator<Item = ToolRule>) -> Self {
+        Self {
+            rules: rules.into_iter().collect(),
+        }
+    }
+
+    /// Add a rule.
+    pub fn rule(mut self, rule: ToolRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The rules in the set.
+    pub fn rules(&self) -> &[ToolRule] {
+        &self.rules
+    }
+
+    /// Take the rules, e.g. for [`CreateAgentRequest::tool_rules`](crate::types::CreateAgentRequest::tool_rules).
+    pub fn into_rules(self) -> Vec<ToolRule> {
+        self.rules
+    }
+
+    /// The tools the rules mention, in the order they are first mentioned.
+    pub fn tool_names(&self) -> Vec<&str> {
+        RuleGraph::new(&self.rules).tools
+    }
+
+    /// Check the rules against the names of the agent's tools.
+    ///
+    /// Reports rules that name tools the agent does not have, tools that
+    /// both end and continue the loop, tools that can never be called, and
+    /// groups of tools that can only call each other with none of them
+    /// ending the loop.
+    ///
+    /// Returns the diagnostics in that order; an empty list means the rules
+    /// look coherent. Use [`RuleDiagnostic::is_error`] to tell rules that
+    /// will break the agent from warnings.
+    ///
+    /// # Arguments
+    ///
+    /// * `tool_names` - The names of all tools attached to the agent
+    pub fn validate<S: AsRef<str>>(&self, tool_names: &[S]) -> Vec<RuleDiagnostic> {
+        let graph = RuleGraph::new(&self.rules);
+        let attached: Vec<&str> = tool_names.iter().map(AsRef::as_ref).collect();
+        let mut diagnostics = Vec::new();
+
+        for &tool in &graph.tools {
+            if !attached.contains(&tool) {
+                diagnostics.push(RuleDiagnostic::error(
+                    "letta::tool_rules::unknown_tool",
+                    format!(
+                        "Tool rules refer to `{}`, which is not attached to the agent",
+                        tool
+                    ),
+                    [tool],
+                ));
+            }
+        }
+
+        for &tool in &graph.tools {
+            if !graph.exit.contains(tool) {
+                continue;
+            }
+            if graph.continue_loop.contains(tool) {
+                diagnostics.push(RuleDiagnostic::error(
+                    "letta::tool_rules::conflicting_terminal_rules",
+                    format!("`{}` both exits and continues the loop", tool),
+                    [tool],
+                ));
+            }
+            if graph.children.contains_key(tool) {
+                diagnostics.push(RuleDiagnostic::error(
+                    "letta::tool_rules::conflicting_terminal_rules",
+                    format!(
+                        "`{}` exits the loop, so the tools its child rules allow next are never called",
+                        tool
+                    ),
+                    [tool],
+                ));
+            }
+        }
+        for rule in &self.rules {
+            if let ToolRule::Terminal { tool_name, .. } = rule {
+                diagnostics.push(RuleDiagnostic {
+                    severity: Severity::Advice,
+                    code: "letta::tool_rules::deprecated_terminal",
+                    message: format!(
+                        "The terminal rule for `{}` is deprecated; use `exit_loop` instead",
+                        tool_name
+                    ),
+                    tools: vec![tool_name.clone()],
+                });
+            }
+        }
+
+        // Every known tool takes part, whether or not a rule mentions it.
+        let mut tools = graph.tools.clone();
+        for &tool in &attached {
+            if !tools.contains(&tool) {
+                tools.push(tool);
+            }
+        }
+
+        for group in graph.traps(&tools) {
+            let names = group
+                .iter()
+                .map(|tool| format!("`{}`", tool))
+                .collect::<Vec<_>>()
+                .join(", ");
+            diagnostics.push(RuleDiagnostic::error(
+                "letta::tool_rules::no_exit",
+                format!(
+                    "Once {} is called, the agent can only call {} and never exits the loop",
+                    if group.len() == 1 {
+                        "it"
+                    } else {
+                        "any of them"
+                    },
+                    names
+                ),
+                group,
+            ));
+        }
+
+        let reachable = graph.reachable(&tools);
+        for &tool in &tools {
+            if reachable.contains(tool) {
+                continue;
+            }
+            diagnostics.push(if graph.required.contains(tool) {
+                RuleDiagnostic::error(
+                    "letta::tool_rules::unreachable_required_tool",
+                    format!(
+                        "`{}` must be called before exiting, but the rules never allow it",
+                        tool
+                    ),
+                    [tool],
+                )
+            } else {
+                RuleDiagnostic::warning(
+                    "letta::tool_rules::unreachable_tool",
+                    format!("The rules never allow `{}` to be called", tool),
+                    [tool],
+                )
+            });
+        }
+
+        diagnostics
+    }
+
+    /// Render the rule graph in Graphviz DOT format.
+    ///
+    /// Solid edges are `child` rules, labelled edges map a `conditional`
+    /// rule's output to the next tool, and dashed edges are `conditional`
+    /// defaults. Dotted edges run from a `parent` rule's parent to the tool
+    /// it gates. Tools with `init` rules are drawn bold and linked from a
+    /// start point; tools with `exit_loop` rules are double-bordered and
+    /// linked to the exit.
+    pub fn to_dot(&self) -> String {
+        let graph = RuleGraph::new(&self.rules);
+        let mut dot =
+            String::from("digraph tool_rules {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        if !graph.init.is_empty() {
+            dot.push_str("    \"__start__\" [shape=circle, label=\"start\"];\n");
+        }
+        if !graph.exit.is_empty() {
+            dot.push_str("    \"__exit__\" [shape=doublecircle, label=\"exit\"];\n");
+        }
+        for &tool in &graph.tools {
+            let mut attrs = vec![format!("label={}", dot_string(&graph.label(tool, "\n")))];
+            if graph.init.contains(&tool) {
+                attrs.push("style=bold".to_string());
+            }
+            if graph.exit.contains(tool) {
+                attrs.push("peripheries=2".to_string());
+            }
+            let _ = writeln!(dot, "    {} [{}];", dot_string(tool), attrs.join(", "));
+        }
+
+        for &tool in &graph.init {
+            let _ = writeln!(dot, "    \"__start__\" -> {};", dot_string(tool));
+        }
+        for edge in &graph.edges {
+            let attrs = match edge.kind {
+                EdgeKind::Child => String::new(),
+                EdgeKind::Output(output) => format!(" [label={}]", dot_string(output)),
+                EdgeKind::Default => " [label=\"default\", style=dashed]".to_string(),
+                EdgeKind::Parent => " [label=\"parent\", style=dotted]".to_string(),
+            };
+            let _ = writeln!(
+                dot,
+                "    {} -> {}{};",
+                dot_string(edge.from),
+                dot_string(edge.to),
+                attrs
+            );
+        }
+        for &tool in &graph.tools {
+            if graph.exit.contains(tool) {
+                let _ = writeln!(dot, "    {} -> \"__exit__\";", dot_string(tool));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the rule graph as a Mermaid flowchart.
+    ///
+    /// Edges follow the same conventions as [`to_dot`](Self::to_dot), with
+    /// dotted `parent` and `default` edges told apart by their labels.
+    pub fn to_mermaid(&self) -> String {
+        let graph = RuleGraph::new(&self.rules);
+        // Tool names are not always valid Mermaid ids, so number them.
+        let ids: HashMap<&str, String> = graph
+            .tools
+            .iter()
+            .enumerate()
+            .map(|(i, &tool)| (tool, format!("t{}", i)))
+            .collect();
+        let mut mermaid = String::from("flowchart LR\n");
+
+        if !graph.init.is_empty() {
+            mermaid.push_str("    start((start))\n");
+        }
+        if !graph.exit.is_empty() {
+            mermaid.push_str("    stop(((exit)))\n");
+        }
+        for &tool in &graph.tools {
+            let _ = writeln!(
+                mermaid,
+                "    {}[{}]",
+                ids[tool],
+                mermaid_string(&graph.label(tool, "<br/>"))
+            );
+        }
+
+        for &tool in &graph.init {
+            let _ = writeln!(mermaid, "    start --> {}", ids[tool]);
+        }
+        for edge in &graph.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Child => "-->".to_string(),
+                EdgeKind::Output(output) => format!("-->|{}|", mermaid_string(output)),
+                EdgeKind::Default => "-.->|default|".to_string(),
+                EdgeKind::Parent => "-.->|parent|".to_string(),
+            };
+            let _ = writeln!(mermaid, "    {} {} {}", ids[edge.from], arrow, ids[edge.to]);
+        }
+        for &tool in &graph.tools {
+            if graph.exit.contains(tool) {
+                let _ = writeln!(mermaid, "    {} --> stop", ids[tool]);
+            }
+        }
+
+        let init: Vec<&str> = graph.init.iter().map(|tool| ids[tool].as_str()).collect();
+        if !init.is_empty() {
+            mermaid.push_str("    classDef init stroke-width:3px\n");
+            let _ = writeln!(mermaid, "    class {} init", init.join(","));
+        }
+
+        mermaid
+    }
+}
+
+impl From<Vec<ToolRule>> for ToolRuleSet {
+    fn from(rules: Vec<ToolRule>) -> Self {
+        Self { rules }
+    }
+}
+
+impl FromIterator<ToolRule> for ToolRuleSet {
+    fn from_iter<I: IntoIterator<Item = ToolRule>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// A problem found by [`ToolRuleSet::validate`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct RuleDiagnostic {
+    /// [`Severity::Error`] if the agent will not behave as the rules intend,
+    /// [`Severity::Warning`] if a tool is likely misconfigured, and
+    /// [`Severity::Advice`] for deprecated rules.
+    pub severity: Severity,
+    /// Stable identifier, e.g. `letta::tool_rules::unknown_tool`.
+    pub code: &'static str,
+    /// Human-readable description of the problem.
+    pub message: String,
+    /// The tools involved.
+    pub tools: Vec<String>,
+}
+
+impl RuleDiagnostic {
+    fn error<S: ToString>(
+        code: &'static str,
+        message: String,
+        tools: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message,
+            tools: tools.into_iter().map(|tool| tool.to_string()).collect(),
+        }
+    }
+
+    fn warning<S: ToString>(
+        code: &'static str,
+        message: String,
+        tools: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message, tools)
+        }
+    }
+
+    /// Whether the agent will not behave as the rules intend.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl miette::Diagnostic for RuleDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.code))
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeKind<'a> {
+    /// A `child` rule.
+    Child,
+    /// A `conditional` rule's mapping from this output.
+    Output(&'a str),
+    /// A `conditional` rule's default child.
+    Default,
+    /// A `parent` rule, from the parent to the tool it gates.
+    Parent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Edge<'a> {
+    from: &'a str,
+    to: &'a str,
+    kind: EdgeKind<'a>,
+}
+
+/// The rules, indexed by tool.
+#[derive(Debug, Default)]
+struct RuleGraph<'a> {
+    /// Tools in the order they are first mentioned.
+    tools: Vec<&'a str>,
+    init: Vec<&'a str>,
+    exit: HashSet<&'a str>,
+    continue_loop: HashSet<&'a str>,
+    required: HashSet<&'a str>,
+    max_count: HashMap<&'a str, u32>,
+    edges: Vec<Edge<'a>>,
+    /// The tools allowed after each tool whose successors are restricted.
+    children: HashMap<&'a str, Vec<&'a str>>,
+    /// Tools whose conditional rule allows any tool when the output is unmapped.
+    open: HashSet<&'a str>,
+    /// The tools each parent-gated tool must follow.
+    parents: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> RuleGraph<'a> {
+    fn new(rules: &'a [ToolRule]) -> Self {
+        let mut graph = Self::default();
+        for rule in rules {
+            let tool = rule.tool_name();
+            graph.add_tool(tool);
+            match rule {
+                ToolRule::Init { .. } => {
+                    if !graph.init.contains(&tool) {
+                        graph.init.push(tool);
+                    }
+                }
+                ToolRule::ExitLoop { .. } | ToolRule::Terminal { .. } => {
+                    graph.exit.insert(tool);
+                }
+                ToolRule::ContinueLoop { .. } => {
+                    graph.continue_loop.insert(tool);
+                }
+                ToolRule::RequiredBeforeExit { .. } => {
+                    graph.required.insert(tool);
+                }
+                ToolRule::MaxCountPerStep {
+                    max_count_limit, ..
+                } => {
+                    graph.max_count.insert(tool, *max_count_limit);
+                }
+                ToolRule::Child {
+                    child_tool_name, ..
+                } => {
+                    graph.add_edge(tool, child_tool_name, EdgeKind::Child);
+                }
+                ToolRule::Parent {
+                    parent_tool_name, ..
+                } => {
+                    graph.add_tool(parent_tool_name);
+                    graph.edges.push(Edge {
+                        from: parent_tool_name,
+                        to: tool,
+                        kind: EdgeKind::Parent,
+                    });
+                    graph
+                        .parents
+                        .entry(tool)
+                        .or_default()
+                        .push(parent_tool_name);
+                }
+                ToolRule::Conditional {
+                    default_child,
+                    child_output_mapping,
+                    require_output_mapping,
+                    ..
+                } => {
+                    let mut mapping: Vec<_> = child_output_mapping.iter().collect();
+                    mapping.sort();
+                    for (output, child) in mapping {
+                        graph.add_edge(tool, child, EdgeKind::Output(output));
+                    }
+                    match default_child {
+                        Some(child) => graph.add_edge(tool, child, EdgeKind::Default),
+                        None if !require_output_mapping => {
+                            graph.open.insert(tool);
+                        }
+                        None => {}
+                    }
+                    graph.children.entry(tool).or_default();
+                }
+            }
+        }
+        graph
+    }
+
+    fn add_tool(&mut self, tool: &'a str) {
+        if !self.tools.contains(&tool) {
+            self.tools.push(tool);
+        }
+    }
+
+    fn add_edge(&mut self, from: &'a str, to: &'a str, kind: EdgeKind<'a>) {
+        self.add_tool(to);
+        let children = self.children.entry(from).or_default();
+        if !children.contains(&to) {
+            children.push(to);
+        }
+        self.edges.push(Edge { from, to, kind });
+    }
+
+    /// Whether the agent may call any tool after `tool`.
+    fn is_unrestricted(&self, tool: &str) -> bool {
+        !self.children.contains_key(tool) || self.open.contains(tool)
+    }
+
+    /// The tools the agent can call at some point, starting from the `init`
+    /// tools (or from any tool without them).
+    fn reachable(&self, tools: &[&'a str]) -> HashSet<&'a str> {
+        let allowed = |tool: &str, reached: &HashSet<&str>| {
+            self.parents
+                .get(tool)
+                .is_none_or(|parents| parents.iter().any(|parent| reached.contains(parent)))
+        };
+
+        let mut reached: HashSet<&'a str> = HashSet::new();
+        if self.init.is_empty() {
+            reached.extend(
+                tools
+                    .iter()
+                    .filter(|tool| !self.parents.contains_key(*tool)),
+            );
+        } else {
+            reached.extend(self.init.iter().copied());
+        }
+
+        loop {
+            let mut next: Vec<&'a str> = Vec::new();
+            for &tool in &reached {
+                if self.exit.contains(tool) {
+                    continue;
+                }
+                let successors: &[&'a str] = if self.is_unrestricted(tool) {
+                    tools
+                } else {
+                    &self.children[tool]
+                };
+                next.extend(
+                    successors
+                        .iter()
+                        .filter(|next| !reached.contains(*next) && allowed(next, &reached)),
+                );
+            }
+            if next.is_empty() {
+                return reached;
+            }
+            reached.extend(next);
+        }
+    }
+
+    /// Groups of tools from which no sequence of calls ends the loop.
+    fn traps(&self, tools: &[&'a str]) -> Vec<Vec<&'a str>> {
+        let mut can_exit: HashSet<&str> = tools
+            .iter()
+            .copied()
+            .filter(|tool| self.exit.contains(tool) || self.is_unrestricted(tool))
+            .collect();
+        loop {
+            let before = can_exit.len();
+            for (&tool, children) in &self.children {
+                if children.iter().any(|child| can_exit.contains(child)) {
+                    can_exit.insert(tool);
+                }
+            }
+            if can_exit.len() == before {
+                break;
+            }
+        }
+
+        // Group the trapped tools by the child rules linking them.
+        let trapped: Vec<&'a str> = tools
+            .iter()
+            .copied()
+            .filter(|tool| !can_exit.contains(tool))
+            .collect();
+        let mut groups: Vec<Vec<&'a str>> = Vec::new();
+        let mut seen = HashSet::new();
+        for &start in &trapped {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut group = vec![start];
+            let mut i = 0;
+            while i < group.len() {
+                let tool = group[i];
+                for edge in &self.edges {
+                    if edge.kind == EdgeKind::Parent {
+                        continue;
+                    }
+                    let other = if edge.from == tool {
+                        edge.to
+                    } else if edge.to == tool {
+                        edge.from
+                    } else {
+                        continue;
+                    };
+                    if trapped.contains(&other) && seen.insert(other) {
+                        group.push(other);
+                    }
+                }
+                i += 1;
+            }
+            group.sort_by_key(|tool| tools.iter().position(|t| t == tool));
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// The tool's name with notes for the rules that do not draw as edges.
+    fn label(&self, tool: &str, separator: &str) -> String {
+        let mut label = tool.to_string();
+        if let Some(limit) = self.max_count.get(tool) {
+            let _ = write!(label, "{}max {} per step", separator, limit);
+        }
+        if self.required.contains(tool) {
+            let _ = write!(label, "{}required before exit", separator);
+        }
+        if self.continue_loop.contains(tool) {
+            let _ = write!(label, "{}continues loop", separator);
+        }
+        label
+    }
+}
+
+/// Quote a DOT identifier or label.
+fn dot_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Quote a Mermaid label.
+fn mermaid_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "#quot;"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(diagnostics: &[RuleDiagnostic]) -> Vec<(&'static str, Vec<&str>)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.code, d.tools.iter().map(String::as_str).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let tools = ["search", "summarize", "send_message", "archive", "notify"];
+
+        let coherent = ToolRuleSet::new(vec![
+            ToolRule::init("search"),
+            ToolRule::child("search", "summarize"),
+            ToolRule::conditional("summarize")
+                .with_mapping("done", "send_message")
+                .with_mapping("more", "search")
+                .build(),
+            ToolRule::exit_loop("send_message"),
+            ToolRule::parent("archive", "summarize"),
+        ]);
+        assert_eq!(codes(&coherent.validate(&tools)), vec![]);
+
+        let broken = ToolRuleSet::new(vec![
+            ToolRule::init("search"),
+            ToolRule::child("search", "summarize"),
+            ToolRule::child("summarize", "search"),
+            ToolRule::child("send_message", "search"),
+            ToolRule::exit_loop("send_message"),
+            ToolRule::required_before_exit("notify"),
+            ToolRule::terminal("missing"),
+        ]);
+        assert_eq!(
+            codes(&broken.validate(&tools)),
+            vec![
+                ("letta::tool_rules::unknown_tool", vec!["missing"]),
+                (
+                    "letta::tool_rules::conflicting_terminal_rules",
+                    vec!["send_message"]
+                ),
+                ("letta::tool_rules::deprecated_terminal", vec!["missing"]),
+                ("letta::tool_rules::no_exit", vec!["search", "summarize"]),
+                ("letta::tool_rules::unreachable_tool", vec!["send_message"]),
+                (
+                    "letta::tool_rules::unreachable_required_tool",
+                    vec!["notify"]
+                ),
+                ("letta::tool_rules::unreachable_tool", vec!["missing"]),
+                ("letta::tool_rules::unreachable_tool", vec!["archive"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rule_graph_export() {
+        let mut search = ToolRule::conditional("search").with_mapping("found", "send_message");
+        search.default_child = Some("search".to_string());
+        let rules = ToolRuleSet::new(vec![
+            ToolRule::init("search"),
+            search.build(),
+            ToolRule::exit_loop("send_message"),
+            ToolRule::max_count_per_step("search", 3),
+        ]);
+
+        assert_eq!(
+            rules.to_dot(),
+            r#"digraph tool_rules {
+    rankdir=LR;
+    node [shape=box];
+    "__start__" [shape=circle, label="start"];
+    "__exit__" [shape=doublecircle, label="exit"];
+    "search" [label="search\nmax 3 per step", style=bold];
+    "send_message" [label="send_message", peripheries=2];
+    "__start__" -> "search";
+    "search" -> "send_message" [label="found"];
+    "search" -> "search" [label="default", style=dashed];
+    "send_message" -> "__exit__";
+}
+"#
+        );
+
+        assert_eq!(
+            rules.to_mermaid(),
+            r#"flowchart LR
+    start((start))
+    stop(((exit)))
+    t0["search<br/>max 3 per step"]
+    t1["send_message"]
+    start --> t0
+    t0 -->|"found"| t1
+    t0 -.->|default| t0
+    t1 --> stop
+    classDef init stroke-width:3px
+    class t0 init
+"#
+        );
+    }
+}
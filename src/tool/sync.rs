@@ -0,0 +1,741 @@
+//! Reconcile a directory of tool files with the tools on the server.
+//!
+//! Each `<stem>.py` file in the directory is one tool. Its JSON schema is read
+//! from `<stem>_schema.json` (or `<stem>.json`) next to it, and an arguments
+//! schema from `<stem>_args_schema.json`; without a schema the server derives
+//! one from the docstring. The tool is named by the schema, or else by the
+//! Python function.
+//!
+//! [`ToolSync::plan`] compares the local tools with the server's by name and
+//! a hash of the source code and any local schemas, and [`SyncPlan::apply`] creates, updates and
+//! (optionally) deletes tools to match:
+//!
+//! ```no_run
+//! # use letta::tool::ToolSync;
+//! # async fn example(client: &letta::LettaClient) -> letta::LettaResult<()> {
+//! let sync = ToolSync::new("tools/").tag("managed-by-git").delete_missing(true);
+//!
+//! let plan = sync.plan(client).await?;
+//! print!("{plan}");
+//! if !plan.is_empty() {
+//!     plan.apply(client).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::validate::{function_name, validate, ToolDiagnostic};
+use crate::client::LettaClient;
+use crate::error::{LettaError, LettaResult};
+use crate::types::tool::{CreateToolRequest, SourceType, Tool, ToolType};
+use crate::types::LettaId;
+use crate::utils::Fnv1a;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Synchronizes a directory of tool files with the server.
+#[derive(Debug, Clone)]
+pub struct ToolSync {
+    dir: PathBuf,
+    delete_missing: bool,
+    tag: Option<String>,
+}
+
+impl ToolSync {
+    /// Sync the tools in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            delete_missing: false,
+            tag: None,
+        }
+    }
+
+    /// Delete custom tools on the server that have no local file.
+    ///
+    /// Combine with [`tag`](Self::tag) to limit deletion to tools created by
+    /// this sync; otherwise every custom tool not in the directory is deleted.
+    /// Built-in, MCP and Composio tools are never deleted.
+    pub fn delete_missing(mut self, delete_missing: bool) -> Self {
+        self.delete_missing = delete_missing;
+        self
+    }
+
+    /// Tag every synced tool, and only consider tagged tools for deletion.
+    ///
+    /// A server tool with the same name as a local one but without the tag
+    /// is reported as a [`SyncAction::Conflict`] and left unchanged.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Read the tools in the directory, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the directory or a tool file
+    /// cannot be read, a schema is not valid JSON, or two files define the
+    /// same tool.
+    pub fn discover(&self) -> LettaResult<Vec<LocalTool>> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "py") {
+                paths.push(path);
+            }
+        }
+
+        let mut tools: Vec<LocalTool> = Vec::with_capacity(paths.len());
+        for path in paths {
+            let tool = LocalTool::load(&path, self.tag.as_deref())?;
+            if let Some(existing) = tools.iter().find(|t| t.name == tool.name) {
+                return Err(LettaError::validation_field(
+                    format!(
+                        "Tool '{}' is defined by both {} and {}",
+                        tool.name,
+                        existing.path.display(),
+                        path.display()
+                    ),
+                    "name",
+                ));
+            }
+            tools.push(tool);
+        }
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(tools)
+    }
+
+    /// Compare the local tools with the server's and work out what to change.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if discovery fails or the server's
+    /// tools cannot be listed.
+    pub async fn plan(&self, client: &LettaClient) -> LettaResult<SyncPlan> {
+        let local = self.discover()?;
+        let remote = client.tools().paginated(None).collect().await?;
+        Ok(self.diff(local, remote))
+    }
+
+    /// Plan and apply the changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if planning fails, a tool fails
+    /// validation, or a request fails.
+    pub async fn sync(&self, client: &LettaClient) -> LettaResult<SyncReport> {
+        self.plan(client).await?.apply(client).await
+    }
+
+    fn diff(&self, local: Vec<LocalTool>, remote: Vec<Tool>) -> SyncPlan {
+        let remote_by_name: HashMap<&str, &Tool> = remote
+            .iter()
+            .map(|tool| (tool.name.as_str(), tool))
+            .collect();
+
+        let mut changes = Vec::new();
+        for tool in local {
+            let change = match remote_by_name.get(tool.name.as_str()) {
+                None => SyncChange::new(SyncAction::Create, tool.name.clone(), None),
+                Some(existing) => {
+                    // Only compare the schemas the local tool defines; the
+                    // server derives the others from the docstring.
+                    let remote_hash = tool_hash(
+                        existing.source_code.as_deref().unwrap_or(""),
+                        tool.request
+                            .json_schema
+                            .as_ref()
+                            .and(existing.json_schema.as_ref()),
+                        tool.request
+                            .args_json_schema
+                            .as_ref()
+                            .and(existing.args_json_schema.as_ref()),
+                    );
+                    // A same-named tool without the tag was not made by this
+                    // sync, so it is left alone rather than taken over.
+                    let tagged = self.tag.as_ref().is_none_or(|tag| {
+                        existing
+                            .tags
+                            .as_ref()
+                            .is_some_and(|tags| tags.contains(tag))
+                    });
+                    let action = if !tagged {
+                        SyncAction::Conflict
+                    } else if remote_hash == tool.hash {
+                        SyncAction::Unchanged
+                    } else {
+                        SyncAction::Update
+                    };
+                    let mut change =
+                        SyncChange::new(action, tool.name.clone(), existing.id.clone());
+                    change.remote_hash = Some(remote_hash);
+                    change
+                }
+            };
+            changes.push(SyncChange {
+                path: Some(tool.path.clone()),
+                local_hash: Some(tool.hash.clone()),
+                local: Some(tool),
+                ..change
+            });
+        }
+
+        if self.delete_missing {
+            for tool in &remote {
+                let deletable = matches!(tool.tool_type, None | Some(ToolType::Custom))
+                    && self.tag.as_ref().is_none_or(|tag| {
+                        tool.tags.as_ref().is_some_and(|tags| tags.contains(tag))
+                    });
+                if deletable && !changes.iter().any(|change| change.name == tool.name) {
+                    let mut change =
+                        SyncChange::new(SyncAction::Delete, tool.name.clone(), tool.id.clone());
+                    change.remote_hash = tool.source_code.as_deref().map(source_hash);
+                    changes.push(change);
+                }
+            }
+        }
+
+        SyncPlan { changes }
+    }
+}
+
+/// A tool read from the sync directory.
+#[derive(Debug, Clone)]
+pub struct LocalTool {
+    /// Tool name.
+    pub name: String,
+    /// Path of the Python file.
+    pub path: PathBuf,
+    /// Path of the JSON schema, if there is one.
+    pub schema_path: Option<PathBuf>,
+    /// Request that creates the tool.
+    pub request: CreateToolRequest,
+    /// Hash of the source code and schemas (see [`tool_hash`]).
+    pub hash: String,
+    /// Problems found by [`validate`](super::validate()).
+    pub diagnostics: Vec<ToolDiagnostic>,
+}
+
+impl LocalTool {
+    /// Read a single tool file, with its schemas if they exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a file cannot be read, a schema
+    /// is not valid JSON, or the tool has no name.
+    pub fn from_file(path: impl AsRef<Path>) -> LettaResult<Self> {
+        Self::load(path.as_ref(), None)
+    }
+
+    fn load(path: &Path, tag: Option<&str>) -> LettaResult<Self> {
+        let source_code = std::fs::read_to_string(path)?;
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        let sibling = |name: String| Some(path.with_file_name(name)).filter(|p| p.is_file());
+
+        let schema_path =
+            sibling(format!("{}_schema.json", stem)).or_else(|| sibling(format!("{}.json", stem)));
+        let json_schema = schema_path.as_deref().map(read_json).transpose()?;
+        let args_json_schema = match sibling(format!("{}_args_schema.json", stem)) {
+            Some(args_path) => Some(read_json(&args_path)?),
+            None => json_schema
+                .as_ref()
+                .and_then(|schema| schema.get("parameters"))
+                .cloned(),
+        };
+
+        let name = json_schema
+            .as_ref()
+            .and_then(|schema| schema.get("name"))
+            .and_then(|name| name.as_str())
+            .or_else(|| function_name(&source_code))
+            .ok_or_else(|| {
+                LettaError::validation_field(
+                    format!("No tool function found in {}", path.display()),
+                    "source_code",
+                )
+            })?
+            .to_string();
+        let description = json_schema
+            .as_ref()
+            .and_then(|schema| schema.get("description"))
+            .and_then(|description| description.as_str())
+            .map(String::from);
+
+        let hash = tool_hash(
+            &source_code,
+            json_schema.as_ref(),
+            args_json_schema.as_ref(),
+        );
+        let request = CreateToolRequest {
+            description,
+            tags: tag.map(|tag| vec![tag.to_string()]),
+            source_code,
+            source_type: Some(SourceType::Python),
+            json_schema,
+            args_json_schema,
+            ..Default::default()
+        };
+        let diagnostics = validate(&request);
+
+        Ok(Self {
+            name,
+            path: path.to_path_buf(),
+            schema_path,
+            request,
+            hash,
+            diagnostics,
+        })
+    }
+
+    /// Whether the tool has problems the server will reject.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(ToolDiagnostic::is_error)
+    }
+}
+
+fn read_json(path: &Path) -> LettaResult<serde_json::Value> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        LettaError::validation_field(
+            format!("Invalid JSON in {}: {}", path.display(), e),
+            "json_schema",
+        )
+    })
+}
+
+/// Stable hash of source code, ignoring line endings and trailing whitespace.
+///
+/// 64-bit FNV-1a, as 16 hex digits.
+pub fn source_hash(source: &str) -> String {
+    tool_hash(source, None, None)
+}
+
+/// Stable hash of a tool's source code and, if given, its schemas.
+///
+/// Schemas are hashed as canonical JSON, so key order and formatting don't
+/// matter. Without schemas this is the [`source_hash`].
+pub fn tool_hash(
+    source: &str,
+    json_schema: Option<&Value>,
+    args_json_schema: Option<&Value>,
+) -> String {
+    let mut hasher = Fnv1a::new();
+    for line in source.trim_end().lines() {
+        hasher.write(line.trim_end().as_bytes());
+        hasher.write(b"\n");
+    }
+    for (label, schema) in [
+        ("json_schema", json_schema),
+        ("args_json_schema", args_json_schema),
+    ] {
+        if let Some(schema) = schema {
+            hasher.write(b"\0");
+            hasher.write(label.as_bytes());
+            hasher.write(canonical_json(schema).as_bytes());
+        }
+    }
+    hasher.finish_hex()
+}
+
+/// Serialize `value` with object keys sorted at every level.
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonical_json(value))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// What a sync does to one tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    /// The tool only exists locally and will be created.
+    Create,
+    /// The tool's source or schemas differ from the server's and it will be
+    /// replaced.
+    Update,
+    /// The tool only exists on the server and will be deleted.
+    Delete,
+    /// The tool is up to date.
+    Unchanged,
+    /// A tool with this name exists on the server without the sync tag, so
+    /// it will be left unchanged.
+    Conflict,
+}
+
+impl SyncAction {
+    fn symbol(self) -> char {
+        match self {
+            Self::Create => '+',
+            Self::Update => '~',
+            Self::Delete => '-',
+            Self::Unchanged => '=',
+            Self::Conflict => '!',
+        }
+    }
+}
+
+/// A planned change to one tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncChange {
+    /// Tool name.
+    pub name: String,
+    /// What will be done.
+    pub action: SyncAction,
+    /// ID of the tool on the server, if it exists there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_id: Option<LettaId>,
+    /// Local Python file, if the tool exists locally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Hash of the local source code and schemas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_hash: Option<String>,
+    /// Hash of the server's source code and matching schemas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_hash: Option<String>,
+    /// The local tool, if the tool exists locally.
+    #[serde(skip)]
+    pub local: Option<LocalTool>,
+}
+
+impl SyncChange {
+    fn new(action: SyncAction, name: String, tool_id: Option<LettaId>) -> Self {
+        Self {
+            name,
+            action,
+            tool_id,
+            path: None,
+            local_hash: None,
+            remote_hash: None,
+            local: None,
+        }
+    }
+}
+
+/// The changes needed to bring the server in line with the directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPlan {
+    /// One entry per local tool, then one per tool to delete.
+    pub changes: Vec<SyncChange>,
+}
+
+impl SyncPlan {
+    /// Whether there is nothing to apply: every tool is up to date or in
+    /// conflict.
+    pub fn is_empty(&self) -> bool {
+        self.changes
+            .iter()
+            .all(|change| matches!(change.action, SyncAction::Unchanged | SyncAction::Conflict))
+    }
+
+    /// Number of changes with the given action.
+    pub fn count(&self, action: SyncAction) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.action == action)
+            .count()
+    }
+
+    /// Local tools that fail validation; the plan cannot be applied while
+    /// there are any.
+    pub fn invalid_tools(&self) -> impl Iterator<Item = &LocalTool> {
+        self.changes
+            .iter()
+            .filter_map(|change| change.local.as_ref())
+            .filter(|tool| tool.has_errors())
+    }
+
+    /// Create, update and delete tools as planned.
+    ///
+    /// Changes are applied in order and stop at the first failure. Tools in
+    /// conflict are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if a local tool fails validation
+    /// (before anything is changed) or if a request fails.
+    pub async fn apply(&self, client: &LettaClient) -> LettaResult<SyncReport> {
+        let invalid: Vec<&str> = self
+            .invalid_tools()
+            .map(|tool| tool.name.as_str())
+            .collect();
+        if !invalid.is_empty() {
+            return Err(LettaError::validation(format!(
+                "Not syncing: {} failed validation",
+                invalid.join(", ")
+            )));
+        }
+
+        let mut report = SyncReport::default();
+        for change in &self.changes {
+            let request = change.local.as_ref().map(|tool| tool.request.clone());
+            match (change.action, request, &change.tool_id) {
+                (SyncAction::Create, Some(request), _) => {
+                    report.created.push(client.tools().create(request).await?);
+                }
+                (SyncAction::Update, Some(request), _) => {
+                    report.updated.push(client.tools().upsert(request).await?);
+                }
+                (SyncAction::Delete, _, Some(tool_id)) => {
+                    client.tools().delete(tool_id).await?;
+                    report.deleted.push(change.name.clone());
+                }
+                (SyncAction::Conflict, _, _) => report.conflicts.push(change.name.clone()),
+                _ => report.unchanged.push(change.name.clone()),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .changes
+            .iter()
+            .map(|change| change.name.len())
+            .max()
+            .unwrap_or(0);
+        for change in &self.changes {
+            write!(
+                f,
+                "{} {:<9} {:<width$}",
+                change.action.symbol(),
+                format!("{:?}", change.action).to_lowercase(),
+                change.name,
+                width = width
+            )?;
+            if let Some(path) = &change.path {
+                write!(f, "  {}", path.display())?;
+            }
+            match (&change.remote_hash, &change.local_hash) {
+                (Some(remote), Some(local)) if change.action == SyncAction::Update => {
+                    write!(f, "  {} -> {}", &remote[..8], &local[..8])?
+                }
+                (_, Some(hash)) | (Some(hash), None) => write!(f, "  {}", &hash[..8])?,
+                (None, None) => {}
+            }
+            writeln!(f)?;
+        }
+        write!(
+            f,
+            "{} to create, {} to update, {} to delete, {} unchanged",
+            self.count(SyncAction::Create),
+            self.count(SyncAction::Update),
+            self.count(SyncAction::Delete),
+            self.count(SyncAction::Unchanged)
+        )?;
+        match self.count(SyncAction::Conflict) {
+            0 => writeln!(f),
+            conflicts => writeln!(f, ", {} in conflict", conflicts),
+        }
+    }
+}
+
+/// Outcome of [`SyncPlan::apply`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Tools that were created.
+    pub created: Vec<Tool>,
+    /// Tools that were updated.
+    pub updated: Vec<Tool>,
+    /// Names of the tools that were deleted.
+    pub deleted: Vec<String>,
+    /// Names of the tools that were already up to date.
+    pub unchanged: Vec<String>,
+    /// Names of the tools skipped because a server tool without the sync tag
+    /// has the same name.
+    pub conflicts: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEATHER: &str = r#"def get_weather(location: str) -> str:
+    """
+    Get current weather for a location.
+
+    Args:
+        location: The city to get weather for
+
+    Returns:
+        str: A description of the weather
+    """
+    return f"Sunny in {location}"
+"#;
+
+    fn remote(name: &str, source: &str, tags: &[&str]) -> Tool {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("tool-00000000-0000-0000-0000-00000000000{}", name.len() % 10),
+            "name": name,
+            "tool_type": "custom",
+            "source_code": source,
+            "tags": tags,
+        }))
+        .unwrap()
+    }
+
+    fn write_tools(dir: &Path) {
+        std::fs::write(dir.join("weather.py"), WEATHER).unwrap();
+        std::fs::write(
+            dir.join("shout.py"),
+            WEATHER
+                .replace("get_weather", "shout")
+                .replace("Sunny in ", ""),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("shout_schema.json"),
+            serde_json::json!({
+                "name": "shout_loud",
+                "description": "Shout.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "location": {"type": "string", "description": "The city"}
+                    },
+                    "required": ["location"]
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a tool").unwrap();
+    }
+
+    #[test]
+    fn test_source_hash() {
+        assert_eq!(source_hash("a\r\nb  \n\n"), source_hash("a\nb"));
+        assert_ne!(source_hash("a\nb"), source_hash("a\nc"));
+        assert_eq!(source_hash("").len(), 16);
+    }
+
+    #[test]
+    fn test_tool_hash_schemas() {
+        let schema =
+            serde_json::json!({"name": "f", "parameters": {"type": "object", "required": []}});
+        let reordered =
+            serde_json::json!({"parameters": {"required": [], "type": "object"}, "name": "f"});
+        assert_eq!(tool_hash("src", None, None), source_hash("src"));
+        assert_eq!(
+            tool_hash("src", Some(&schema), None),
+            tool_hash("src", Some(&reordered), None)
+        );
+        assert_ne!(tool_hash("src", Some(&schema), None), source_hash("src"));
+        assert_ne!(
+            tool_hash("src", Some(&schema), None),
+            tool_hash("src", None, Some(&schema))
+        );
+    }
+
+    #[test]
+    fn test_discover_and_diff() {
+        let dir = std::env::temp_dir().join(format!("letta-tool-sync-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_tools(&dir);
+
+        let sync = ToolSync::new(&dir).tag("git").delete_missing(true);
+        let local = sync.discover().unwrap();
+        assert_eq!(
+            local.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["get_weather", "shout_loud"]
+        );
+        assert!(local[0].schema_path.is_none());
+        assert!(local[1].schema_path.is_some());
+        assert_eq!(local[0].request.tags, Some(vec!["git".to_string()]));
+        // The schema names the tool differently from the function
+        assert!(local[1].has_errors());
+
+        let plan = sync.diff(
+            local,
+            vec![
+                remote("get_weather", WEATHER, &["git"]),
+                remote("stale", "def stale(): pass", &["git"]),
+                remote("hand_made", "def hand_made(): pass", &[]),
+            ],
+        );
+        let actions: Vec<_> = plan
+            .changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("get_weather", SyncAction::Unchanged),
+                ("shout_loud", SyncAction::Create),
+                ("stale", SyncAction::Delete),
+            ]
+        );
+        assert_eq!(plan.invalid_tools().count(), 1);
+        assert!(plan
+            .to_string()
+            .ends_with("1 to create, 0 to update, 1 to delete, 1 unchanged\n"));
+
+        let plan = ToolSync::new(&dir).diff(
+            sync.discover().unwrap(),
+            vec![remote("get_weather", "def get_weather(): pass", &[])],
+        );
+        assert_eq!(plan.changes[0].action, SyncAction::Update);
+        assert_eq!(plan.count(SyncAction::Delete), 0);
+
+        // With a tag, an untagged tool of the same name is not taken over.
+        let weather = sync.discover().unwrap().remove(0);
+        for source in [WEATHER, "def get_weather(): pass"] {
+            let plan = sync.diff(
+                vec![weather.clone()],
+                vec![remote("get_weather", source, &["hand-made"])],
+            );
+            assert_eq!(plan.changes[0].action, SyncAction::Conflict);
+            assert!(plan.is_empty());
+            assert!(plan
+                .to_string()
+                .ends_with("0 to create, 0 to update, 0 to delete, 0 unchanged, 1 in conflict\n"));
+        }
+
+        // Editing only a schema file is a change too.
+        let shout = sync.discover().unwrap().remove(1);
+        let mut synced = remote("shout_loud", &shout.request.source_code, &["git"]);
+        synced.json_schema = shout.request.json_schema.clone();
+        synced.args_json_schema = shout.request.args_json_schema.clone();
+        let plan = sync.diff(vec![shout.clone()], vec![synced.clone()]);
+        assert_eq!(plan.changes[0].action, SyncAction::Unchanged);
+
+        std::fs::write(
+            dir.join("shout_args_schema.json"),
+            serde_json::json!({"type": "object", "properties": {}, "required": []}).to_string(),
+        )
+        .unwrap();
+        let edited = sync.discover().unwrap().remove(1);
+        assert_ne!(edited.hash, shout.hash);
+        let plan = sync.diff(vec![edited], vec![synced]);
+        assert_eq!(plan.changes[0].action, SyncAction::Update);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
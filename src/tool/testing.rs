@@ -0,0 +1,361 @@
+//! Run a tool on the server before creating it.
+//!
+//! [`ToolTester`] wraps [`ToolApi::run_from_source`](crate::api::ToolApi::run_from_source)
+//! with the tool's schemas, environment variables and pip requirements, so a
+//! tool can be tried out with different arguments. [`ToolTestCase`]s pair
+//! arguments with the expected result for regression testing, and can be
+//! loaded from a JSON file:
+//!
+//! ```json
+//! [
+//!   {"name": "default units", "args": {"location": "Paris"}, "contains": "celsius"},
+//!   {"args": {"a": 5, "b": 3}, "expected": 8},
+//!   {"args": {}, "status": "error"}
+//! ]
+//! ```
+//!
+//! A case passes when the run has the expected `status` (`success` unless
+//! given), returns exactly `expected` (compared as JSON when it is not a
+//! string) and the return value contains `contains`.
+//!
+//! ```no_run
+//! # use letta::tool::{LocalTool, ToolTestCase, ToolTester};
+//! # async fn example(client: &letta::LettaClient) -> letta::LettaResult<()> {
+//! let tool = LocalTool::from_file("examples/weather_tool.py")?;
+//! let tester = ToolTester::from_request(&tool.request).env_var("WEATHER_API_KEY", "test");
+//!
+//! let cases = ToolTestCase::load("examples/weather_tool_cases.json")?;
+//! for result in tester.run_cases(client, &cases).await {
+//!     println!("{}: {}", result.case.label(), if result.passed() { "ok" } else { "FAILED" });
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::LettaClient;
+use crate::error::{LettaError, LettaResult};
+use crate::types::tool::{
+    CreateToolRequest, PipRequirement, RunToolFromSourceRequest, RunToolFromSourceResponse,
+    SourceType, ToolExecutionStatus,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Runs a tool's source code on the server with different arguments.
+#[derive(Debug, Clone)]
+pub struct ToolTester {
+    request: RunToolFromSourceRequest,
+}
+
+impl ToolTester {
+    /// Test the given Python source code.
+    ///
+    /// Without a schema the server derives one from the docstring.
+    pub fn new(source_code: impl Into<String>) -> Self {
+        Self {
+            request: RunToolFromSourceRequest {
+                source_code: source_code.into(),
+                args: Value::Object(Default::default()),
+                source_type: Some(SourceType::Python),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Test the tool a [`CreateToolRequest`] would create, with its schemas
+    /// and pip requirements.
+    pub fn from_request(request: &CreateToolRequest) -> Self {
+        let mut tester = Self::new(request.source_code.clone());
+        tester.request.name = request
+            .json_schema
+            .as_ref()
+            .and_then(|schema| schema.get("name"))
+            .and_then(|name| name.as_str())
+            .map(String::from);
+        tester.request.source_type = request.source_type.or(Some(SourceType::Python));
+        tester.request.json_schema = request.json_schema.clone();
+        tester.request.args_json_schema = request.args_json_schema.clone();
+        tester.request.pip_requirements = request.pip_requirements.clone();
+        tester
+    }
+
+    /// Set an environment variable for the tool.
+    pub fn env_var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.request
+            .env_vars
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Install a pip package before running the tool.
+    pub fn pip_requirement(mut self, package: impl Into<String>, version: Option<String>) -> Self {
+        self.request
+            .pip_requirements
+            .get_or_insert_with(Vec::new)
+            .push(PipRequirement {
+                package: package.into(),
+                version,
+            });
+        self
+    }
+
+    /// Run the tool once.
+    ///
+    /// A tool that raises is not an error: the response has
+    /// [`ToolExecutionStatus::Error`] and the traceback in `stderr`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the request fails or if the response cannot be parsed.
+    pub async fn run(
+        &self,
+        client: &LettaClient,
+        args: Value,
+    ) -> LettaResult<RunToolFromSourceResponse> {
+        let request = RunToolFromSourceRequest {
+            args,
+            ..self.request.clone()
+        };
+        client.tools().run_from_source(request).await
+    }
+
+    /// Run each case in order and check its result.
+    ///
+    /// A case whose request fails is reported as a failure rather than
+    /// stopping the run.
+    pub async fn run_cases(
+        &self,
+        client: &LettaClient,
+        cases: &[ToolTestCase],
+    ) -> Vec<ToolTestResult> {
+        let mut results = Vec::with_capacity(cases.len());
+        for case in cases {
+            let result = match self.run(client, case.args.clone()).await {
+                Ok(response) => ToolTestResult {
+                    failures: case.check(&response),
+                    case: case.clone(),
+                    response: Some(response),
+                },
+                Err(e) => ToolTestResult {
+                    case: case.clone(),
+                    response: None,
+                    failures: vec![format!("request failed: {}", e)],
+                },
+            };
+            results.push(result);
+        }
+        results
+    }
+}
+
+/// Arguments for a tool run and the result they should produce.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolTestCase {
+    /// Name shown in reports; defaults to the arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Arguments to call the tool with.
+    #[serde(default = "empty_args")]
+    pub args: Value,
+    /// Exact expected return value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// Text the return value must contain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<String>,
+    /// Expected status; `success` when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ToolExecutionStatus>,
+}
+
+fn empty_args() -> Value {
+    Value::Object(Default::default())
+}
+
+impl Default for ToolTestCase {
+    fn default() -> Self {
+        Self::new(empty_args())
+    }
+}
+
+impl ToolTestCase {
+    /// A case that only checks the tool runs successfully.
+    pub fn new(args: Value) -> Self {
+        Self {
+            name: None,
+            args,
+            expected: None,
+            contains: None,
+            status: None,
+        }
+    }
+
+    /// Name the case.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Expect exactly this return value.
+    pub fn expected(mut self, expected: impl Into<Value>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Expect the return value to contain this text.
+    pub fn contains(mut self, text: impl Into<String>) -> Self {
+        self.contains = Some(text.into());
+        self
+    }
+
+    /// Expect the tool to fail.
+    pub fn expect_error(mut self) -> Self {
+        self.status = Some(ToolExecutionStatus::Error);
+        self
+    }
+
+    /// Read a JSON array of cases from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the file cannot be read or is
+    /// not a JSON array of cases.
+    pub fn load(path: impl AsRef<Path>) -> LettaResult<Vec<Self>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            LettaError::validation(format!("Invalid test cases in {}: {}", path.display(), e))
+        })
+    }
+
+    /// The case's name, or its arguments if it has none.
+    pub fn label(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.args.to_string())
+    }
+
+    /// Check a response against the case, returning what did not match.
+    pub fn check(&self, response: &RunToolFromSourceResponse) -> Vec<String> {
+        let mut failures = Vec::new();
+
+        let status = self.status.unwrap_or(ToolExecutionStatus::Success);
+        if response.status != status {
+            failures.push(format!(
+                "expected status {:?}, got {:?}",
+                status, response.status
+            ));
+        }
+
+        if let Some(expected) = &self.expected {
+            if !returns_value(&response.tool_return, expected) {
+                failures.push(format!(
+                    "expected {}, got {:?}",
+                    expected, response.tool_return
+                ));
+            }
+        }
+
+        if let Some(text) = &self.contains {
+            if !response.tool_return.contains(text.as_str()) {
+                failures.push(format!(
+                    "expected return to contain {:?}, got {:?}",
+                    text, response.tool_return
+                ));
+            }
+        }
+
+        failures
+    }
+}
+
+/// Whether a tool's return string matches an expected value.
+///
+/// Strings must match exactly. Other values are compared with the return
+/// parsed as JSON, with numbers compared by value so `8` matches `"8.0"`.
+fn returns_value(tool_return: &str, expected: &Value) -> bool {
+    match expected {
+        Value::String(s) => tool_return == s,
+        _ => match serde_json::from_str::<Value>(tool_return.trim()) {
+            Ok(Value::Number(actual)) => match expected {
+                Value::Number(expected) => actual.as_f64() == expected.as_f64(),
+                _ => false,
+            },
+            Ok(actual) => &actual == expected,
+            Err(_) => false,
+        },
+    }
+}
+
+/// The outcome of one [`ToolTestCase`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolTestResult {
+    /// The case that was run.
+    pub case: ToolTestCase,
+    /// The server's response, unless the request failed.
+    pub response: Option<RunToolFromSourceResponse>,
+    /// Why the case failed; empty if it passed.
+    pub failures: Vec<String>,
+}
+
+impl ToolTestResult {
+    /// Whether the case passed.
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: &str, tool_return: &str) -> RunToolFromSourceResponse {
+        serde_json::from_value(serde_json::json!({
+            "id": "null",
+            "date": "2025-01-01T00:00:00Z",
+            "tool_return": tool_return,
+            "status": status,
+            "tool_call_id": "null",
+            "name": null,
+            "message_type": "tool_return_message",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_load_cases() {
+        let cases: Vec<ToolTestCase> = serde_json::from_str(
+            r#"[
+                {"name": "sum", "args": {"a": 5, "b": 3}, "expected": 8},
+                {"status": "error"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cases[0],
+            ToolTestCase::new(serde_json::json!({"a": 5, "b": 3}))
+                .name("sum")
+                .expected(8)
+        );
+        assert_eq!(cases[1].args, serde_json::json!({}));
+        assert_eq!(cases[1].label(), "{}");
+    }
+
+    #[test]
+    fn test_check() {
+        let sum = ToolTestCase::default().expected(8);
+        assert!(sum.check(&response("success", "8.0")).is_empty());
+        assert_eq!(sum.check(&response("success", "9")).len(), 1);
+        assert_eq!(sum.check(&response("error", "oops")).len(), 2);
+
+        let list = ToolTestCase::default().expected(serde_json::json!([1, 2]));
+        assert!(list.check(&response("success", "[1, 2]")).is_empty());
+
+        let text = ToolTestCase::default().expected("8").contains("8");
+        assert!(text.check(&response("success", "8")).is_empty());
+        assert_eq!(text.check(&response("success", "8.0")).len(), 1);
+
+        let failing = ToolTestCase::default().expect_error();
+        assert!(failing.check(&response("error", "")).is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! Tools whose signature is defined by a Rust type.
+//!
+//! [`LettaTool`] ties an argument struct to a [`ToolDefinition`], so the
+//! struct is the single source of truth for the tool's parameters. With the
+//! `derive` feature enabled it can be derived from the struct's fields and doc
+//! comments:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use letta::tool::LettaTool;
+//!
+//! /// Roll a number of dice.
+//! #[derive(LettaTool)]
+//! #[letta_tool(returns = "The rolled values, comma separated")]
+//! struct RollDice {
+//!     /// Number of sides per die.
+//!     sides: u32,
+//!     /// How many dice to roll.
+//!     #[letta_tool(default = 1)]
+//!     count: Option<u32>,
+//! }
+//!
+//! let request = RollDice::create_request(
+//!     "import random\nreturn ', '.join(str(random.randint(1, sides)) for _ in range(count))",
+//! )?;
+//! assert_eq!(request.json_schema.unwrap()["name"], "roll_dice");
+//! # }
+//! # Ok::<(), letta::LettaError>(())
+//! ```
+//!
+//! The struct's doc comment becomes the tool description and each field's doc
+//! comment becomes its parameter description; fields without docs are a
+//! compile error, since Letta rejects undocumented parameters. The tool name
+//! defaults to the struct name in `snake_case` and can be overridden with
+//! `#[letta_tool(name = "...")]`; fields can be renamed with
+//! `#[letta_tool(rename = "...")]`.
+
+use super::authoring::{ParamType, ToolDefinition};
+use crate::error::LettaResult;
+use crate::types::tool::CreateToolRequest;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// A Rust type that can be used as a tool parameter.
+///
+/// Implemented for strings, numbers, booleans, sequences, maps and
+/// [`Option`]. Implement it for your own types (e.g. a unit-only enum mapped
+/// to [`ParamType::Enum`]) to use them in a [`LettaTool`] struct.
+pub trait ToolParamType {
+    /// Whether the parameter may be omitted by the model.
+    const OPTIONAL: bool = false;
+
+    /// The tool parameter type for this Rust type.
+    fn param_type() -> ParamType;
+}
+
+macro_rules! impl_tool_param_type {
+    ($param_type:expr => $($ty:ty),+ $(,)?) => {
+        $(
+            impl ToolParamType for $ty {
+                fn param_type() -> ParamType {
+                    $param_type
+                }
+            }
+        )+
+    };
+}
+
+impl_tool_param_type!(ParamType::String => String, str, char);
+impl_tool_param_type!(
+    ParamType::Integer => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+impl_tool_param_type!(ParamType::Number => f32, f64);
+impl_tool_param_type!(ParamType::Boolean => bool);
+impl_tool_param_type!(ParamType::Object => Value, serde_json::Map<String, Value>);
+
+impl<T: ToolParamType + ?Sized> ToolParamType for &T {
+    const OPTIONAL: bool = T::OPTIONAL;
+
+    fn param_type() -> ParamType {
+        T::param_type()
+    }
+}
+
+impl<T: ToolParamType> ToolParamType for Option<T> {
+    const OPTIONAL: bool = true;
+
+    fn param_type() -> ParamType {
+        T::param_type()
+    }
+}
+
+macro_rules! impl_tool_param_type_seq {
+    ($($seq:ident),+) => {
+        $(
+            impl<T: ToolParamType> ToolParamType for $seq<T> {
+                fn param_type() -> ParamType {
+                    ParamType::array(T::param_type())
+                }
+            }
+        )+
+    };
+}
+
+impl_tool_param_type_seq!(Vec, VecDeque, HashSet, BTreeSet);
+
+impl<T: ToolParamType> ToolParamType for [T] {
+    fn param_type() -> ParamType {
+        ParamType::array(T::param_type())
+    }
+}
+
+impl<T: ToolParamType, const N: usize> ToolParamType for [T; N] {
+    fn param_type() -> ParamType {
+        ParamType::array(T::param_type())
+    }
+}
+
+impl<K, V, S> ToolParamType for HashMap<K, V, S> {
+    fn param_type() -> ParamType {
+        ParamType::Object
+    }
+}
+
+impl<K, V> ToolParamType for BTreeMap<K, V> {
+    fn param_type() -> ParamType {
+        ParamType::Object
+    }
+}
+
+/// An argument type that defines a tool's signature.
+///
+/// Only [`tool_definition`](Self::tool_definition) must be provided; it
+/// describes the tool without a body. The remaining methods derive the
+/// schemas and a [`CreateToolRequest`] from it.
+pub trait LettaTool {
+    /// The tool's name, description, parameters and return documentation.
+    fn tool_definition() -> ToolDefinition;
+
+    /// The tool's name.
+    fn tool_name() -> String {
+        Self::tool_definition().name
+    }
+
+    /// The arguments schema (`args_json_schema`).
+    fn args_json_schema() -> Value {
+        Self::tool_definition().args_json_schema()
+    }
+
+    /// The OpenAI-style function schema (`json_schema`).
+    fn json_schema() -> Value {
+        Self::tool_definition().json_schema()
+    }
+
+    /// Build a [`CreateToolRequest`] with the given Python function body.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the definition fails validation.
+    fn create_request(body: &str) -> LettaResult<CreateToolRequest> {
+        Self::tool_definition().body(body).to_create_request()
+    }
+
+    /// Build a [`CreateToolRequest`] whose body raises `NotImplementedError`.
+    ///
+    /// Useful for registering the signature of a tool that is executed
+    /// client-side, or as a starting point to fill in.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the definition fails validation.
+    fn create_request_skeleton() -> LettaResult<CreateToolRequest> {
+        Self::create_request(&format!(
+            "raise NotImplementedError(\"{} is not implemented\")",
+            Self::tool_name()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::ToolParam;
+
+    struct Search;
+
+    impl LettaTool for Search {
+        fn tool_definition() -> ToolDefinition {
+            ToolDefinition::new("search", "Search documents.")
+                .param(ToolParam::new(
+                    "query",
+                    <String as ToolParamType>::param_type(),
+                    "Search text",
+                ))
+                .param(ToolParam::optional(
+                    "tags",
+                    <Option<Vec<String>> as ToolParamType>::param_type(),
+                    "Tags to filter by",
+                ))
+        }
+    }
+
+    #[test]
+    fn test_param_types() {
+        assert_eq!(<u8 as ToolParamType>::param_type(), ParamType::Integer);
+        assert_eq!(<&str as ToolParamType>::param_type(), ParamType::String);
+        assert_eq!(
+            <[f64; 3] as ToolParamType>::param_type(),
+            ParamType::array(ParamType::Number)
+        );
+        assert_eq!(
+            <HashMap<String, i32> as ToolParamType>::param_type(),
+            ParamType::Object
+        );
+        const { assert!(<Option<bool> as ToolParamType>::OPTIONAL) };
+        const { assert!(!<bool as ToolParamType>::OPTIONAL) };
+    }
+
+    #[test]
+    fn test_letta_tool_defaults() {
+        assert_eq!(Search::tool_name(), "search");
+        assert_eq!(
+            Search::args_json_schema()["required"],
+            serde_json::json!(["query"])
+        );
+        assert_eq!(
+            Search::json_schema()["parameters"]["properties"]["tags"]["type"],
+            "array"
+        );
+
+        let request = Search::create_request_skeleton().unwrap();
+        assert!(request
+            .source_code
+            .contains("raise NotImplementedError(\"search is not implemented\")"));
+    }
+}
@@ -0,0 +1,1430 @@
+//! Check a tool's source code and schemas before sending them to the server.
+//!
+//! Letta derives and checks tool schemas from the Python function's signature
+//! and Google-style docstring, so a tool is only accepted when the source,
+//! `json_schema` and `args_json_schema` agree. [`validate`] performs the same
+//! checks locally and reports every problem it finds as a [`ToolDiagnostic`]
+//! instead of stopping at the first one.
+//!
+//! Diagnostics about the source code carry a byte span into
+//! [`CreateToolRequest::source_code`]; diagnostics about a schema carry a JSON
+//! pointer into it. [`ToolDiagnostic`] implements [`miette::Diagnostic`], so
+//! it can be rendered with the source attached:
+//!
+//! ```
+//! use letta::tool::validate;
+//! use letta::types::CreateToolRequest;
+//!
+//! let request = CreateToolRequest {
+//!     source_code: "def shout(text: str) -> str:\n    \"\"\"Shout.\"\"\"\n    return text.upper()\n"
+//!         .to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let diagnostics = validate(&request);
+//! assert!(diagnostics.iter().any(|d| d.is_error()));
+//!
+//! for diagnostic in diagnostics {
+//!     let report = miette::Report::new(diagnostic).with_source_code(request.source_code.clone());
+//!     eprintln!("{report:?}");
+//! }
+//! ```
+
+use super::authoring::PYTHON_KEYWORDS;
+use crate::types::tool::{CreateToolRequest, SourceType};
+use miette::{LabeledSpan, Severity};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::ops::Range;
+
+/// Parameters Letta injects itself; they never appear in the schemas.
+const INJECTED_PARAMS: &[&str] = &["self", "agent_state"];
+
+/// JSON Schema type names.
+const JSON_TYPES: &[&str] = &[
+    "string", "number", "integer", "boolean", "array", "object", "null",
+];
+
+/// Where in a [`CreateToolRequest`] a diagnostic applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticLocation {
+    /// Byte range in `source_code`.
+    SourceCode(Range<usize>),
+    /// JSON pointer into `json_schema` (empty for the whole schema).
+    JsonSchema(String),
+    /// JSON pointer into `args_json_schema` (empty for the whole schema).
+    ArgsJsonSchema(String),
+}
+
+impl fmt::Display for DiagnosticLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceCode(span) => write!(f, "source_code[{}..{}]", span.start, span.end),
+            Self::JsonSchema(pointer) => write!(f, "json_schema#{}", pointer),
+            Self::ArgsJsonSchema(pointer) => write!(f, "args_json_schema#{}", pointer),
+        }
+    }
+}
+
+/// A problem found by [`validate`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct ToolDiagnostic {
+    /// [`Severity::Error`] if the server will reject the tool,
+    /// [`Severity::Warning`] if it will likely misbehave, and
+    /// [`Severity::Advice`] for style suggestions.
+    pub severity: Severity,
+    /// Stable identifier, e.g. `letta::tool::missing_docstring`.
+    pub code: &'static str,
+    /// Human-readable description of the problem.
+    pub message: String,
+    /// Where the problem is.
+    pub location: DiagnosticLocation,
+    /// How to fix it, if there is more to say than the message.
+    pub help: Option<String>,
+}
+
+impl ToolDiagnostic {
+    fn new(
+        severity: Severity,
+        code: &'static str,
+        message: impl Into<String>,
+        location: DiagnosticLocation,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            location,
+            help: None,
+        }
+    }
+
+    fn error(code: &'static str, message: impl Into<String>, location: DiagnosticLocation) -> Self {
+        Self::new(Severity::Error, code, message, location)
+    }
+
+    fn warning(
+        code: &'static str,
+        message: impl Into<String>,
+        location: DiagnosticLocation,
+    ) -> Self {
+        Self::new(Severity::Warning, code, message, location)
+    }
+
+    fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Whether the server will reject the tool because of this problem.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// The byte range in `source_code`, if the diagnostic is about the source.
+    pub fn span(&self) -> Option<Range<usize>> {
+        match &self.location {
+            DiagnosticLocation::SourceCode(span) => Some(span.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl miette::Diagnostic for ToolDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.code))
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        match &self.location {
+            DiagnosticLocation::SourceCode(_) => self
+                .help
+                .as_ref()
+                .map(|help| Box::new(help) as Box<dyn fmt::Display + 'a>),
+            location => Some(Box::new(match &self.help {
+                Some(help) => format!("at {}\n{}", location, help),
+                None => format!("at {}", location),
+            })),
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span()?;
+        Some(Box::new(std::iter::once(LabeledSpan::underline(span))))
+    }
+}
+
+/// Check a tool's Python source and schemas against each other.
+///
+/// The source is checked for a function with type-annotated parameters and a
+/// Google-style docstring documenting each of them. `json_schema` and
+/// `args_json_schema` are checked for well-formedness, and then against the
+/// function signature: names, parameters, types and `required` must agree.
+/// Source checks are skipped for non-Python tools.
+///
+/// Returns the diagnostics in the order they were found; an empty list means
+/// the tool looks valid. Use [`ToolDiagnostic::is_error`] to tell problems the
+/// server will reject from warnings.
+pub fn validate(request: &CreateToolRequest) -> Vec<ToolDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if let Some(schema) = &request.json_schema {
+        check_function_schema(schema, &mut diagnostics);
+    }
+    if let Some(args) = &request.args_json_schema {
+        let parameters = request
+            .json_schema
+            .as_ref()
+            .and_then(|schema| schema.get("parameters"));
+        // An args schema copied from `json_schema` has already been checked.
+        if parameters != Some(args) {
+            check_parameters_schema(args, SchemaRef::args(), &mut diagnostics);
+            if let Some(parameters) = parameters {
+                compare_parameter_schemas(parameters, args, &mut diagnostics);
+            }
+        }
+    }
+
+    if !matches!(request.source_type, None | Some(SourceType::Python)) {
+        return diagnostics;
+    }
+
+    let schema_name = request
+        .json_schema
+        .as_ref()
+        .and_then(|schema| schema.get("name"))
+        .and_then(Value::as_str);
+    let function = match parse_function(&request.source_code, schema_name) {
+        Ok(function) => function,
+        Err(diagnostic) => {
+            diagnostics.push(diagnostic);
+            return diagnostics;
+        }
+    };
+    check_function(&function, &mut diagnostics);
+
+    if let Some(name) = schema_name.filter(|name| *name != function.name) {
+        diagnostics.push(ToolDiagnostic::error(
+            "letta::tool::name_mismatch",
+            format!(
+                "Function is named '{}' but json_schema names the tool '{}'",
+                function.name, name
+            ),
+            DiagnosticLocation::SourceCode(function.name_span.clone()),
+        ));
+    }
+
+    let schema_parameters = request
+        .json_schema
+        .as_ref()
+        .and_then(|schema| schema.get("parameters"));
+    let parameters = match (&request.args_json_schema, schema_parameters) {
+        (Some(args), Some(parameters)) if parameters != args => Some((args, SchemaRef::args())),
+        (_, Some(parameters)) => Some((parameters, SchemaRef::json("/parameters"))),
+        (Some(args), None) => Some((args, SchemaRef::args())),
+        (None, None) => None,
+    };
+    if let Some((parameters, at)) = parameters {
+        cross_check(&function, parameters, at, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+/// Which schema, and where in it, a JSON value lives.
+#[derive(Clone, Copy)]
+struct SchemaRef<'a> {
+    args: bool,
+    pointer: &'a str,
+}
+
+impl<'a> SchemaRef<'a> {
+    fn json(pointer: &'a str) -> Self {
+        Self {
+            args: false,
+            pointer,
+        }
+    }
+
+    fn args() -> Self {
+        Self {
+            args: true,
+            pointer: "",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        if self.args {
+            "args_json_schema"
+        } else {
+            "json_schema"
+        }
+    }
+
+    fn at(self, path: &[&str]) -> DiagnosticLocation {
+        let mut pointer = self.pointer.to_string();
+        for segment in path {
+            pointer.push('/');
+            pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        if self.args {
+            DiagnosticLocation::ArgsJsonSchema(pointer)
+        } else {
+            DiagnosticLocation::JsonSchema(pointer)
+        }
+    }
+}
+
+fn check_function_schema(schema: &Value, out: &mut Vec<ToolDiagnostic>) {
+    let at = SchemaRef::json("");
+    let Some(obj) = schema.as_object() else {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            "json_schema must be a JSON object",
+            at.at(&[]),
+        ));
+        return;
+    };
+
+    match obj.get("name") {
+        None => out.push(
+            ToolDiagnostic::error(
+                "letta::tool::schema",
+                "json_schema is missing the tool 'name'",
+                at.at(&[]),
+            )
+            .with_help(r#"Add the function name: {"name": "my_tool", ...}"#),
+        ),
+        Some(Value::String(name)) => {
+            if let Some(problem) = identifier_problem(name) {
+                out.push(ToolDiagnostic::error(
+                    "letta::tool::schema",
+                    format!("json_schema name {}", problem),
+                    at.at(&["name"]),
+                ));
+            }
+        }
+        Some(_) => out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            "json_schema 'name' must be a string",
+            at.at(&["name"]),
+        )),
+    }
+
+    match obj.get("description") {
+        None => out.push(ToolDiagnostic::warning(
+            "letta::tool::schema",
+            "json_schema has no 'description'; the model will not know what the tool does",
+            at.at(&[]),
+        )),
+        Some(Value::String(_)) => {}
+        Some(_) => out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            "json_schema 'description' must be a string",
+            at.at(&["description"]),
+        )),
+    }
+
+    match obj.get("parameters") {
+        Some(parameters) => {
+            check_parameters_schema(parameters, SchemaRef::json("/parameters"), out)
+        }
+        None => out.push(
+            ToolDiagnostic::error(
+                "letta::tool::schema",
+                "json_schema is missing 'parameters'",
+                at.at(&[]),
+            )
+            .with_help(
+                r#"Add "parameters": {"type": "object", "properties": {...}, "required": [...]}"#,
+            ),
+        ),
+    }
+}
+
+fn check_parameters_schema(schema: &Value, at: SchemaRef<'_>, out: &mut Vec<ToolDiagnostic>) {
+    let name = at.name();
+    let Some(obj) = schema.as_object() else {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!("Parameters in {} must be a JSON object", name),
+            at.at(&[]),
+        ));
+        return;
+    };
+
+    match obj.get("type") {
+        Some(Value::String(ty)) if ty == "object" => {}
+        Some(ty) => out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!(
+                "Parameters 'type' in {} must be \"object\", got {}",
+                name, ty
+            ),
+            at.at(&["type"]),
+        )),
+        None => out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!("Parameters in {} are missing \"type\": \"object\"", name),
+            at.at(&[]),
+        )),
+    }
+
+    let properties = match obj.get("properties") {
+        Some(Value::Object(properties)) => properties,
+        Some(_) => {
+            out.push(ToolDiagnostic::error(
+                "letta::tool::schema",
+                format!("Parameters 'properties' in {} must be a JSON object", name),
+                at.at(&["properties"]),
+            ));
+            return;
+        }
+        None => {
+            out.push(
+                ToolDiagnostic::error(
+                    "letta::tool::schema",
+                    format!("Parameters in {} are missing 'properties'", name),
+                    at.at(&[]),
+                )
+                .with_help(r#"Use "properties": {} for a tool without parameters"#),
+            );
+            return;
+        }
+    };
+
+    for (property, value) in properties {
+        check_property_schema(property, value, at, &["properties", property], true, out);
+    }
+
+    match obj.get("required") {
+        None => {}
+        Some(Value::Array(required)) => {
+            for (i, entry) in required.iter().enumerate() {
+                let index = i.to_string();
+                match entry.as_str() {
+                    Some(property) if !properties.contains_key(property) => {
+                        out.push(ToolDiagnostic::error(
+                            "letta::tool::schema",
+                            format!(
+                                "'{}' is listed as required in {} but is not a property",
+                                property, name
+                            ),
+                            at.at(&["required", &index]),
+                        ))
+                    }
+                    Some(_) => {}
+                    None => out.push(ToolDiagnostic::error(
+                        "letta::tool::schema",
+                        format!("Entries of 'required' in {} must be strings", name),
+                        at.at(&["required", &index]),
+                    )),
+                }
+            }
+        }
+        Some(_) => out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!("'required' in {} must be an array of property names", name),
+            at.at(&["required"]),
+        )),
+    }
+}
+
+fn check_property_schema(
+    property: &str,
+    value: &Value,
+    at: SchemaRef<'_>,
+    path: &[&str],
+    top_level: bool,
+    out: &mut Vec<ToolDiagnostic>,
+) {
+    let name = at.name();
+    let Some(obj) = value.as_object() else {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!("Property '{}' in {} must be a JSON object", property, name),
+            at.at(path),
+        ));
+        return;
+    };
+
+    let composite = ["anyOf", "oneOf", "allOf", "$ref", "enum", "const"]
+        .iter()
+        .any(|key| obj.contains_key(*key));
+    let types: Vec<&str> = match obj.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        Some(_) => {
+            out.push(ToolDiagnostic::error(
+                "letta::tool::schema",
+                format!(
+                    "'type' of property '{}' in {} must be a string",
+                    property, name
+                ),
+                at.at(&[path, &["type"]].concat()),
+            ));
+            Vec::new()
+        }
+        None if composite => Vec::new(),
+        None => {
+            out.push(ToolDiagnostic::error(
+                "letta::tool::schema",
+                format!("Property '{}' in {} is missing 'type'", property, name),
+                at.at(path),
+            ));
+            Vec::new()
+        }
+    };
+    for ty in &types {
+        if !JSON_TYPES.contains(ty) {
+            out.push(
+                ToolDiagnostic::error(
+                    "letta::tool::schema",
+                    format!(
+                        "Property '{}' in {} has unknown type '{}'",
+                        property, name, ty
+                    ),
+                    at.at(&[path, &["type"]].concat()),
+                )
+                .with_help(format!("Use one of: {}", JSON_TYPES.join(", "))),
+            );
+        }
+    }
+
+    if top_level && !obj.contains_key("description") {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!(
+                "Property '{}' in {} is missing 'description'",
+                property, name
+            ),
+            at.at(path),
+        ));
+    }
+
+    if obj.get("enum").is_some_and(|values| !values.is_array()) {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema",
+            format!(
+                "'enum' of property '{}' in {} must be an array",
+                property, name
+            ),
+            at.at(&[path, &["enum"]].concat()),
+        ));
+    }
+
+    if types.contains(&"array") {
+        match obj.get("items") {
+            Some(items) => check_property_schema(
+                property,
+                items,
+                at,
+                &[path, &["items"]].concat(),
+                false,
+                out,
+            ),
+            None => out.push(ToolDiagnostic::warning(
+                "letta::tool::schema",
+                format!(
+                    "Array property '{}' in {} does not describe its 'items'",
+                    property, name
+                ),
+                at.at(path),
+            )),
+        }
+    }
+}
+
+/// Check that `json_schema.parameters` and `args_json_schema` describe the same arguments.
+fn compare_parameter_schemas(parameters: &Value, args: &Value, out: &mut Vec<ToolDiagnostic>) {
+    let (Some(expected), Some(actual)) = (properties(parameters), properties(args)) else {
+        return;
+    };
+
+    for property in expected.keys().filter(|p| !actual.contains_key(*p)) {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema_mismatch",
+            format!(
+                "Property '{}' is in json_schema parameters but not in args_json_schema",
+                property
+            ),
+            SchemaRef::args().at(&["properties"]),
+        ));
+    }
+    for property in actual.keys().filter(|p| !expected.contains_key(*p)) {
+        out.push(ToolDiagnostic::error(
+            "letta::tool::schema_mismatch",
+            format!(
+                "Property '{}' is in args_json_schema but not in json_schema parameters",
+                property
+            ),
+            SchemaRef::args().at(&["properties", property]),
+        ));
+    }
+
+    let mut expected_required = required(parameters);
+    let mut actual_required = required(args);
+    expected_required.sort_unstable();
+    actual_required.sort_unstable();
+    if expected_required != actual_required {
+        out.push(ToolDiagnostic::warning(
+            "letta::tool::schema_mismatch",
+            "'required' differs between json_schema parameters and args_json_schema",
+            SchemaRef::args().at(&["required"]),
+        ));
+    }
+}
+
+fn properties(schema: &Value) -> Option<&Map<String, Value>> {
+    schema.get("properties")?.as_object()
+}
+
+fn required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn check_function(function: &PyFunction<'_>, out: &mut Vec<ToolDiagnostic>) {
+    let name_span = || DiagnosticLocation::SourceCode(function.name_span.clone());
+
+    for param in function.params() {
+        if param.annotation.is_none() {
+            out.push(
+                ToolDiagnostic::error(
+                    "letta::tool::missing_annotation",
+                    format!(
+                        "Parameter '{}' in function '{}' lacks a type annotation",
+                        param.name, function.name
+                    ),
+                    DiagnosticLocation::SourceCode(param.span.clone()),
+                )
+                .with_help(format!("Annotate it, e.g. `{}: str`", param.name)),
+            );
+        }
+    }
+
+    let Some(docstring) = &function.docstring else {
+        out.push(
+            ToolDiagnostic::error(
+                "letta::tool::missing_docstring",
+                format!("Function '{}' has no docstring", function.name),
+                name_span(),
+            )
+            .with_help(format!(
+                r#"Letta builds the tool schema from a Google-style docstring:
+
+def {}(...):
+    """
+    Brief description.
+
+    Args:
+        param1: Description
+
+    Returns:
+        Description of return value
+    """"#,
+                function.name
+            )),
+        );
+        return;
+    };
+    let docstring_span = || DiagnosticLocation::SourceCode(docstring.span.clone());
+
+    if docstring.summary.is_empty() {
+        out.push(ToolDiagnostic::warning(
+            "letta::tool::docstring",
+            format!(
+                "Docstring of '{}' has no description of what the tool does",
+                function.name
+            ),
+            docstring_span(),
+        ));
+    }
+
+    if function.params().next().is_some() && !docstring.has_args_section {
+        out.push(
+            ToolDiagnostic::error(
+                "letta::tool::docstring",
+                format!("Docstring of '{}' has no 'Args:' section", function.name),
+                docstring_span(),
+            )
+            .with_help(
+                "Document every parameter:\n\n    Args:\n        param_name: Description of parameter",
+            ),
+        );
+    } else {
+        for param in function.params() {
+            let documented = docstring
+                .args
+                .iter()
+                .any(|arg| arg.name == param.name && !arg.description.is_empty());
+            if !documented {
+                out.push(ToolDiagnostic::error(
+                    "letta::tool::undocumented_parameter",
+                    format!(
+                        "Parameter '{}' in function '{}' lacks a description in the docstring",
+                        param.name, function.name
+                    ),
+                    DiagnosticLocation::SourceCode(param.span.clone()),
+                ));
+            }
+        }
+    }
+
+    for arg in &docstring.args {
+        if !function.params().any(|param| param.name == arg.name) {
+            out.push(ToolDiagnostic::warning(
+                "letta::tool::docstring",
+                format!(
+                    "Docstring documents '{}', which is not a parameter of '{}'",
+                    arg.name, function.name
+                ),
+                DiagnosticLocation::SourceCode(arg.span.clone()),
+            ));
+        }
+    }
+
+    if !docstring.has_returns_section {
+        out.push(ToolDiagnostic::new(
+            Severity::Advice,
+            "letta::tool::docstring",
+            format!("Docstring of '{}' has no 'Returns:' section", function.name),
+            docstring_span(),
+        ));
+    }
+}
+
+/// Check the function signature against the arguments schema.
+fn cross_check(
+    function: &PyFunction<'_>,
+    parameters: &Value,
+    at: SchemaRef<'_>,
+    out: &mut Vec<ToolDiagnostic>,
+) {
+    let Some(properties) = properties(parameters) else {
+        return;
+    };
+    let required = required(parameters);
+    let name = at.name();
+
+    for param in function.params() {
+        let location = DiagnosticLocation::SourceCode(param.span.clone());
+        let Some(property) = properties.get(param.name) else {
+            out.push(ToolDiagnostic::error(
+                "letta::tool::schema_mismatch",
+                format!("Parameter '{}' is missing from {}", param.name, name),
+                location,
+            ));
+            continue;
+        };
+
+        if let (Some(expected), Some(annotation)) = (
+            param.annotation.and_then(annotation_json_type),
+            param.annotation,
+        ) {
+            let types: Vec<&str> = match property.get("type") {
+                Some(Value::String(ty)) => vec![ty.as_str()],
+                Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !types.is_empty() && !types.contains(&expected) {
+                out.push(ToolDiagnostic::warning(
+                    "letta::tool::type_mismatch",
+                    format!(
+                        "Parameter '{}' is annotated '{}' but {} gives type '{}'",
+                        param.name,
+                        annotation,
+                        name,
+                        types.join(" | ")
+                    ),
+                    location.clone(),
+                ));
+            }
+        }
+
+        let is_required = required.contains(&param.name);
+        if param.default.is_none() && !is_required {
+            out.push(ToolDiagnostic::warning(
+                "letta::tool::schema_mismatch",
+                format!(
+                    "Parameter '{}' has no default but is not required in {}",
+                    param.name, name
+                ),
+                location,
+            ));
+        } else if param.default.is_some() && is_required {
+            out.push(ToolDiagnostic::warning(
+                "letta::tool::schema_mismatch",
+                format!(
+                    "Parameter '{}' has a default but is required in {}",
+                    param.name, name
+                ),
+                location,
+            ));
+        }
+    }
+
+    for property in properties.keys() {
+        if !function
+            .params()
+            .any(|param| param.name == property.as_str())
+        {
+            out.push(ToolDiagnostic::error(
+                "letta::tool::schema_mismatch",
+                format!(
+                    "Property '{}' in {} is not a parameter of '{}'",
+                    property, name, function.name
+                ),
+                at.at(&["properties", property]),
+            ));
+        }
+    }
+}
+
+/// The JSON Schema type matching a Python annotation, if it is a simple one.
+fn annotation_json_type(annotation: &str) -> Option<&'static str> {
+    let mut annotation = annotation.trim().trim_matches(|c| c == '"' || c == '\'');
+    if let Some(inner) = annotation
+        .strip_prefix("Optional[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        annotation = inner.trim();
+    }
+    let parts: Vec<&str> = annotation
+        .split('|')
+        .map(str::trim)
+        .filter(|part| *part != "None")
+        .collect();
+    let [annotation] = parts.as_slice() else {
+        return None;
+    };
+
+    let base = annotation.split('[').next().unwrap_or(annotation).trim();
+    let base = base.rsplit('.').next().unwrap_or(base);
+    match base {
+        "str" => Some("string"),
+        "int" => Some("integer"),
+        "float" => Some("number"),
+        "bool" => Some("boolean"),
+        "list" | "List" | "tuple" | "Tuple" | "set" | "Set" | "Sequence" => Some("array"),
+        "dict" | "Dict" | "Mapping" => Some("object"),
+        _ => None,
+    }
+}
+
+/// Describe why a name is not a usable Python identifier, if it is not.
+fn identifier_problem(name: &str) -> Option<String> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+
+    if !valid {
+        Some(format!("'{}' is not a valid Python identifier", name))
+    } else if PYTHON_KEYWORDS.contains(&name) {
+        Some(format!("'{}' is a reserved Python keyword", name))
+    } else {
+        None
+    }
+}
+
+/// Name of the tool function defined by `source`, if one can be found.
+pub(crate) fn function_name(source: &str) -> Option<&str> {
+    parse_function(source, None)
+        .ok()
+        .map(|function| function.name)
+}
+
+/// A top-level Python function, as far as the schemas are concerned.
+#[derive(Debug)]
+struct PyFunction<'a> {
+    name: &'a str,
+    name_span: Range<usize>,
+    params: Vec<PyParam<'a>>,
+    docstring: Option<PyDocstring>,
+}
+
+impl PyFunction<'_> {
+    /// Parameters that are part of the tool schema.
+    fn params(&self) -> impl Iterator<Item = &PyParam<'_>> {
+        self.params
+            .iter()
+            .filter(|param| !INJECTED_PARAMS.contains(&param.name))
+    }
+}
+
+#[derive(Debug)]
+struct PyParam<'a> {
+    name: &'a str,
+    span: Range<usize>,
+    annotation: Option<&'a str>,
+    default: Option<&'a str>,
+}
+
+#[derive(Debug, Default)]
+struct PyDocstring {
+    span: Range<usize>,
+    summary: String,
+    has_args_section: bool,
+    args: Vec<PyDocArg>,
+    has_returns_section: bool,
+}
+
+#[derive(Debug)]
+struct PyDocArg {
+    name: String,
+    span: Range<usize>,
+    description: String,
+}
+
+/// Find the tool function: the one named `name` if given, else the last
+/// top-level function (helpers usually come first).
+fn parse_function<'a>(
+    source: &'a str,
+    name: Option<&str>,
+) -> Result<PyFunction<'a>, ToolDiagnostic> {
+    // Editors on Windows often start files with a byte order mark.
+    let mut offset = if source.starts_with('\u{feff}') {
+        '\u{feff}'.len_utf8()
+    } else {
+        0
+    };
+    let mut defs = Vec::new();
+    for line in source[offset..].split_inclusive('\n') {
+        let def = line
+            .strip_prefix("def ")
+            .or_else(|| line.strip_prefix("async def "));
+        if let Some(rest) = def {
+            let trimmed = rest.trim_start();
+            let start = offset + line.len() - trimmed.len();
+            let rest = trimmed;
+            let len = rest
+                .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+                .unwrap_or(rest.len());
+            defs.push(start..start + len);
+        }
+        offset += line.len();
+    }
+
+    let Some(name_span) = defs
+        .iter()
+        .find(|span| Some(&source[(*span).clone()]) == name)
+        .or(defs.last())
+        .cloned()
+    else {
+        return Err(ToolDiagnostic::error(
+            "letta::tool::no_function",
+            "Source code does not define a top-level function",
+            DiagnosticLocation::SourceCode(0..source.chars().next().map_or(0, char::len_utf8)),
+        )
+        .with_help("Tools are a single Python function: `def my_tool(arg: str) -> str: ...`"));
+    };
+
+    let name = &source[name_span.clone()];
+    let signature_error = |message: &str, span: Range<usize>| {
+        ToolDiagnostic::error(
+            "letta::tool::syntax",
+            format!("Could not parse the signature of '{}': {}", name, message),
+            DiagnosticLocation::SourceCode(span),
+        )
+    };
+
+    let after_name = &source[name_span.end..];
+    let open = name_span.end + (after_name.len() - after_name.trim_start().len());
+    if !source[open..].starts_with('(') {
+        return Err(signature_error("expected '('", name_span));
+    }
+    let Some(close) = find_top_level(source, open + 1, source.len(), &[')']) else {
+        return Err(signature_error("unclosed '('", open..open + 1));
+    };
+    let Some(colon) = find_top_level(source, close + 1, source.len(), &[':']) else {
+        return Err(signature_error("expected ':'", close..close + 1));
+    };
+
+    let params = split_top_level(source, open + 1..close, ',')
+        .into_iter()
+        .filter_map(|span| parse_param(source, span))
+        .collect();
+
+    Ok(PyFunction {
+        name,
+        name_span,
+        params,
+        docstring: parse_docstring(source, colon + 1)?,
+    })
+}
+
+/// Parse one parameter; `None` for `*args`, `**kwargs` and bare `*` or `/`.
+fn parse_param(source: &str, span: Range<usize>) -> Option<PyParam<'_>> {
+    let text = &source[span.clone()];
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('*') || trimmed.starts_with('/') || trimmed.trim().is_empty() {
+        return None;
+    }
+    let start = span.start + (text.len() - trimmed.len());
+    let len = trimmed
+        .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+        .unwrap_or(trimmed.len());
+    let name_end = start + len;
+
+    let equals = find_top_level(source, name_end, span.end, &['=']);
+    let annotation_end = equals.unwrap_or(span.end);
+    let annotation = source[name_end..annotation_end]
+        .trim()
+        .strip_prefix(':')
+        .map(str::trim);
+    let default = equals.map(|equals| source[equals + 1..span.end].trim());
+
+    Some(PyParam {
+        name: &source[start..name_end],
+        span: start..name_end,
+        annotation,
+        default,
+    })
+}
+
+/// Parse the docstring, if the function body starts with one.
+fn parse_docstring(source: &str, body_start: usize) -> Result<Option<PyDocstring>, ToolDiagnostic> {
+    let mut pos = body_start;
+    loop {
+        let rest = &source[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with('#') {
+            pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else {
+            break;
+        }
+    }
+
+    let rest = &source[pos..];
+    let prefix = rest
+        .find(|c: char| !matches!(c, 'r' | 'R' | 'u' | 'U'))
+        .unwrap_or(0)
+        .min(1);
+    let quote = match &rest[prefix..] {
+        r if r.starts_with(r#"""""#) => r#"""""#,
+        r if r.starts_with("'''") => "'''",
+        _ => return Ok(None),
+    };
+    let content_start = pos + prefix + quote.len();
+    let Some(len) = source[content_start..].find(quote) else {
+        return Err(ToolDiagnostic::error(
+            "letta::tool::syntax",
+            "Docstring is missing its closing triple quotes",
+            DiagnosticLocation::SourceCode(pos..content_start),
+        ));
+    };
+    let content_end = content_start + len;
+
+    let mut docstring = PyDocstring {
+        span: pos..content_end + quote.len(),
+        ..Default::default()
+    };
+    let mut section = DocSection::Summary;
+    let mut summary = Vec::new();
+    let mut entry_indent = None;
+    let mut offset = content_start;
+    for line in source[content_start..content_end].split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if section == DocSection::Summary {
+                summary.push("");
+            }
+            continue;
+        }
+
+        if let Some(header) = DocSection::parse_header(trimmed) {
+            section = header;
+            entry_indent = None;
+            match header {
+                DocSection::Args => docstring.has_args_section = true,
+                DocSection::Returns => docstring.has_returns_section = true,
+                _ => {}
+            }
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        match section {
+            DocSection::Summary => summary.push(trimmed),
+            DocSection::Args => {
+                let indent_of_entries = *entry_indent.get_or_insert(indent);
+                match docstring.args.last_mut() {
+                    Some(arg) if indent > indent_of_entries => {
+                        if !arg.description.is_empty() {
+                            arg.description.push(' ');
+                        }
+                        arg.description.push_str(trimmed);
+                    }
+                    _ => docstring
+                        .args
+                        .push(parse_doc_arg(trimmed, line_start + indent)),
+                }
+            }
+            DocSection::Returns | DocSection::Other => {}
+        }
+    }
+    docstring.summary = summary.join("\n").trim().to_string();
+
+    Ok(Some(docstring))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocSection {
+    Summary,
+    Args,
+    Returns,
+    Other,
+}
+
+impl DocSection {
+    fn parse_header(line: &str) -> Option<Self> {
+        let title = line.strip_suffix(':')?;
+        let looks_like_header = title.len() <= 20
+            && title.starts_with(|c: char| c.is_ascii_uppercase())
+            && title.chars().all(|c| c.is_ascii_alphabetic() || c == ' ');
+        if !looks_like_header {
+            return None;
+        }
+        Some(match title {
+            "Args" | "Arguments" | "Parameters" | "Params" | "Keyword Args"
+            | "Keyword Arguments" => Self::Args,
+            "Returns" | "Return" | "Yields" | "Yield" => Self::Returns,
+            _ => Self::Other,
+        })
+    }
+}
+
+/// Parse an `Args:` entry: `name (type): description` or `name: description`.
+fn parse_doc_arg(entry: &str, start: usize) -> PyDocArg {
+    let stars = entry.len() - entry.trim_start_matches('*').len();
+    let len = entry[stars..]
+        .find(|c: char| !(c == '_' || c.is_alphanumeric()))
+        .unwrap_or(entry.len() - stars);
+    let name = &entry[stars..stars + len];
+
+    let mut rest = entry[stars + len..].trim_start();
+    if rest.starts_with('(') {
+        rest = rest
+            .find(')')
+            .map_or("", |close| rest[close + 1..].trim_start());
+    }
+    let description = rest.strip_prefix(':').map_or("", str::trim);
+
+    PyDocArg {
+        name: name.to_string(),
+        span: start + stars..start + stars + len,
+        description: description.to_string(),
+    }
+}
+
+/// Byte offset of the first `targets` character in `source[start..end]` that
+/// is outside brackets, string literals and comments.
+fn find_top_level(source: &str, start: usize, end: usize, targets: &[char]) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut pos = start;
+    while pos < end {
+        let c = source[pos..].chars().next()?;
+        match c {
+            '"' | '\'' => {
+                pos = string_end(source, pos);
+                continue;
+            }
+            '#' => {
+                pos += source[pos..].find('\n').unwrap_or(source.len() - pos);
+                continue;
+            }
+            _ if depth == 0 && targets.contains(&c) => return Some(pos),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        pos += c.len_utf8();
+    }
+    None
+}
+
+/// Offset just past the string literal starting at `start`.
+fn string_end(source: &str, start: usize) -> usize {
+    let rest = &source[start..];
+    let quote = rest.chars().next().unwrap_or('"');
+    let triple = quote.to_string().repeat(3);
+    if rest.starts_with(&triple) {
+        return rest[3..]
+            .find(&triple)
+            .map_or(source.len(), |i| start + 3 + i + 3);
+    }
+
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' => return start + i,
+            c if c == quote => return start + i + 1,
+            _ => {}
+        }
+    }
+    source.len()
+}
+
+/// Split `source[range]` at top-level `separator`s.
+fn split_top_level(source: &str, range: Range<usize>, separator: char) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = range.start;
+    while let Some(pos) = find_top_level(source, start, range.end, &[separator]) {
+        parts.push(start..pos);
+        start = pos + 1;
+    }
+    parts.push(start..range.end);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::{ParamType, ToolDefinition, ToolParam};
+    use serde_json::json;
+
+    const SOURCE: &str = r#"import math
+
+def helper(x):
+    return x
+
+def area(shape: str, size: float, precision: "int" = 2, *, agent_state: "AgentState" = None) -> str:
+    """
+    Compute the area of a shape.
+
+    Args:
+        shape (str): Either "square" or
+            "circle".
+        size (float): Side length or radius.
+        colour: Not a parameter.
+
+    Returns:
+        str: The area.
+    """
+    # "Args:" inside a comment is not a docstring
+    return str(round(size ** 2, precision))
+"#;
+
+    fn request(source: &str, schema: Option<Value>) -> CreateToolRequest {
+        CreateToolRequest {
+            source_code: source.to_string(),
+            json_schema: schema,
+            ..Default::default()
+        }
+    }
+
+    fn codes(diagnostics: &[ToolDiagnostic]) -> Vec<(&'static str, Severity)> {
+        diagnostics.iter().map(|d| (d.code, d.severity)).collect()
+    }
+
+    #[test]
+    fn test_parse_function() {
+        let function = parse_function(SOURCE, None).unwrap();
+        assert_eq!(function.name, "area");
+        assert_eq!(&SOURCE[function.name_span.clone()], "area");
+
+        let params: Vec<_> = function
+            .params()
+            .map(|p| (p.name, p.annotation, p.default))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("shape", Some("str"), None),
+                ("size", Some("float"), None),
+                ("precision", Some("\"int\""), Some("2")),
+            ]
+        );
+
+        let docstring = function.docstring.unwrap();
+        assert_eq!(docstring.summary, "Compute the area of a shape.");
+        assert!(docstring.has_args_section && docstring.has_returns_section);
+        assert_eq!(docstring.args.len(), 3);
+        assert_eq!(
+            docstring.args[0].description,
+            "Either \"square\" or \"circle\"."
+        );
+        assert_eq!(&SOURCE[docstring.args[2].span.clone()], "colour");
+
+        let helper = parse_function(SOURCE, Some("helper")).unwrap();
+        assert!(helper.docstring.is_none());
+    }
+
+    #[test]
+    fn test_parse_function_signatures() {
+        let source = "@tool\ndef f(a: int, /, b: str = \"x\", *args, c: bool, **kwargs) -> None:\n    pass\n";
+        let function = parse_function(source, None).unwrap();
+        assert_eq!(function.name, "f");
+        let params: Vec<_> = function
+            .params()
+            .map(|p| (p.name, p.annotation, p.default))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("a", Some("int"), None),
+                ("b", Some("str"), Some("\"x\"")),
+                ("c", Some("bool"), None),
+            ]
+        );
+
+        let source = "def größe(länge: float, *, breite: float) -> float:\n    return länge\n";
+        let function = parse_function(source, None).unwrap();
+        assert_eq!(function.name, "größe");
+        assert_eq!(&source[function.name_span.clone()], "größe");
+        let names: Vec<_> = function.params().map(|p| p.name).collect();
+        assert_eq!(names, vec!["länge", "breite"]);
+    }
+
+    #[test]
+    fn test_parse_function_byte_order_mark() {
+        let source = "\u{feff}def f(a): ...\n";
+        assert_eq!(function_name(source), Some("f"));
+        let function = parse_function(source, None).unwrap();
+        assert_eq!(&source[function.name_span.clone()], "f");
+
+        let source = "\u{feff}x = 1\n";
+        let diagnostics = validate(&request(source, None));
+        assert_eq!(diagnostics[0].code, "letta::tool::no_function");
+        assert_eq!(&source[diagnostics[0].span().unwrap()], "\u{feff}");
+    }
+
+    #[test]
+    fn test_unterminated_docstring() {
+        let source = "def f(a: int):\n    \"\"\"Add one.\n\n    Args:\n        a: A number\n    return a + 1\n";
+        let diagnostics = validate(&request(source, None));
+        assert_eq!(
+            codes(&diagnostics),
+            vec![("letta::tool::syntax", Severity::Error)]
+        );
+        assert!(diagnostics[0].message.contains("closing triple quotes"));
+        assert_eq!(&source[diagnostics[0].span().unwrap()], "\"\"\"");
+    }
+
+    #[test]
+    fn test_validate_source_only() {
+        let diagnostics = validate(&request(SOURCE, None));
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("letta::tool::undocumented_parameter", Severity::Error),
+                ("letta::tool::docstring", Severity::Warning),
+            ]
+        );
+        assert_eq!(
+            &SOURCE[diagnostics[0].span().unwrap()],
+            "precision",
+            "error should point at the parameter"
+        );
+
+        let diagnostics = validate(&request("x = 1\n", None));
+        assert_eq!(diagnostics[0].code, "letta::tool::no_function");
+
+        let diagnostics = validate(&request("def f(a: int):\n    return a\n", None));
+        assert_eq!(diagnostics[0].code, "letta::tool::missing_docstring");
+    }
+
+    #[test]
+    fn test_validate_cross_check() {
+        let schema = json!({
+            "name": "area",
+            "description": "Compute the area of a shape.",
+            "parameters": {
+                "type": "object",
+                "properties": {
+                    "shape": {"type": "string", "description": "Shape"},
+                    "size": {"type": "string", "description": "Size"},
+                    "units": {"type": "string", "description": "Units"}
+                },
+                "required": ["shape", "size", "precision"]
+            }
+        });
+        let diagnostics = validate(&request(SOURCE, Some(schema)));
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+
+        assert!(messages
+            .contains(&"'precision' is listed as required in json_schema but is not a property"));
+        assert!(messages.contains(
+            &"Parameter 'size' is annotated 'float' but json_schema gives type 'string'"
+        ));
+        assert!(messages.contains(&"Parameter 'precision' is missing from json_schema"));
+        assert!(messages.contains(&"Property 'units' in json_schema is not a parameter of 'area'"));
+        let units = diagnostics
+            .iter()
+            .find(|d| d.message.contains("'units'"))
+            .unwrap();
+        assert_eq!(
+            units.location,
+            DiagnosticLocation::JsonSchema("/parameters/properties/units".to_string())
+        );
+    }
+
+    #[test]
+    fn test_validate_schema_shape() {
+        let schema = json!({
+            "name": "class",
+            "parameters": {
+                "type": "array",
+                "properties": {
+                    "tags": {"type": "array", "description": "Tags"},
+                    "mode": {"type": "text"}
+                },
+                "required": "tags"
+            }
+        });
+        let diagnostics = validate(&CreateToolRequest {
+            json_schema: Some(schema),
+            source_type: Some(SourceType::JavaScript),
+            ..Default::default()
+        });
+        let locations: Vec<String> = diagnostics.iter().map(|d| d.location.to_string()).collect();
+        assert_eq!(
+            locations,
+            vec![
+                "json_schema#/name",
+                "json_schema#",
+                "json_schema#/parameters/type",
+                "json_schema#/parameters/properties/mode/type",
+                "json_schema#/parameters/properties/mode",
+                "json_schema#/parameters/properties/tags",
+                "json_schema#/parameters/required",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generated_tool_is_valid() {
+        let request = ToolDefinition::new("search", "Search documents.")
+            .param(ToolParam::new("query", ParamType::String, "Search text"))
+            .param(ToolParam::optional(
+                "tags",
+                ParamType::array(ParamType::String),
+                "Tags to filter by",
+            ))
+            .param(
+                ToolParam::optional("limit", ParamType::Integer, "Maximum results")
+                    .default_value(10),
+            )
+            .returns(ParamType::String, "Matching documents")
+            .body("return query")
+            .to_create_request()
+            .unwrap();
+
+        assert_eq!(validate(&request), Vec::new());
+    }
+}
@@ -586,6 +586,21 @@ impl ToolRule {
         }
     }
 
+    /// The name of the tool this rule applies to.
+    pub fn tool_name(&self) -> &str {
+        match self {
+            Self::ContinueLoop { tool_name, .. }
+            | Self::ExitLoop { tool_name, .. }
+            | Self::Terminal { tool_name, .. }
+            | Self::MaxCountPerStep { tool_name, .. }
+            | Self::Conditional { tool_name, .. }
+            | Self::Child { tool_name, .. }
+            | Self::Parent { tool_name, .. }
+            | Self::RequiredBeforeExit { tool_name, .. }
+            | Self::Init { tool_name, .. } => tool_name,
+        }
+    }
+
     /// Add a prompt template to this rule.
     pub fn with_prompt_template(mut self, template: impl Into<String>) -> Self {
         match &mut self {
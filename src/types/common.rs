@@ -319,6 +319,63 @@ impl<T> IntoIterator for PaginatedResponse<T> {
     }
 }
 
+/// Outcome of an operation applied to many items.
+///
+/// Bulk helpers send one request per item and keep going after a failure, so
+/// one bad item doesn't stop the rest. Each failure is recorded with the item
+/// it belongs to instead of being returned as an error.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkReport<T> {
+    /// Results for the items that succeeded.
+    pub succeeded: Vec<T>,
+    /// Items that failed.
+    pub failed: Vec<BulkFailure>,
+}
+
+impl<T> Default for BulkReport<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> BulkReport<T> {
+    pub(crate) fn record<E: fmt::Display>(
+        &mut self,
+        item: impl Into<String>,
+        result: Result<T, E>,
+    ) {
+        match result {
+            Ok(value) => self.succeeded.push(value),
+            Err(e) => self.failed.push(BulkFailure {
+                item: item.into(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    /// Whether every item succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// Number of items attempted.
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+}
+
+/// An item a bulk operation failed on.
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkFailure {
+    /// The item, such as a tool name or agent ID.
+    pub item: String,
+    /// Why it failed.
+    pub error: String,
+}
+
 /// Standard error response from the API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiError {
@@ -472,6 +529,21 @@ mod tests {
         assert!(!response.has_more);
     }
 
+    #[test]
+    fn test_bulk_report() {
+        let mut report = BulkReport::default();
+        report.record("a", Ok::<_, String>(1));
+        report.record("b", Err("boom".to_string()));
+        report.record("c", Ok::<_, String>(3));
+
+        assert_eq!(report.succeeded, vec![1, 3]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].item, "b");
+        assert_eq!(report.failed[0].error, "boom");
+        assert_eq!(report.total(), 3);
+        assert!(!report.is_complete());
+    }
+
     #[test]
     fn test_metadata() {
         let mut metadata = Metadata::new();
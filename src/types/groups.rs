@@ -1,7 +1,10 @@
 //! Group and multi-agent management types for the Letta API.
 
 use crate::types::common::{LettaId, Timestamp};
+use crate::types::message::ToolCallMessage;
+use crate::types::runs::Run;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// Manager type for multi-agent groups.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -73,6 +76,98 @@ pub struct Group {
     pub updated_at: Option<Timestamp>,
 }
 
+impl Group {
+    /// Whether the group pairs a primary agent with sleep-time agents.
+    pub fn is_sleeptime(&self) -> bool {
+        matches!(
+            self.manager_type,
+            ManagerType::Sleeptime | ManagerType::VoiceSleeptime
+        )
+    }
+
+    /// IDs of the group's agents other than its manager agent.
+    ///
+    /// For a sleep-time group these are the sleep-time agents.
+    pub fn worker_ids(&self) -> impl Iterator<Item = &LettaId> {
+        self.agent_ids
+            .iter()
+            .filter(move |id| self.manager_agent_id.as_ref() != Some(*id))
+    }
+}
+
+/// Argument names that memory tools use for the label of the block they edit.
+const BLOCK_LABEL_ARGUMENTS: [&str; 3] = ["label", "target_block_label", "block_label"];
+
+/// Whether `name` is one of Letta's memory editing tools, such as
+/// `memory_replace`, `core_memory_append` or `rethink_memory`.
+fn is_memory_tool(name: &str) -> bool {
+    name.starts_with("memory_") || name.starts_with("core_memory_") || name == "rethink_memory"
+}
+
+/// A memory block edit made by a sleep-time agent, found in the tool calls of
+/// one of its runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SleeptimeMemoryEdit {
+    /// The run that made the edit.
+    pub run_id: LettaId,
+    /// The tool call message.
+    pub message_id: LettaId,
+    /// When the tool was called.
+    pub date: Timestamp,
+    /// The memory tool that was called.
+    pub tool_name: String,
+    /// Label of the edited block.
+    pub label: String,
+    /// The tool call's arguments.
+    pub arguments: serde_json::Value,
+}
+
+impl SleeptimeMemoryEdit {
+    /// Read an edit from a tool call, if it calls a memory tool on a block.
+    ///
+    /// Other tools are ignored even if they take a `label` argument.
+    pub fn from_tool_call(run_id: &LettaId, message: &ToolCallMessage) -> Option<Self> {
+        if !is_memory_tool(&message.tool_call.name) {
+            return None;
+        }
+        let arguments: serde_json::Value =
+            serde_json::from_str(&message.tool_call.arguments).ok()?;
+        let label = BLOCK_LABEL_ARGUMENTS
+            .iter()
+            .find_map(|key| arguments.get(key)?.as_str())?
+            .to_string();
+        Some(Self {
+            run_id: run_id.clone(),
+            message_id: message.id.clone(),
+            date: message.date,
+            tool_name: message.tool_call.name.clone(),
+            label,
+            arguments,
+        })
+    }
+}
+
+/// Recent activity of a primary agent's sleep-time agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SleeptimeActivity {
+    /// The sleep-time group.
+    pub group: Group,
+    /// The sleep-time agent.
+    pub sleeptime_agent_id: LettaId,
+    /// The sleep-time agent's most recent runs, newest first.
+    pub runs: Vec<Run>,
+    /// Memory edits made in those runs, oldest first.
+    pub edits: Vec<SleeptimeMemoryEdit>,
+}
+
+impl SleeptimeActivity {
+    /// Labels of the blocks edited in the listed runs, sorted.
+    pub fn modified_labels(&self) -> Vec<&str> {
+        let labels: BTreeSet<&str> = self.edits.iter().map(|edit| edit.label.as_str()).collect();
+        labels.into_iter().collect()
+    }
+}
+
 /// Manager configuration for group creation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "manager_type", rename_all = "snake_case")]
@@ -292,4 +387,70 @@ mod tests {
         assert!(json.contains("\"manager_type\":\"round_robin\""));
         assert!(json.contains("\"max_turns\":10"));
     }
+
+    #[test]
+    fn test_sleeptime_activity() {
+        let primary = "agent-00000000-0000-0000-0000-000000000001";
+        let sleeptime = "agent-00000000-0000-0000-0000-000000000002";
+        let run_id: LettaId = "run-00000000-0000-0000-0000-000000000003".parse().unwrap();
+        let group: Group = serde_json::from_value(serde_json::json!({
+            "id": "group-00000000-0000-0000-0000-000000000004",
+            "manager_type": "sleeptime",
+            "agent_ids": [primary, sleeptime],
+            "description": "",
+            "manager_agent_id": primary,
+            "sleeptime_agent_frequency": 5,
+        }))
+        .unwrap();
+        assert!(group.is_sleeptime());
+        assert_eq!(
+            group
+                .worker_ids()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+            [sleeptime]
+        );
+
+        let call = |name: &str, arguments: &str| -> ToolCallMessage {
+            serde_json::from_value(serde_json::json!({
+                "id": "message-00000000-0000-0000-0000-000000000005",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {"name": name, "arguments": arguments, "tool_call_id": "call-1"},
+            }))
+            .unwrap()
+        };
+        let edits: Vec<SleeptimeMemoryEdit> = [
+            call(
+                "memory_replace",
+                r#"{"label": "human", "old_str": "a", "new_str": "b"}"#,
+            ),
+            call(
+                "rethink_memory",
+                r#"{"target_block_label": "persona", "new_memory": "x"}"#,
+            ),
+            call("memory_insert", r#"{"label": "human", "new_str": "c"}"#),
+            call("memory_finish_edits", "{}"),
+            call("memory_insert", "not json"),
+            call("send_message", r#"{"label": "human", "message": "hi"}"#),
+            call(
+                "core_memory_append",
+                r#"{"label": "human", "content": "d"}"#,
+            ),
+        ]
+        .iter()
+        .filter_map(|message| SleeptimeMemoryEdit::from_tool_call(&run_id, message))
+        .collect();
+        assert_eq!(edits.len(), 4);
+        assert_eq!(edits[1].tool_name, "rethink_memory");
+        assert_eq!(edits[3].tool_name, "core_memory_append");
+        assert_eq!(edits[1].arguments["new_memory"], "x");
+
+        let activity = SleeptimeActivity {
+            group,
+            sleeptime_agent_id: sleeptime.parse().unwrap(),
+            runs: Vec::new(),
+            edits,
+        };
+        assert_eq!(activity.modified_labels(), ["human", "persona"]);
+    }
 }
@@ -1,6 +1,8 @@
 //! Memory-related types.
 
 use crate::types::common::{LettaId, Metadata, Timestamp};
+use crate::types::message::LettaMessageUnion;
+use crate::utils::Fnv1a;
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -263,6 +265,112 @@ impl Block {
         self.preserve_on_migration = true;
         self
     }
+
+    /// Check that `value` fits within the block's character limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for the `value` field if it is longer than
+    /// [`limit`](Self::limit) characters.
+    pub fn check_limit(&self, value: &str) -> crate::error::LettaResult<()> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let len = value.chars().count();
+        if len > limit as usize {
+            return Err(crate::error::LettaError::validation_field(
+                format!(
+                    "block '{}' value is {} characters, over its limit of {}",
+                    self.label, len, limit
+                ),
+                "value",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stable hash of the block's value.
+    ///
+    /// 64-bit FNV-1a, as 16 hex digits.
+    pub fn content_hash(&self) -> String {
+        content_hash(&self.value)
+    }
+}
+
+/// 64-bit FNV-1a hash of `text`, as 16 hex digits.
+pub(crate) fn content_hash(text: &str) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(text.as_bytes());
+    hasher.finish_hex()
+}
+
+/// What a block must still look like for a conditional update to apply.
+///
+/// The server has no conditional update, so the block is re-fetched and
+/// compared just before the update is sent. This catches edits made since the
+/// block was read, such as the agent rewriting its own memory, but an edit
+/// landing between the check and the update can still be overwritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockPrecondition {
+    /// The block's `updated_at` timestamp is unchanged.
+    UpdatedAt(Timestamp),
+    /// The block's [`content_hash`](Block::content_hash) is unchanged.
+    ContentHash(String),
+}
+
+impl BlockPrecondition {
+    /// The precondition that `block` is unchanged.
+    ///
+    /// Uses `updated_at` when the server reported it, and the content hash
+    /// otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// # use letta::types::memory::{Block, BlockPrecondition};
+    /// let block = Block::human("The human's name is Alice");
+    /// let precondition = BlockPrecondition::of(&block);
+    /// assert!(precondition.matches(&block));
+    /// assert!(!precondition.matches(&Block::human("The human's name is Bob")));
+    /// ```
+    pub fn of(block: &Block) -> Self {
+        match block.updated_at {
+            Some(updated_at) => Self::UpdatedAt(updated_at),
+            None => Self::ContentHash(block.content_hash()),
+        }
+    }
+
+    /// Whether `block` still satisfies the precondition.
+    pub fn matches(&self, block: &Block) -> bool {
+        match self {
+            Self::UpdatedAt(updated_at) => block.updated_at == Some(*updated_at),
+            Self::ContentHash(hash) => block.content_hash() == *hash,
+        }
+    }
+
+    /// Check `current` against the precondition.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LettaError::Conflict`](crate::error::LettaError::Conflict)
+    /// carrying `current` if it no longer matches.
+    pub fn check(&self, current: Block) -> crate::error::LettaResult<Block> {
+        if self.matches(&current) {
+            return Ok(current);
+        }
+        let message = match self {
+            Self::UpdatedAt(updated_at) => format!(
+                "block '{}' was updated at {}, expected {}",
+                current.label,
+                current
+                    .updated_at
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_else(|| "an unknown time".to_string()),
+                updated_at.to_rfc3339()
+            ),
+            Self::ContentHash(_) => format!("block '{}' value has changed", current.label),
+        };
+        Err(crate::error::LettaError::conflict(message, Some(current)))
+    }
 }
 
 /// Archival memory passage.
@@ -310,17 +418,61 @@ pub struct Passage {
     /// Whether the passage is deleted.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub is_deleted: Option<bool>,
+    /// Tags attached to the passage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
 }
 
 /// Request to create archival memory.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Build with [`new`](Self::new) and the setters; new optional fields may be
+/// added without a breaking change.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct CreateArchivalMemoryRequest {
     /// Memory text content.
     pub text: String,
+    /// Tags to attach to the passage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Metadata to attach to the passage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl CreateArchivalMemoryRequest {
+    /// Create a request for a passage with the given text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Attach tags to the passage.
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Attach metadata to the passage.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
 }
 
 /// Request to update archival memory.
+///
+/// Build with [`new`](Self::new) or [`from_passage`](Self::from_passage) and
+/// adjust the fields; new optional fields may be added without a breaking
+/// change.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct UpdateArchivalMemoryRequest {
     /// Passage ID (required).
     pub id: LettaId,
@@ -363,9 +515,68 @@ pub struct UpdateArchivalMemoryRequest {
     pub embedding: Vec<f32>,
     /// Updated embedding config.
     pub embedding_config: EmbeddingConfig,
+    /// Updated tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+impl UpdateArchivalMemoryRequest {
+    /// Create an update for passage `id` with the fields the server requires
+    /// on every update.
+    pub fn new(
+        id: LettaId,
+        text: impl Into<String>,
+        embedding: Vec<f32>,
+        embedding_config: EmbeddingConfig,
+    ) -> Self {
+        Self {
+            id,
+            created_by_id: None,
+            last_updated_by_id: None,
+            created_at: None,
+            updated_at: None,
+            is_deleted: None,
+            agent_id: None,
+            source_id: None,
+            file_id: None,
+            file_name: None,
+            metadata: None,
+            organization_id: None,
+            text: text.into(),
+            embedding,
+            embedding_config,
+            tags: None,
+        }
+    }
+
+    /// Create an update that rewrites `passage` with its current text and
+    /// embedding, to be adjusted before sending.
+    ///
+    /// The server requires the text, embedding and embedding config on every
+    /// update, so this returns `None` if the passage was listed without its
+    /// embedding; use [`new`](Self::new) in that case.
+    pub fn from_passage(passage: &Passage) -> Option<Self> {
+        let mut request = Self::new(
+            passage.id.clone(),
+            passage.text.clone(),
+            passage.embedding.clone()?,
+            passage.embedding_config.clone()?,
+        );
+        request.metadata = passage.metadata.clone();
+        request.tags = passage.tags.clone();
+        Some(request)
+    }
 }
 
 /// Query parameters for listing archival memory.
+///
+/// The search text and cursor fields are sent to the server. Tag, date and
+/// metadata filters are applied client-side (see
+/// [`ArchivalMemoryQueryParams::matches`]): to each returned page by
+/// [`MemoryApi::list_archival_memory`](crate::api::MemoryApi::list_archival_memory),
+/// so a filtered page may contain fewer than `limit` passages, and to each
+/// passage by
+/// [`MemoryApi::archival_query_paginated`](crate::api::MemoryApi::archival_query_paginated).
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ArchivalMemoryQueryParams {
     /// Search text for semantic search.
@@ -383,6 +594,70 @@ pub struct ArchivalMemoryQueryParams {
     /// Sort order (true for ascending/oldest first).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ascending: Option<bool>,
+    /// Only return passages with these tags (client-side).
+    #[serde(skip)]
+    pub tags: Option<Vec<String>>,
+    /// Require every tag in `tags` instead of any one (client-side).
+    #[serde(skip)]
+    pub match_all_tags: Option<bool>,
+    /// Only return passages created at or after this time (client-side).
+    #[serde(skip)]
+    pub created_after: Option<Timestamp>,
+    /// Only return passages created before this time (client-side).
+    #[serde(skip)]
+    pub created_before: Option<Timestamp>,
+    /// Only return passages whose metadata has all these keys (client-side).
+    #[serde(skip)]
+    pub metadata_keys: Option<Vec<String>>,
+    /// Only return passages whose metadata contains all these key-value
+    /// pairs (client-side).
+    #[serde(skip)]
+    pub metadata: Option<Metadata>,
+}
+
+impl ArchivalMemoryQueryParams {
+    /// Check whether a passage passes the client-side filters.
+    pub fn matches(&self, passage: &Passage) -> bool {
+        if let Some(tags) = self.tags.as_ref().filter(|t| !t.is_empty()) {
+            let has = |tag: &String| passage.tags.iter().flatten().any(|t| t == tag);
+            let matched = if self.match_all_tags.unwrap_or(false) {
+                tags.iter().all(has)
+            } else {
+                tags.iter().any(has)
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(after) = self.created_after {
+            if passage.created_at.is_none_or(|t| t < after) {
+                return false;
+            }
+        }
+        if let Some(before) = self.created_before {
+            if passage.created_at.is_none_or(|t| t >= before) {
+                return false;
+            }
+        }
+        if let Some(keys) = &self.metadata_keys {
+            if !keys.iter().all(|key| {
+                passage
+                    .metadata
+                    .as_ref()
+                    .is_some_and(|m| m.get(key).is_some())
+            }) {
+                return false;
+            }
+        }
+        if let Some(metadata) = &self.metadata {
+            if !metadata.data.iter().all(|(key, value)| {
+                passage.metadata.as_ref().and_then(|m| m.get(key)) == Some(value)
+            }) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 /// Memory response from GET /v1/agents/{id}/core-memory.
@@ -527,6 +802,186 @@ pub struct RecallMemoryEntry {
     pub created_at: Timestamp,
 }
 
+impl RecallMemoryEntry {
+    /// Flatten a message from an agent's history into an entry.
+    ///
+    /// The role is the message type without its `_message` suffix (`user`,
+    /// `assistant`, `reasoning`, `tool_call`, ...). Tool calls are shown as
+    /// `name(arguments)`.
+    pub fn from_message(agent_id: &LettaId, message: &LettaMessageUnion) -> Self {
+        let (role, id, date, content) = match message {
+            LettaMessageUnion::SystemMessage(m) => ("system", &m.id, m.date, m.content.clone()),
+            LettaMessageUnion::UserMessage(m) => ("user", &m.id, m.date, m.content.clone()),
+            LettaMessageUnion::AssistantMessage(m) => {
+                ("assistant", &m.id, m.date, m.content.clone())
+            }
+            LettaMessageUnion::ReasoningMessage(m) => {
+                ("reasoning", &m.id, m.date, m.reasoning.clone())
+            }
+            LettaMessageUnion::HiddenReasoningMessage(m) => (
+                "hidden_reasoning",
+                &m.id,
+                m.date,
+                m.hidden_reasoning.clone().unwrap_or_default(),
+            ),
+            LettaMessageUnion::ToolCallMessage(m) => (
+                "tool_call",
+                &m.id,
+                m.date,
+                format!("{}({})", m.tool_call.name, m.tool_call.arguments),
+            ),
+            LettaMessageUnion::ToolReturnMessage(m) => {
+                ("tool_return", &m.id, m.date, m.tool_return.clone())
+            }
+            LettaMessageUnion::ApprovalRequestMessage(m) => (
+                "approval_request",
+                &m.id,
+                m.date,
+                format!("{}({})", m.tool_call.name, m.tool_call.arguments),
+            ),
+        };
+        Self {
+            id: id.clone(),
+            agent_id: agent_id.clone(),
+            role: role.to_string(),
+            content,
+            created_at: date,
+        }
+    }
+}
+
+/// Breakdown of an agent's context window, from
+/// `GET /v1/agents/{id}/context`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextWindowOverview {
+    /// Maximum tokens the context window can hold.
+    pub context_window_size_max: u32,
+    /// Tokens currently in the context window.
+    pub context_window_size_current: u32,
+    /// Number of messages in the context window.
+    #[serde(default)]
+    pub num_messages: u32,
+    /// Number of passages in archival memory.
+    #[serde(default)]
+    pub num_archival_memory: u32,
+    /// Number of messages in recall memory.
+    #[serde(default)]
+    pub num_recall_memory: u32,
+    /// Tokens used by the external memory summary.
+    #[serde(default)]
+    pub num_tokens_external_memory_summary: u32,
+    /// Summary of the agent's external (archival and recall) memory.
+    #[serde(default)]
+    pub external_memory_summary: String,
+    /// Tokens used by the system prompt.
+    #[serde(default)]
+    pub num_tokens_system: u32,
+    /// Tokens used by core memory.
+    #[serde(default)]
+    pub num_tokens_core_memory: u32,
+    /// Tokens used by the summary of evicted messages.
+    #[serde(default)]
+    pub num_tokens_summary_memory: u32,
+    /// Summary of messages evicted from the context window.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_memory: Option<String>,
+    /// Tokens used by tool definitions.
+    #[serde(default)]
+    pub num_tokens_functions_definitions: u32,
+    /// Tokens used by the message buffer.
+    #[serde(default)]
+    pub num_tokens_messages: u32,
+}
+
+/// How full an agent's context window is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextUsage {
+    /// Size of the context window in tokens.
+    pub context_window: u32,
+    /// Tokens currently in the context window.
+    pub total_tokens: u32,
+    /// Tokens used by the message buffer.
+    pub message_tokens: u32,
+    /// Number of messages in the message buffer.
+    pub message_count: u32,
+}
+
+impl ContextUsage {
+    /// Measure `overview` against a context window of `context_window`
+    /// tokens, such as the agent's [`LLMConfig::context_window`](crate::types::agent::LLMConfig::context_window).
+    pub fn new(overview: &ContextWindowOverview, context_window: Option<u32>) -> Self {
+        Self {
+            context_window: context_window.unwrap_or(overview.context_window_size_max),
+            total_tokens: overview.context_window_size_current,
+            message_tokens: overview.num_tokens_messages,
+            message_count: overview.num_messages,
+        }
+    }
+
+    /// Fraction of the context window in use.
+    pub fn fraction(&self) -> f64 {
+        if self.context_window == 0 {
+            return 0.0;
+        }
+        f64::from(self.total_tokens) / f64::from(self.context_window)
+    }
+
+    /// Fraction of the context window used by the message buffer.
+    pub fn message_fraction(&self) -> f64 {
+        if self.context_window == 0 {
+            return 0.0;
+        }
+        f64::from(self.message_tokens) / f64::from(self.context_window)
+    }
+
+    /// Tokens left before the context window is full.
+    pub fn remaining_tokens(&self) -> u32 {
+        self.context_window.saturating_sub(self.total_tokens)
+    }
+}
+
+/// When a client should ask the server to summarize an agent's messages.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SummarizationPolicy {
+    /// Summarize once this fraction of the context window is in use.
+    pub threshold: f64,
+    /// Number of messages to keep after summarizing.
+    pub target_messages: u32,
+}
+
+impl Default for SummarizationPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.8,
+            target_messages: 10,
+        }
+    }
+}
+
+/// Result of applying a [`SummarizationPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SummarizationOutcome {
+    /// Context usage before summarizing.
+    pub before: ContextUsage,
+    /// Context usage after summarizing, if the policy called for it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<ContextUsage>,
+}
+
+impl SummarizationOutcome {
+    /// Whether the agent's messages were summarized.
+    pub fn summarized(&self) -> bool {
+        self.after.is_some()
+    }
+}
+
+impl SummarizationPolicy {
+    /// Whether `usage` calls for summarizing.
+    pub fn should_summarize(&self, usage: &ContextUsage) -> bool {
+        usage.fraction() >= self.threshold && usage.message_count > self.target_messages
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -558,6 +1013,28 @@ mod tests {
         assert_eq!(block.value, deserialized.value);
     }
 
+    #[test]
+    fn test_block_precondition() {
+        let mut block = Block::human("Name: Alice");
+        let by_hash = BlockPrecondition::of(&block);
+        assert!(matches!(by_hash, BlockPrecondition::ContentHash(_)));
+
+        block.updated_at = Some(chrono::Utc::now());
+        let by_time = BlockPrecondition::of(&block);
+        assert!(by_time.check(block.clone()).is_ok());
+
+        block.value = "Name: Bob".to_string();
+        block.updated_at = Some(chrono::Utc::now() + chrono::Duration::seconds(1));
+        assert!(!by_hash.matches(&block));
+        match by_time.check(block) {
+            Err(crate::error::LettaError::Conflict {
+                current: Some(current),
+                ..
+            }) => assert_eq!(current.value, "Name: Bob"),
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_memory_structure() {
         let memory = Memory {
@@ -629,6 +1106,7 @@ mod tests {
             created_at: Some(chrono::Utc::now()),
             updated_at: None,
             is_deleted: None,
+            tags: None,
         };
 
         let json = serde_json::to_string(&passage).unwrap();
@@ -650,4 +1128,115 @@ mod tests {
         assert!(json.contains("query"));
         assert!(!json.contains("before")); // Should be skipped when None
     }
+
+    #[test]
+    fn test_archival_memory_query_params() {
+        let passage: Passage = serde_json::from_value(serde_json::json!({
+            "id": "passage-550e8400-e29b-41d4-a716-446655440003",
+            "agent_id": "agent-00000000-0000-0000-0000-000000000000",
+            "text": "The user likes tea.",
+            "tags": ["preferences", "drinks"],
+            "metadata": {"source": "chat", "confidence": 0.9},
+            "created_at": "2025-01-15T12:00:00Z"
+        }))
+        .unwrap();
+
+        let params = ArchivalMemoryQueryParams {
+            tags: Some(vec!["drinks".to_string(), "food".to_string()]),
+            created_after: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+            metadata_keys: Some(vec!["confidence".to_string()]),
+            ..Default::default()
+        };
+        assert!(params.matches(&passage));
+
+        let params = ArchivalMemoryQueryParams {
+            tags: Some(vec!["drinks".to_string(), "food".to_string()]),
+            match_all_tags: Some(true),
+            ..Default::default()
+        };
+        assert!(!params.matches(&passage));
+
+        let mut metadata = Metadata::new();
+        metadata.insert("source".to_string(), serde_json::json!("import"));
+        let params = ArchivalMemoryQueryParams {
+            metadata: Some(metadata),
+            ..Default::default()
+        };
+        assert!(!params.matches(&passage));
+
+        let params = ArchivalMemoryQueryParams {
+            created_before: Some("2025-01-15T12:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(!params.matches(&passage));
+
+        // Client-side filters never reach the query string
+        let query = serde_urlencoded::to_string(&ArchivalMemoryQueryParams {
+            search: Some("tea".to_string()),
+            tags: Some(vec!["drinks".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(query, "search=tea");
+
+        let request = CreateArchivalMemoryRequest::new("note").tags(["a", "b"]);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({"text": "note", "tags": ["a", "b"]})
+        );
+    }
+
+    #[test]
+    fn test_context_usage() {
+        let overview: ContextWindowOverview = serde_json::from_value(serde_json::json!({
+            "context_window_size_max": 32000,
+            "context_window_size_current": 6000,
+            "num_messages": 24,
+            "num_archival_memory": 3,
+            "num_recall_memory": 40,
+            "num_tokens_messages": 4000,
+            "messages": [],
+            "core_memory": "<memory_blocks>...</memory_blocks>"
+        }))
+        .unwrap();
+
+        let usage = ContextUsage::new(&overview, Some(8000));
+        assert_eq!(usage.fraction(), 0.75);
+        assert_eq!(usage.message_fraction(), 0.5);
+        assert_eq!(usage.remaining_tokens(), 2000);
+        assert_eq!(ContextUsage::new(&overview, None).context_window, 32000);
+
+        let policy = SummarizationPolicy {
+            threshold: 0.7,
+            target_messages: 10,
+        };
+        assert!(policy.should_summarize(&usage));
+        assert!(!SummarizationPolicy::default().should_summarize(&usage));
+        let few_messages = ContextUsage {
+            message_count: 8,
+            ..usage
+        };
+        assert!(!policy.should_summarize(&few_messages));
+    }
+
+    #[test]
+    fn test_recall_memory_entry_from_message() {
+        let agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000").unwrap();
+        let message: LettaMessageUnion = serde_json::from_value(serde_json::json!({
+            "message_type": "tool_call_message",
+            "id": "message-550e8400-e29b-41d4-a716-446655440000",
+            "date": "2025-01-15T12:00:00Z",
+            "tool_call": {
+                "name": "archival_memory_search",
+                "arguments": "{\"query\": \"tea\"}",
+                "tool_call_id": "call_1"
+            }
+        }))
+        .unwrap();
+
+        let entry = RecallMemoryEntry::from_message(&agent_id, &message);
+        assert_eq!(entry.role, "tool_call");
+        assert_eq!(entry.content, r#"archival_memory_search({"query": "tea"})"#);
+        assert_eq!(entry.agent_id, agent_id);
+    }
 }
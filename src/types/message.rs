@@ -448,6 +448,9 @@ pub enum MessageType {
     /// Tool execution result message.
     #[serde(rename = "tool_return_message")]
     ToolReturnMessage,
+    /// Tool call awaiting approval or client-side execution.
+    #[serde(rename = "approval_request_message")]
+    ApprovalRequestMessage,
     /// Stop reason indicator.
     #[serde(rename = "stop_reason")]
     StopReason,
@@ -477,6 +480,8 @@ pub enum LettaMessageUnion {
     ToolCallMessage(ToolCallMessage),
     /// Tool execution result message.
     ToolReturnMessage(ToolReturnMessage),
+    /// Tool call waiting for the client to approve or execute it.
+    ApprovalRequestMessage(ApprovalRequestMessage),
 }
 
 /// System message from Letta.
@@ -659,6 +664,83 @@ pub struct ToolReturnMessage {
     pub stderr: Option<Vec<String>>,
 }
 
+/// Tool call that the agent cannot run itself.
+///
+/// Sent with a [`StopReasonType::RequiresApproval`] stop reason when a tool
+/// requires approval or is executed by the client. The agent resumes once it
+/// receives an [`ApprovalCreate`] for the call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequestMessage {
+    /// Message ID.
+    pub id: LettaId,
+    /// Message timestamp.
+    pub date: Timestamp,
+    /// Optional participant name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Offline threading ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otid: Option<String>,
+    /// Sender ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<LettaId>,
+    /// Step ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<LettaId>,
+    /// The pending tool call.
+    pub tool_call: ToolCall,
+}
+
+/// Message answering pending [`ApprovalRequestMessage`]s.
+///
+/// Sent in place of a [`MessageCreate`] to resume an agent that stopped with
+/// [`StopReasonType::RequiresApproval`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "approval")]
+pub struct ApprovalCreate {
+    /// Results of the pending tool calls, executed by the client.
+    pub approvals: Vec<ToolReturnApproval>,
+}
+
+/// Result of a tool call executed by the client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "tool")]
+pub struct ToolReturnApproval {
+    /// ID of the tool call this result answers.
+    pub tool_call_id: String,
+    /// Tool return value as string.
+    pub tool_return: String,
+    /// Whether the tool succeeded.
+    pub status: ToolReturnStatus,
+    /// Captured stdout from tool invocation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<Vec<String>>,
+    /// Captured stderr from tool invocation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<Vec<String>>,
+}
+
+impl ToolReturnApproval {
+    /// A successful tool result.
+    pub fn success(tool_call_id: impl Into<String>, tool_return: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: tool_call_id.into(),
+            tool_return: tool_return.into(),
+            status: ToolReturnStatus::Success,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    /// A failed tool result; `message` is shown to the agent.
+    pub fn error(tool_call_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            status: ToolReturnStatus::Error,
+            ..Self::success(tool_call_id, message)
+        }
+    }
+}
+
 /// Request to create a message for Letta agents.
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 pub struct MessageCreate {
@@ -818,6 +900,10 @@ pub enum StopReasonType {
     NoToolCall,
     /// Tool rule triggered.
     ToolRule,
+    /// Run was cancelled.
+    Cancelled,
+    /// Waiting for tool calls to be approved or executed by the client.
+    RequiresApproval,
 }
 
 /// Reason why message processing stopped.
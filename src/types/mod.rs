@@ -20,6 +20,7 @@ pub mod tags;
 pub mod telemetry;
 pub mod template;
 pub mod tool;
+pub mod typed_block;
 pub mod voice;
 
 // Re-export commonly used types
@@ -40,4 +41,5 @@ pub use tags::*;
 pub use telemetry::*;
 pub use template::*;
 pub use tool::*;
+pub use typed_block::*;
 pub use voice::*;
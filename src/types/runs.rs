@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use crate::types::common::{LettaId, Timestamp};
+use crate::types::common::{LettaId, SortOrder, Timestamp};
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
@@ -83,6 +83,94 @@ pub struct Job {
     pub callback_error: Option<String>,
 }
 
+impl Job {
+    /// The agent this job was created for, if recorded in its metadata.
+    ///
+    /// Jobs created by async message processing carry the agent ID in their
+    /// metadata rather than as a top-level field.
+    pub fn agent_id(&self) -> Option<LettaId> {
+        self.metadata
+            .as_ref()?
+            .get("agent_id")?
+            .as_str()?
+            .parse()
+            .ok()
+    }
+}
+
+/// Query parameters for listing jobs.
+///
+/// The server only understands the pagination and `source_id` parameters;
+/// `agent_id`, `statuses`, `job_type` and the date range are applied client-side
+/// (see [`ListJobsParams::matches`]): `JobApi::list` filters the one page it
+/// fetches, so its result may contain fewer than `limit` jobs, while
+/// `JobApi::paginated` filters the streamed jobs and keeps paging.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListJobsParams {
+    /// Filter by source ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<LettaId>,
+    /// Pagination cursor (before).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Pagination cursor (after).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Maximum number of jobs to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// Sort order (true for oldest first).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ascending: Option<bool>,
+    /// Only return jobs created for this agent (client-side).
+    #[serde(skip)]
+    pub agent_id: Option<LettaId>,
+    /// Only return jobs in one of these statuses (client-side).
+    #[serde(skip)]
+    pub statuses: Option<Vec<JobStatus>>,
+    /// Only return jobs of this type (client-side).
+    #[serde(skip)]
+    pub job_type: Option<JobType>,
+    /// Only return jobs created at or after this time (client-side).
+    #[serde(skip)]
+    pub created_after: Option<Timestamp>,
+    /// Only return jobs created before this time (client-side).
+    #[serde(skip)]
+    pub created_before: Option<Timestamp>,
+}
+
+impl ListJobsParams {
+    /// Check whether a job passes the client-side filters.
+    pub fn matches(&self, job: &Job) -> bool {
+        if let Some(agent_id) = &self.agent_id {
+            if job.agent_id().as_ref() != Some(agent_id) {
+                return false;
+            }
+        }
+        if let Some(statuses) = &self.statuses {
+            if job.status.is_none_or(|s| !statuses.contains(&s)) {
+                return false;
+            }
+        }
+        if let Some(job_type) = self.job_type {
+            if job.job_type != Some(job_type) {
+                return false;
+            }
+        }
+        if let Some(after) = self.created_after {
+            if job.created_at.is_none_or(|t| t < after) {
+                return false;
+            }
+        }
+        if let Some(before) = self.created_before {
+            if job.created_at.is_none_or(|t| t >= before) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Representation of a run for async message processing.
 /// Extends Job with request configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -128,6 +216,29 @@ pub struct Run {
     /// Request configuration for the run.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_config: Option<RunRequestConfig>,
+    /// Agent that owns the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<LettaId>,
+    /// Whether the run was started in background mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<bool>,
+    /// Why the run stopped, once it has finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<crate::types::message::StopReasonType>,
+}
+
+impl Run {
+    /// The agent this run belongs to.
+    ///
+    /// Falls back to the request configuration for servers that do not
+    /// report the agent ID on the run itself.
+    pub fn owner_id(&self) -> Option<&LettaId> {
+        self.agent_id.as_ref().or_else(|| {
+            self.request_config
+                .as_ref()
+                .and_then(|config| config.agent_id.as_ref())
+        })
+    }
 }
 
 /// Configuration for a run request.
@@ -157,23 +268,127 @@ pub struct RunRequestConfig {
 }
 
 /// Query parameters for listing runs.
+///
+/// Agent IDs, the background flag and the cursor fields are sent to the
+/// server. Status and date filters are applied client-side (see
+/// [`ListRunsParams::matches`]): `RunApi::list` filters the one page it
+/// fetches, so its result may contain fewer than `limit` runs, while
+/// `RunApi::paginated` filters the streamed runs and keeps paging.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ListRunsParams {
+    /// Only return runs belonging to these agents.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_ids: Option<Vec<LettaId>>,
+    /// Only return runs started (or not started) in background mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<bool>,
+    /// Pagination cursor (before).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
     /// Pagination cursor (after).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<String>,
+    /// Maximum number of runs to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// Sort order (true for oldest first).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ascending: Option<bool>,
+    /// Only return runs in one of these statuses (client-side).
+    #[serde(skip)]
+    pub statuses: Option<Vec<JobStatus>>,
+    /// Only return runs created at or after this time (client-side).
+    #[serde(skip)]
+    pub created_after: Option<Timestamp>,
+    /// Only return runs created before this time (client-side).
+    #[serde(skip)]
+    pub created_before: Option<Timestamp>,
+}
+
+impl ListRunsParams {
+    /// Check whether a run passes the client-side filters.
+    pub fn matches(&self, run: &Run) -> bool {
+        if let Some(statuses) = &self.statuses {
+            if run.status.is_none_or(|s| !statuses.contains(&s)) {
+                return false;
+            }
+        }
+        if let Some(after) = self.created_after {
+            if run.created_at.is_none_or(|t| t < after) {
+                return false;
+            }
+        }
+        if let Some(before) = self.created_before {
+            if run.created_at.is_none_or(|t| t >= before) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Encode the server-side fields as query pairs.
+    ///
+    /// The server expects `agent_ids` as a repeated key, which
+    /// `serde_urlencoded` cannot produce from a `Vec`.
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        for agent_id in self.agent_ids.iter().flatten() {
+            query.push(("agent_ids", agent_id.to_string()));
+        }
+        if let Some(background) = self.background {
+            query.push(("background", background.to_string()));
+        }
+        if let Some(before) = &self.before {
+            query.push(("before", before.clone()));
+        }
+        if let Some(after) = &self.after {
+            query.push(("after", after.clone()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(ascending) = self.ascending {
+            query.push(("ascending", ascending.to_string()));
+        }
+        query
+    }
+}
+
+/// Query parameters for listing the messages of a run.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListRunMessagesParams {
     /// Pagination cursor (before).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub before: Option<String>,
-    /// Maximum number of runs to return.
+    /// Pagination cursor (after).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Maximum number of messages to return.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
-    /// Filter by status.
+    /// Sort order by creation time.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub status: Option<JobStatus>,
-    /// Filter by agent ID.
+    pub order: Option<SortOrder>,
+    /// Only return messages with this role.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub agent_id: Option<LettaId>,
+    pub role: Option<crate::types::message::MessageRole>,
+}
+
+/// Query parameters for listing the steps of a run.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListRunStepsParams {
+    /// Pagination cursor (before).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Pagination cursor (after).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Maximum number of steps to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// Sort order by creation time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
 }
 
 /// Run status update request.
@@ -192,6 +407,27 @@ pub struct ListStepsParams {
     /// Filter by agent ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub agent_id: Option<LettaId>,
+    /// Filter by model name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Filter by project ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Only return steps created at or after this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<Timestamp>,
+    /// Only return steps created before this time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<Timestamp>,
+    /// Filter by feedback value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<StepFeedback>,
+    /// Only return steps that have (or lack) feedback.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_feedback: Option<bool>,
+    /// Sort order by creation time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
     /// Pagination cursor (before).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub before: Option<String>,
@@ -212,12 +448,68 @@ pub struct Step {
     /// Step ID.
     pub id: LettaId,
     /// Run ID this step belongs to.
-    pub run_id: LettaId,
+    #[serde(default, alias = "job_id", skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<LettaId>,
+    /// Agent that executed the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<LettaId>,
     /// Step execution order.
-    pub step_number: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_number: Option<i32>,
     /// Step name or type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// Where the step originated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// Organization ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<LettaId>,
+    /// Project ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Provider ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<LettaId>,
+    /// Provider name (e.g. "openai").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_name: Option<String>,
+    /// Provider category ("base" or "byok").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_category: Option<String>,
+    /// Model used for the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Model endpoint used for the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_endpoint: Option<String>,
+    /// Context window limit at the time of the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window_limit: Option<i32>,
+    /// Completion tokens used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens: Option<i32>,
+    /// Prompt tokens used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens: Option<i32>,
+    /// Total tokens used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<i32>,
+    /// Provider-specific completion token details.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<serde_json::Value>,
+    /// Why the step stopped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<crate::types::message::StopReasonType>,
+    /// Step tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Trace ID for observability.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Feedback left on the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feedback: Option<StepFeedback>,
     /// Messages produced in this step.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub messages: Option<Vec<crate::types::message::LettaMessageUnion>>,
@@ -235,8 +527,52 @@ pub struct Step {
     pub provider_trace: Option<ProviderTrace>,
 }
 
+impl Step {
+    /// Token counts for this step.
+    ///
+    /// The server reports tokens as top-level fields; the nested `usage`
+    /// object is used as a fallback for older responses.
+    pub fn token_usage(&self) -> StepUsage {
+        let nested = self.usage.as_ref();
+        StepUsage {
+            completion_tokens: self
+                .completion_tokens
+                .or_else(|| nested.and_then(|u| u.completion_tokens)),
+            prompt_tokens: self
+                .prompt_tokens
+                .or_else(|| nested.and_then(|u| u.prompt_tokens)),
+            total_tokens: self
+                .total_tokens
+                .or_else(|| nested.and_then(|u| u.total_tokens)),
+        }
+    }
+
+    /// Usage and cost breakdown for this step.
+    ///
+    /// The cost is only computed when pricing for the step's model is given.
+    pub fn usage_breakdown(&self, pricing: Option<&ModelPricing>) -> StepUsageBreakdown {
+        let usage = self.token_usage();
+        let tokens = |count: Option<i32>| count.and_then(|c| u64::try_from(c).ok());
+        let prompt_tokens = tokens(usage.prompt_tokens).unwrap_or(0);
+        let completion_tokens = tokens(usage.completion_tokens).unwrap_or(0);
+        let total_tokens = tokens(usage.total_tokens).unwrap_or(prompt_tokens + completion_tokens);
+
+        StepUsageBreakdown {
+            step_id: self.id.clone(),
+            run_id: self.run_id.clone(),
+            agent_id: self.agent_id.clone(),
+            model: self.model.clone(),
+            created_at: self.created_at,
+            prompt_tokens,
+            completion_tokens,
+            total_tokens,
+            cost: pricing.map(|p| p.cost(prompt_tokens, completion_tokens)),
+        }
+    }
+}
+
 /// Token usage for a step.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StepUsage {
     /// Completion tokens used.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -249,6 +585,142 @@ pub struct StepUsage {
     pub total_tokens: Option<i32>,
 }
 
+/// Per-token pricing for a model, in arbitrary currency units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Price per million prompt tokens.
+    pub prompt_per_million: f64,
+    /// Price per million completion tokens.
+    pub completion_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Create pricing from per-million-token prices.
+    pub fn per_million(prompt: f64, completion: f64) -> Self {
+        Self {
+            prompt_per_million: prompt,
+            completion_per_million: completion,
+        }
+    }
+
+    /// Cost of the given token counts.
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.prompt_per_million
+            + completion_tokens as f64 * self.completion_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Usage and cost of a single step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepUsageBreakdown {
+    /// Step ID.
+    pub step_id: LettaId,
+    /// Run the step belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<LettaId>,
+    /// Agent that executed the step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<LettaId>,
+    /// Model used for the step.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// When the step was created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<Timestamp>,
+    /// Prompt tokens used.
+    pub prompt_tokens: u64,
+    /// Completion tokens used.
+    pub completion_tokens: u64,
+    /// Total tokens used.
+    pub total_tokens: u64,
+    /// Cost of the step, if pricing for its model was known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+/// Aggregated usage over a set of steps.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    /// Number of steps.
+    pub step_count: u64,
+    /// Prompt tokens used.
+    pub prompt_tokens: u64,
+    /// Completion tokens used.
+    pub completion_tokens: u64,
+    /// Total tokens used.
+    pub total_tokens: u64,
+    /// Total cost of steps whose model had known pricing.
+    pub cost: f64,
+    /// Number of steps that could not be priced.
+    pub unpriced_steps: u64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, step: &StepUsageBreakdown) {
+        self.step_count += 1;
+        self.prompt_tokens += step.prompt_tokens;
+        self.completion_tokens += step.completion_tokens;
+        self.total_tokens += step.total_tokens;
+        match step.cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_steps += 1,
+        }
+    }
+}
+
+/// Usage report built from a list of steps.
+///
+/// # Example
+/// ```
+/// # use letta::types::{ModelPricing, UsageReport};
+/// # use std::collections::HashMap;
+/// let mut pricing = HashMap::new();
+/// pricing.insert("gpt-4o-mini".to_string(), ModelPricing::per_million(0.15, 0.60));
+///
+/// let report = UsageReport::from_steps(&[], &pricing);
+/// assert_eq!(report.totals.step_count, 0);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageReport {
+    /// Per-step breakdown, in input order.
+    pub steps: Vec<StepUsageBreakdown>,
+    /// Totals across all steps.
+    pub totals: UsageTotals,
+    /// Totals grouped by model (steps without a model are grouped under "unknown").
+    pub by_model: std::collections::BTreeMap<String, UsageTotals>,
+    /// Totals grouped by agent ID (steps without an agent are grouped under "unknown").
+    pub by_agent: std::collections::BTreeMap<String, UsageTotals>,
+}
+
+impl UsageReport {
+    /// Build a report from steps, pricing each by its model name.
+    pub fn from_steps(
+        steps: &[Step],
+        pricing: &std::collections::HashMap<String, ModelPricing>,
+    ) -> Self {
+        let mut report = Self::default();
+        for step in steps {
+            let model_pricing = step.model.as_ref().and_then(|m| pricing.get(m));
+            report.push(step.usage_breakdown(model_pricing));
+        }
+        report
+    }
+
+    /// Add a single step breakdown to the report.
+    pub fn push(&mut self, step: StepUsageBreakdown) {
+        self.totals.add(&step);
+        let model = step.model.clone().unwrap_or_else(|| "unknown".to_string());
+        self.by_model.entry(model).or_default().add(&step);
+        let agent = step
+            .agent_id
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), ToString::to_string);
+        self.by_agent.entry(agent).or_default().add(&step);
+        self.steps.push(step);
+    }
+}
+
 /// Provider execution trace information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderTrace {
@@ -369,4 +841,141 @@ mod tests {
             "\"negative\""
         );
     }
+
+    #[test]
+    fn test_list_jobs_params_client_side_filters() {
+        let job: Job = serde_json::from_value(serde_json::json!({
+            "id": "job-550e8400-e29b-41d4-a716-446655440000",
+            "status": "running",
+            "job_type": "run",
+            "created_at": "2025-01-15T12:00:00Z",
+            "metadata": {"agent_id": "agent-550e8400-e29b-41d4-a716-446655440001"}
+        }))
+        .unwrap();
+
+        let agent_id: LettaId = "agent-550e8400-e29b-41d4-a716-446655440001"
+            .parse()
+            .unwrap();
+        assert_eq!(job.agent_id(), Some(agent_id.clone()));
+
+        let params = ListJobsParams {
+            agent_id: Some(agent_id),
+            statuses: Some(vec![JobStatus::Running, JobStatus::Pending]),
+            job_type: Some(JobType::Run),
+            created_after: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(params.matches(&job));
+
+        let params = ListJobsParams {
+            statuses: Some(vec![JobStatus::Completed]),
+            ..Default::default()
+        };
+        assert!(!params.matches(&job));
+
+        let params = ListJobsParams {
+            created_before: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(!params.matches(&job));
+
+        // Client-side filters never reach the query string
+        let query = serde_urlencoded::to_string(&ListJobsParams {
+            limit: Some(10),
+            job_type: Some(JobType::Run),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(query, "limit=10");
+    }
+
+    #[test]
+    fn test_list_runs_params() {
+        let run: Run = serde_json::from_value(serde_json::json!({
+            "id": "run-550e8400-e29b-41d4-a716-446655440000",
+            "status": "completed",
+            "created_at": "2025-01-15T12:00:00Z",
+            "background": true,
+            "stop_reason": "end_turn",
+            "request_config": {"agent_id": "agent-550e8400-e29b-41d4-a716-446655440001"}
+        }))
+        .unwrap();
+
+        let agent_id: LettaId = "agent-550e8400-e29b-41d4-a716-446655440001"
+            .parse()
+            .unwrap();
+        assert_eq!(run.owner_id(), Some(&agent_id));
+
+        let params = ListRunsParams {
+            statuses: Some(vec![JobStatus::Completed, JobStatus::Failed]),
+            created_after: Some("2025-01-01T00:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(params.matches(&run));
+
+        let params = ListRunsParams {
+            statuses: Some(vec![JobStatus::Running]),
+            ..Default::default()
+        };
+        assert!(!params.matches(&run));
+
+        // Agent IDs are repeated; client-side filters are left out
+        let other: LettaId = "agent-550e8400-e29b-41d4-a716-446655440002"
+            .parse()
+            .unwrap();
+        let params = ListRunsParams {
+            agent_ids: Some(vec![agent_id, other]),
+            background: Some(true),
+            limit: Some(5),
+            statuses: Some(vec![JobStatus::Running]),
+            ..Default::default()
+        };
+        let query = serde_urlencoded::to_string(params.to_query()).unwrap();
+        assert_eq!(
+            query,
+            "agent_ids=agent-550e8400-e29b-41d4-a716-446655440001\
+             &agent_ids=agent-550e8400-e29b-41d4-a716-446655440002\
+             &background=true&limit=5"
+        );
+    }
+
+    #[test]
+    fn test_step_usage_report() {
+        let steps: Vec<Step> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "step-550e8400-e29b-41d4-a716-446655440000",
+                "job_id": "run-550e8400-e29b-41d4-a716-446655440000",
+                "agent_id": "agent-550e8400-e29b-41d4-a716-446655440001",
+                "model": "gpt-4o-mini",
+                "prompt_tokens": 1000,
+                "completion_tokens": 200,
+                "total_tokens": 1200
+            },
+            {
+                "id": "step-550e8400-e29b-41d4-a716-446655440001",
+                "agent_id": "agent-550e8400-e29b-41d4-a716-446655440001",
+                "model": "unpriced-model",
+                "usage": {"prompt_tokens": 50, "completion_tokens": 10}
+            }
+        ]))
+        .unwrap();
+
+        assert!(steps[0].run_id.is_some());
+
+        let mut pricing = std::collections::HashMap::new();
+        pricing.insert(
+            "gpt-4o-mini".to_string(),
+            ModelPricing::per_million(1_000.0, 2_000.0),
+        );
+
+        let report = UsageReport::from_steps(&steps, &pricing);
+        assert_eq!(report.totals.step_count, 2);
+        assert_eq!(report.totals.prompt_tokens, 1050);
+        assert_eq!(report.totals.total_tokens, 1260);
+        assert_eq!(report.totals.unpriced_steps, 1);
+        assert!((report.totals.cost - 1.4).abs() < 1e-9);
+        assert_eq!(report.by_model.len(), 2);
+        assert_eq!(report.by_agent.len(), 1);
+        assert_eq!(report.steps[1].cost, None);
+    }
 }
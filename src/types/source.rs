@@ -234,6 +234,119 @@ pub struct ListPassagesParams {
     pub after: Option<String>,
 }
 
+/// A file attached to an agent through one of its sources, as reported by
+/// the agent files endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentFileAttachment {
+    /// ID of the file-agent association.
+    pub id: LettaId,
+    /// File ID.
+    pub file_id: LettaId,
+    /// File name.
+    #[serde(default)]
+    pub file_name: String,
+    /// Source (folder) the file belongs to.
+    #[serde(alias = "folder_id")]
+    pub source_id: LettaId,
+    /// Name of the source (folder).
+    #[serde(alias = "folder_name", skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    /// Whether the file is currently open in the agent's context window.
+    #[serde(default)]
+    pub is_open: bool,
+    /// When the agent last accessed the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_accessed_at: Option<Timestamp>,
+    /// Portion of the file visible to the agent while open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible_content: Option<String>,
+    /// First visible line, if the agent opened a line range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<i32>,
+    /// Last visible line, if the agent opened a line range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<i32>,
+}
+
+/// One page of files attached to an agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedAgentFiles {
+    /// Files on this page.
+    pub files: Vec<AgentFileAttachment>,
+    /// Cursor for the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Whether more files are available.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// A file visible to an agent, with its open/closed state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentFile {
+    /// Metadata of the underlying source file.
+    #[serde(flatten)]
+    pub metadata: FileMetadata,
+    /// Whether the file is currently open in the agent's context window.
+    pub is_open: bool,
+    /// When the agent last accessed the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_accessed_at: Option<Timestamp>,
+    /// Portion of the file visible to the agent while open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible_content: Option<String>,
+}
+
+impl AgentFile {
+    /// Combine an attachment with the metadata of its source file.
+    ///
+    /// When `metadata` is `None` (e.g. the file was not found in its source
+    /// listing) a minimal [`FileMetadata`] is built from the attachment.
+    pub fn from_attachment(
+        attachment: AgentFileAttachment,
+        metadata: Option<FileMetadata>,
+    ) -> Self {
+        let metadata = metadata.unwrap_or_else(|| FileMetadata {
+            id: Some(attachment.file_id.clone()),
+            organization_id: None,
+            source_id: attachment.source_id.clone(),
+            file_name: Some(attachment.file_name.clone()),
+            file_path: None,
+            file_type: None,
+            file_size: None,
+            file_creation_date: None,
+            file_last_modified_date: None,
+            processing_status: None,
+            error_message: None,
+            created_at: None,
+            updated_at: None,
+            is_deleted: None,
+            content: None,
+        });
+
+        Self {
+            metadata,
+            is_open: attachment.is_open,
+            last_accessed_at: attachment.last_accessed_at,
+            visible_content: attachment.visible_content,
+        }
+    }
+}
+
+/// Query parameters for listing an agent's files.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ListAgentFilesParams {
+    /// Pagination cursor from a previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Maximum number of files to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// Only return open (`true`) or closed (`false`) files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_open: Option<bool>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,4 +408,32 @@ mod tests {
         assert_eq!(file_metadata.source_id, deserialized.source_id);
         assert_eq!(file_metadata.file_name, deserialized.file_name);
     }
+
+    #[test]
+    fn test_agent_file_from_attachment() {
+        let attachment: AgentFileAttachment = serde_json::from_value(serde_json::json!({
+            "id": "file_agent-550e8400-e29b-41d4-a716-446655440000",
+            "file_id": "file-550e8400-e29b-41d4-a716-446655440001",
+            "file_name": "notes.md",
+            "folder_id": "source-550e8400-e29b-41d4-a716-446655440002",
+            "folder_name": "docs",
+            "is_open": true,
+            "visible_content": "# Notes"
+        }))
+        .unwrap();
+        assert_eq!(attachment.source_name.as_deref(), Some("docs"));
+
+        let file = AgentFile::from_attachment(attachment, None);
+        assert!(file.is_open);
+        assert_eq!(file.metadata.file_name.as_deref(), Some("notes.md"));
+        assert_eq!(
+            file.metadata.source_id.as_str(),
+            "source-550e8400-e29b-41d4-a716-446655440002"
+        );
+
+        // Metadata fields are flattened next to the open state
+        let json = serde_json::to_value(&file).unwrap();
+        assert_eq!(json["file_name"], "notes.md");
+        assert_eq!(json["is_open"], true);
+    }
 }
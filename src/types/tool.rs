@@ -1,6 +1,6 @@
 //! Tool-related types.
 
-use crate::types::common::{LettaId, Metadata, Timestamp};
+use crate::types::common::{BulkReport, LettaId, Metadata, Timestamp};
 use bon::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -59,7 +59,7 @@ pub enum SourceType {
 }
 
 /// Pip requirement for a tool.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PipRequirement {
     /// Package name.
     pub package: String,
@@ -305,6 +305,99 @@ pub struct StreamableHttpServerConfig {
     pub custom_headers: Option<std::collections::HashMap<String, String>>,
 }
 
+impl SseServerConfig {
+    /// Configure an SSE server at `server_url`.
+    pub fn new(server_name: impl Into<String>, server_url: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            server_type: Some(McpServerType::Sse),
+            server_url: server_url.into(),
+            auth_header: None,
+            auth_token: None,
+            custom_headers: None,
+        }
+    }
+
+    /// Authenticate with an `Authorization: Bearer <token>` header.
+    pub fn bearer_token(self, token: impl AsRef<str>) -> Self {
+        self.auth("Authorization", format!("Bearer {}", token.as_ref()))
+    }
+
+    /// Authenticate by sending `value` in the `header` header.
+    pub fn auth(mut self, header: impl Into<String>, value: impl Into<String>) -> Self {
+        self.auth_header = Some(header.into());
+        self.auth_token = Some(value.into());
+        self
+    }
+
+    /// Send an extra header with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom_headers
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
+impl StdioServerConfig {
+    /// Configure a server that the Letta server runs as `command args...`.
+    pub fn new<I, S>(server_name: impl Into<String>, command: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            server_name: server_name.into(),
+            server_type: Some(McpServerType::Stdio),
+            command: command.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            env: None,
+        }
+    }
+
+    /// Set an environment variable for the command.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env
+            .get_or_insert_with(Default::default)
+            .insert(key.into(), value.into());
+        self
+    }
+}
+
+impl StreamableHttpServerConfig {
+    /// Configure a streamable HTTP server at `server_url`.
+    pub fn new(server_name: impl Into<String>, server_url: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            server_type: Some(McpServerType::StreamableHttp),
+            server_url: server_url.into(),
+            auth_header: None,
+            auth_token: None,
+            custom_headers: None,
+        }
+    }
+
+    /// Authenticate with an `Authorization: Bearer <token>` header.
+    pub fn bearer_token(self, token: impl AsRef<str>) -> Self {
+        self.auth("Authorization", format!("Bearer {}", token.as_ref()))
+    }
+
+    /// Authenticate by sending `value` in the `header` header.
+    pub fn auth(mut self, header: impl Into<String>, value: impl Into<String>) -> Self {
+        self.auth_header = Some(header.into());
+        self.auth_token = Some(value.into());
+        self
+    }
+
+    /// Send an extra header with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom_headers
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
 /// MCP server configuration (union type).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -317,6 +410,44 @@ pub enum McpServerConfig {
     StreamableHttp(StreamableHttpServerConfig),
 }
 
+impl McpServerConfig {
+    /// The server's name.
+    pub fn server_name(&self) -> &str {
+        match self {
+            Self::Sse(config) => &config.server_name,
+            Self::Stdio(config) => &config.server_name,
+            Self::StreamableHttp(config) => &config.server_name,
+        }
+    }
+
+    /// The server's transport.
+    pub fn server_type(&self) -> McpServerType {
+        match self {
+            Self::Sse(_) => McpServerType::Sse,
+            Self::Stdio(_) => McpServerType::Stdio,
+            Self::StreamableHttp(_) => McpServerType::StreamableHttp,
+        }
+    }
+}
+
+impl From<SseServerConfig> for McpServerConfig {
+    fn from(config: SseServerConfig) -> Self {
+        Self::Sse(config)
+    }
+}
+
+impl From<StdioServerConfig> for McpServerConfig {
+    fn from(config: StdioServerConfig) -> Self {
+        Self::Stdio(config)
+    }
+}
+
+impl From<StreamableHttpServerConfig> for McpServerConfig {
+    fn from(config: StreamableHttpServerConfig) -> Self {
+        Self::StreamableHttp(config)
+    }
+}
+
 /// Update request for SSE MCP server.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -389,6 +520,121 @@ pub struct TestMcpServerRequest {
     pub config: McpServerConfig,
 }
 
+/// Whether an MCP server connection test succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTestStatus {
+    /// The server connected and listed its tools.
+    Success,
+    /// The server could not be reached or failed to list its tools.
+    Error,
+}
+
+/// Result of testing an MCP server connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "McpTestResponse")]
+pub struct McpTestResult {
+    /// Whether the connection succeeded.
+    pub status: McpTestStatus,
+    /// Tools the server offers.
+    pub tools: Vec<McpTool>,
+    /// Why the connection failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl McpTestResult {
+    /// A failed test with the given reason.
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            status: McpTestStatus::Error,
+            tools: Vec::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    /// Whether the connection succeeded.
+    pub fn is_success(&self) -> bool {
+        self.status == McpTestStatus::Success
+    }
+}
+
+/// Test responses are a bare tool list from older servers and a status
+/// object from newer ones.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum McpTestResponse {
+    Tools(Vec<McpTool>),
+    Result {
+        status: McpTestStatus,
+        #[serde(default)]
+        tools: Vec<McpTool>,
+        #[serde(default, alias = "message")]
+        error: Option<String>,
+    },
+}
+
+impl From<McpTestResponse> for McpTestResult {
+    fn from(response: McpTestResponse) -> Self {
+        match response {
+            McpTestResponse::Tools(tools) => Self {
+                status: McpTestStatus::Success,
+                tools,
+                error: None,
+            },
+            McpTestResponse::Result {
+                status,
+                tools,
+                error,
+            } => Self {
+                status,
+                tools,
+                error,
+            },
+        }
+    }
+}
+
+/// Stage of connecting to an MCP server that may require OAuth.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpConnectEventType {
+    /// The server is being contacted.
+    ConnectionAttempt,
+    /// The server requires OAuth authorization.
+    OauthRequired,
+    /// Open `url` in a browser to authorize.
+    AuthorizationUrl,
+    /// Waiting for the user to finish authorizing.
+    WaitingForAuth,
+    /// Connected; `tools` lists the server's tools.
+    Success,
+    /// The connection failed; `message` says why.
+    Error,
+    /// An event type this client does not know.
+    #[serde(other)]
+    Other,
+}
+
+/// Progress event while connecting to an MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpConnectEvent {
+    /// Stage of the connection.
+    pub event: McpConnectEventType,
+    /// Human-readable progress or error message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Authorization URL to open, for [`McpConnectEventType::AuthorizationUrl`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// OAuth session ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Tools the server offers, for [`McpConnectEventType::Success`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<McpTool>>,
+}
+
 /// Request to run a tool from source code.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct RunToolFromSourceRequest {
@@ -541,7 +787,8 @@ pub struct AppModel {
     pub description: String,
     /// App categories.
     pub categories: Vec<String>,
-    /// App metadata.
+    /// App metadata; see [`AppModel::app_meta`] for a typed view.
+    #[serde(default)]
     pub meta: serde_json::Value,
     /// App logo URL.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -575,6 +822,47 @@ pub struct AppModel {
     pub docs_sections: Option<serde_json::Value>,
 }
 
+/// Composio app metadata.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppMeta {
+    /// Whether the app was defined by the Composio account rather than Composio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_custom_app: Option<bool>,
+    /// Number of actions the app offers.
+    #[serde(rename = "actionsCount", skip_serializing_if = "Option::is_none")]
+    pub actions_count: Option<u32>,
+    /// Number of triggers the app offers.
+    #[serde(rename = "triggersCount", skip_serializing_if = "Option::is_none")]
+    pub triggers_count: Option<u32>,
+    /// Creation timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// Last update timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Additional metadata.
+    #[serde(flatten)]
+    pub additional_properties: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl AppModel {
+    /// Whether the app is enabled for the Composio account configured on the
+    /// Letta server, so its actions can be used.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// The app metadata as [`AppMeta`], or `None` if it is missing or not an object.
+    pub fn app_meta(&self) -> Option<AppMeta> {
+        serde_json::from_value(self.meta.clone()).ok()
+    }
+
+    /// Whether the app needs a connected account before its actions work.
+    pub fn requires_auth(&self) -> bool {
+        !self.no_auth.unwrap_or(false)
+    }
+}
+
 /// Action parameters/response model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionParametersModel {
@@ -593,6 +881,77 @@ pub struct ActionParametersModel {
     pub examples: Option<Vec<serde_json::Value>>,
 }
 
+impl ActionParametersModel {
+    /// The schema's properties as typed parameters, required ones first.
+    pub fn parameters(&self) -> Vec<ActionParameter> {
+        let required = self.required.as_deref().unwrap_or_default();
+        let mut parameters: Vec<ActionParameter> = self
+            .properties
+            .iter()
+            .map(|(name, schema)| {
+                let str_field =
+                    |key: &str| schema.get(key).and_then(|v| v.as_str()).map(String::from);
+                ActionParameter {
+                    name: name.clone(),
+                    param_type: str_field("type"),
+                    title: str_field("title"),
+                    description: str_field("description"),
+                    required: required.contains(name),
+                    default: schema.get("default").cloned(),
+                    enum_values: schema.get("enum").and_then(|v| v.as_array()).cloned(),
+                    examples: schema
+                        .get("examples")
+                        .and_then(|v| v.as_array())
+                        .cloned()
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
+        parameters.sort_by_key(|parameter| !parameter.required);
+        parameters
+    }
+
+    /// The schema as a JSON Schema object, e.g. for `args_json_schema`.
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut schema = serde_json::json!({
+            "type": self.schema_type,
+            "title": self.title,
+            "properties": self.properties,
+        });
+        if let Some(required) = &self.required {
+            schema["required"] = serde_json::json!(required);
+        }
+        schema
+    }
+}
+
+/// A single parameter of a Composio action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionParameter {
+    /// Parameter name.
+    pub name: String,
+    /// JSON Schema type, if the schema gives one.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<String>,
+    /// Display title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Parameter description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the parameter must be given.
+    pub required: bool,
+    /// Default value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// Allowed values.
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<serde_json::Value>>,
+    /// Example values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<serde_json::Value>,
+}
+
 /// Composio action model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionModel {
@@ -628,6 +987,100 @@ pub struct ActionModel {
     pub enabled: Option<bool>,
 }
 
+impl ActionModel {
+    /// Typed parameters of the action.
+    pub fn parameters(&self) -> Vec<ActionParameter> {
+        self.parameters.parameters()
+    }
+
+    /// Whether the action has `tag`, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Client-side filter for Composio apps.
+///
+/// Letta returns every app at once; the filter and page are applied by the
+/// client.
+#[derive(Debug, Clone, Default)]
+pub struct ComposioAppQuery {
+    /// Words that must all appear in the app's name, key or description.
+    pub query: Option<String>,
+    /// Category the app must be in.
+    pub category: Option<String>,
+    /// Only apps enabled for the server's Composio account.
+    pub enabled_only: bool,
+    /// Number of matching apps to skip.
+    pub offset: Option<usize>,
+    /// Maximum number of apps to return.
+    pub limit: Option<usize>,
+}
+
+impl ComposioAppQuery {
+    /// Whether an app matches the filter (ignoring the page).
+    pub fn matches(&self, app: &AppModel) -> bool {
+        (!self.enabled_only || app.is_enabled())
+            && self.category.as_ref().is_none_or(|category| {
+                app.categories
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(category))
+            })
+            && self.query.as_deref().is_none_or(|query| {
+                matches_words(query, [app.name.as_str(), &app.key, &app.description])
+            })
+    }
+}
+
+/// Client-side search for Composio actions.
+///
+/// Letta returns all of an app's actions at once; the search and page are
+/// applied by the client.
+#[derive(Debug, Clone, Default)]
+pub struct ComposioActionQuery {
+    /// Words describing the use case, which must all appear in the action's
+    /// name, display name or description.
+    pub query: Option<String>,
+    /// Tags the action must all have.
+    pub tags: Vec<String>,
+    /// Number of matching actions to skip.
+    pub offset: Option<usize>,
+    /// Maximum number of actions to return.
+    pub limit: Option<usize>,
+}
+
+impl ComposioActionQuery {
+    /// Whether an action matches the search (ignoring the page).
+    pub fn matches(&self, action: &ActionModel) -> bool {
+        self.tags.iter().all(|tag| action.has_tag(tag))
+            && self.query.as_deref().is_none_or(|query| {
+                let display_name = action.display_name.as_deref().unwrap_or_default();
+                matches_words(
+                    query,
+                    [action.name.as_str(), display_name, &action.description],
+                )
+            })
+    }
+}
+
+/// Outcome of adding several Composio actions as tools: the added tools and
+/// the action names that failed.
+pub type ComposioAddReport = BulkReport<Tool>;
+
+/// Whether every whitespace-separated word of `query` appears in one of
+/// `fields`, ignoring case. Underscores in fields count as spaces, so
+/// "send email" matches `GMAIL_SEND_EMAIL`.
+fn matches_words<'a>(query: &str, fields: impl IntoIterator<Item = &'a str>) -> bool {
+    let haystack = fields
+        .into_iter()
+        .map(|field| field.replace('_', " ").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    query
+        .split_whitespace()
+        .all(|word| haystack.contains(&word.to_lowercase()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -749,6 +1202,140 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mcp_server_config_builders() {
+        let config: McpServerConfig =
+            StreamableHttpServerConfig::new("search", "https://example.com/mcp")
+                .bearer_token("secret")
+                .header("X-Team", "core")
+                .into();
+        assert_eq!(config.server_name(), "search");
+        assert_eq!(config.server_type(), McpServerType::StreamableHttp);
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["type"], "streamable_http");
+        assert_eq!(json["auth_header"], "Authorization");
+        assert_eq!(json["auth_token"], "Bearer secret");
+        assert_eq!(json["custom_headers"]["X-Team"], "core");
+
+        let config: McpServerConfig = StdioServerConfig::new("fs", "npx", ["-y", "server-fs"])
+            .env("ROOT", "/tmp")
+            .into();
+        assert_eq!(config.server_type(), McpServerType::Stdio);
+    }
+
+    #[test]
+    fn test_mcp_test_result() {
+        let tools = serde_json::json!([{"name": "echo", "inputSchema": {"type": "object"}}]);
+
+        let result: McpTestResult = serde_json::from_value(tools.clone()).unwrap();
+        assert!(result.is_success());
+        assert_eq!(result.tools[0].name, "echo");
+
+        let result: McpTestResult =
+            serde_json::from_value(serde_json::json!({"status": "success", "tools": tools}))
+                .unwrap();
+        assert_eq!(result.tools.len(), 1);
+
+        let result: McpTestResult = serde_json::from_value(
+            serde_json::json!({"status": "error", "message": "connection refused"}),
+        )
+        .unwrap();
+        assert!(!result.is_success());
+        assert_eq!(result.error.as_deref(), Some("connection refused"));
+    }
+
+    #[test]
+    fn test_composio_app_meta() {
+        let app = |meta: serde_json::Value| -> AppModel {
+            serde_json::from_value(serde_json::json!({
+                "name": "Gmail",
+                "key": "gmail",
+                "appId": "gmail-id",
+                "description": "Email",
+                "categories": ["email"],
+                "meta": meta,
+            }))
+            .unwrap()
+        };
+
+        let typed = app(serde_json::json!({"actionsCount": 23, "is_custom_app": false, "tier": 1}))
+            .app_meta()
+            .unwrap();
+        assert_eq!(typed.actions_count, Some(23));
+        assert_eq!(typed.is_custom_app, Some(false));
+        assert_eq!(typed.additional_properties["tier"], 1);
+
+        assert!(app(serde_json::Value::Null).app_meta().is_none());
+
+        let without_meta: AppModel = serde_json::from_value(serde_json::json!({
+            "name": "Gmail",
+            "key": "gmail",
+            "appId": "gmail-id",
+            "description": "Email",
+            "categories": [],
+        }))
+        .unwrap();
+        assert!(without_meta.meta.is_null());
+    }
+
+    #[test]
+    fn test_composio_action_search() {
+        let action: ActionModel = serde_json::from_value(serde_json::json!({
+            "name": "GMAIL_SEND_EMAIL",
+            "description": "Send an email from the user's Gmail account.",
+            "parameters": {
+                "title": "SendEmailRequest",
+                "type": "object",
+                "properties": {
+                    "body": {"type": "string", "description": "Email body"},
+                    "is_html": {"type": "boolean", "default": false},
+                    "recipient_email": {"type": "string", "examples": ["a@example.com"]}
+                },
+                "required": ["recipient_email", "body"]
+            },
+            "response": {"title": "SendEmailResponse", "type": "object", "properties": {}},
+            "appName": "gmail",
+            "appId": "gmail",
+            "version": "1.0.0",
+            "availableVersions": ["1.0.0"],
+            "tags": ["Email", "important"]
+        }))
+        .unwrap();
+
+        let parameters = action.parameters();
+        assert_eq!(
+            parameters
+                .iter()
+                .map(|p| (p.name.as_str(), p.required))
+                .collect::<Vec<_>>(),
+            vec![
+                ("body", true),
+                ("recipient_email", true),
+                ("is_html", false)
+            ]
+        );
+        assert_eq!(parameters[2].default, Some(serde_json::json!(false)));
+        assert_eq!(parameters[1].examples.len(), 1);
+        assert_eq!(
+            action.parameters.to_json_schema()["required"],
+            serde_json::json!(["recipient_email", "body"])
+        );
+
+        let search = |query: Option<&str>, tags: &[&str]| {
+            ComposioActionQuery {
+                query: query.map(String::from),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            }
+            .matches(&action)
+        };
+        assert!(search(Some("send email"), &[]));
+        assert!(search(Some("Gmail"), &["email"]));
+        assert!(!search(Some("send calendar invite"), &[]));
+        assert!(!search(None, &["email", "calendar"]));
+    }
+
     #[test]
     fn test_mcp_tool() {
         let tool = McpTool {
@@ -0,0 +1,406 @@
+//! Typed views over structured memory blocks.
+//!
+//! A [`Block`]'s value is plain text with a character limit. When a block
+//! holds structured facts, a [`TypedBlock`] keeps the parsed data alongside
+//! the block and re-renders the value in a fixed [`BlockFormat`] whenever the
+//! data changes, checking the limit before anything is sent to the server.
+
+use crate::error::{LettaError, LettaResult};
+use crate::types::memory::Block;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// How structured data is written into a block's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// One `key: value` line per field of a struct or map.
+    KeyValue,
+    /// A Markdown list: `- key: value` for each field of a struct or map, or
+    /// `- value` for each item of a sequence.
+    MarkdownList,
+}
+
+impl BlockFormat {
+    /// Render `data` as a block value.
+    ///
+    /// In the line-based formats, strings are written bare unless they would
+    /// read back as something else (a number, `true`, a padded or multi-line
+    /// string, ...), in which case they are quoted as JSON. Nested values are
+    /// written as compact JSON. Fields are written in the order `serde_json`
+    /// keeps them: alphabetically, unless its `preserve_order` feature is
+    /// enabled.
+    ///
+    /// # Example
+    /// ```
+    /// # use letta::types::BlockFormat;
+    /// #[derive(serde::Serialize)]
+    /// struct Human {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let human = Human { name: "Alice".into(), age: 30 };
+    /// let value = BlockFormat::KeyValue.render(&human).unwrap();
+    /// assert_eq!(value, "age: 30\nname: Alice");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if `data` cannot be serialized,
+    /// or if the format needs a struct or sequence and `data` is not one.
+    pub fn render<T: Serialize>(&self, data: &T) -> LettaResult<String> {
+        let value = serde_json::to_value(data)?;
+        match (self, value) {
+            (Self::Json, value) => Ok(serde_json::to_string_pretty(&value)?),
+            (Self::KeyValue, Value::Object(fields)) => Ok(render_fields(&fields, "")),
+            (Self::MarkdownList, Value::Object(fields)) => Ok(render_fields(&fields, "- ")),
+            (Self::MarkdownList, Value::Array(items)) => Ok(items
+                .iter()
+                .map(|item| format!("- {}", encode_item(item)))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            (Self::KeyValue, _) => Err(LettaError::validation(
+                "the key/value block format needs a struct or map",
+            )),
+            (Self::MarkdownList, _) => Err(LettaError::validation(
+                "the Markdown list block format needs a struct, map or sequence",
+            )),
+        }
+    }
+
+    /// Parse a block value written by [`render`](Self::render).
+    ///
+    /// Blank lines are ignored in the line-based formats.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the value is not in this format
+    /// or does not deserialize into `T`.
+    pub fn parse<T: DeserializeOwned>(&self, value: &str) -> LettaResult<T> {
+        match self {
+            Self::Json => Ok(serde_json::from_str(value)?),
+            Self::KeyValue => {
+                let lines: Vec<&str> = lines(value).collect();
+                Ok(serde_json::from_value(Value::Object(parse_fields(
+                    &lines,
+                )?))?)
+            }
+            Self::MarkdownList => {
+                let items = lines(value)
+                    .map(|line| {
+                        line.strip_prefix("- ")
+                            .or_else(|| (line == "-").then_some(""))
+                            .ok_or_else(|| {
+                                LettaError::validation(format!(
+                                    "expected a Markdown list item, found '{}'",
+                                    line
+                                ))
+                            })
+                    })
+                    .collect::<LettaResult<Vec<&str>>>()?;
+
+                // A struct renders as `key: value` items and a sequence as bare
+                // ones, so try the former first.
+                if let Ok(fields) = parse_fields(&items) {
+                    if let Ok(data) = serde_json::from_value(Value::Object(fields)) {
+                        return Ok(data);
+                    }
+                }
+                let items = items.iter().map(|item| decode_value(item)).collect();
+                Ok(serde_json::from_value(Value::Array(items))?)
+            }
+        }
+    }
+}
+
+/// A memory block whose value is `T`, rendered in a [`BlockFormat`].
+///
+/// # Example
+/// ```
+/// # use letta::types::{Block, BlockFormat, TypedBlock};
+/// #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// struct Preferences {
+///     units: String,
+///     language: String,
+/// }
+///
+/// let preferences = Preferences { units: "metric".into(), language: "en".into() };
+/// let mut block = TypedBlock::new(
+///     Block::new("preferences", "").with_limit(40),
+///     preferences,
+///     BlockFormat::KeyValue,
+/// )
+/// .unwrap();
+/// assert_eq!(block.block().value, "language: en\nunits: metric");
+///
+/// // Changes that would overflow the limit are rejected locally.
+/// let result = block.update(|p| p.language = "en-GB, fr, de, es, it, nl".into());
+/// assert!(result.is_err());
+/// assert_eq!(block.data().language, "en");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedBlock<T> {
+    block: Block,
+    data: T,
+    format: BlockFormat,
+}
+
+impl<T: Serialize + DeserializeOwned> TypedBlock<T> {
+    /// Create a typed block, rendering `data` into `block`'s value.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block to hold the data; its value is replaced
+    /// * `data` - The structured value
+    /// * `format` - How to render the value
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if `data` cannot be rendered in
+    /// `format` or does not fit within the block's limit.
+    pub fn new(block: Block, data: T, format: BlockFormat) -> LettaResult<Self> {
+        let mut typed = Self {
+            block,
+            data,
+            format,
+        };
+        let value = typed.format.render(&typed.data)?;
+        typed.block.check_limit(&value)?;
+        typed.block.value = value;
+        Ok(typed)
+    }
+
+    /// Parse an existing block's value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the value is not in `format` or
+    /// does not deserialize into `T`.
+    pub fn parse(block: Block, format: BlockFormat) -> LettaResult<Self> {
+        let data = format.parse(&block.value)?;
+        Ok(Self {
+            block,
+            data,
+            format,
+        })
+    }
+
+    /// The structured value.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// The block, with its value rendered from the data.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// The format the value is rendered in.
+    pub fn format(&self) -> BlockFormat {
+        self.format
+    }
+
+    /// Replace the data and re-render the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if `data` cannot be rendered or
+    /// does not fit within the block's limit; the block is left unchanged.
+    pub fn set(&mut self, data: T) -> LettaResult<()> {
+        let value = self.format.render(&data)?;
+        self.block.check_limit(&value)?;
+        self.block.value = value;
+        self.data = data;
+        Ok(())
+    }
+
+    /// Change the data in place and re-render the value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [crate::error::LettaError] if the changed data cannot be
+    /// rendered or does not fit within the block's limit; the block is left
+    /// unchanged.
+    pub fn update(&mut self, f: impl FnOnce(&mut T)) -> LettaResult<()>
+    where
+        T: Clone,
+    {
+        let mut data = self.data.clone();
+        f(&mut data);
+        self.set(data)
+    }
+
+    /// Take the block, e.g. to create it on the server.
+    pub fn into_block(self) -> Block {
+        self.block
+    }
+
+    /// Take the structured value.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+fn lines(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+}
+
+fn render_fields(fields: &Map<String, Value>, prefix: &str) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}{}: {}", prefix, encode_key(key), encode_value(value)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_fields(lines: &[&str]) -> LettaResult<Map<String, Value>> {
+    let mut fields = Map::new();
+    for line in lines {
+        let (key, rest) = if line.starts_with('"') {
+            let mut keys = serde_json::Deserializer::from_str(line).into_iter::<String>();
+            let key = keys.next().transpose()?.unwrap_or_default();
+            (key, &line[keys.byte_offset()..])
+        } else {
+            let key = line.split_once(':').map_or(*line, |(key, _)| key);
+            (key.to_string(), &line[key.len()..])
+        };
+        let value = rest.strip_prefix(':').ok_or_else(|| {
+            LettaError::validation(format!("expected a 'key: value' line, found '{}'", line))
+        })?;
+        fields.insert(key, decode_value(value.trim_start()));
+    }
+    Ok(fields)
+}
+
+fn encode_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key == key.trim()
+        && !key.starts_with(['"', '-'])
+        && !key.contains([':', '\n', '\r']);
+    if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+/// A value after a key; strings are bare unless they would decode differently.
+fn encode_value(value: &Value) -> String {
+    match value {
+        Value::String(s)
+            if !s.is_empty()
+                && s == s.trim()
+                && !s.contains(['\n', '\r'])
+                && serde_json::from_str::<Value>(s).is_err() =>
+        {
+            s.clone()
+        }
+        value => value.to_string(),
+    }
+}
+
+/// A sequence item; bare strings must also not look like `key: value`.
+fn encode_item(value: &Value) -> String {
+    match value {
+        Value::String(s) if s.contains(':') || s.starts_with('"') => value.to_string(),
+        value => encode_value(value),
+    }
+}
+
+fn decode_value(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Human {
+        name: String,
+        age: u32,
+        nickname: Option<String>,
+        note: String,
+        #[serde(rename = "favourite: colour")]
+        colour: String,
+        pets: Vec<String>,
+    }
+
+    fn human() -> Human {
+        Human {
+            name: "Alice".to_string(),
+            age: 30,
+            nickname: None,
+            note: "42".to_string(),
+            colour: " green\nish".to_string(),
+            pets: vec!["cat".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_block_format_round_trip() {
+        let human = human();
+        for format in [
+            BlockFormat::Json,
+            BlockFormat::KeyValue,
+            BlockFormat::MarkdownList,
+        ] {
+            let value = format.render(&human).unwrap();
+            assert_eq!(
+                format.parse::<Human>(&value).unwrap(),
+                human,
+                "{:?}",
+                format
+            );
+        }
+
+        assert_eq!(
+            BlockFormat::KeyValue.render(&human).unwrap(),
+            "age: 30\n\"favourite: colour\": \" green\\nish\"\nname: Alice\n\
+             nickname: null\nnote: \"42\"\npets: [\"cat\"]"
+        );
+
+        let facts = vec!["likes tea".to_string(), "lives in: Paris".to_string()];
+        let value = BlockFormat::MarkdownList.render(&facts).unwrap();
+        assert_eq!(value, "- likes tea\n- \"lives in: Paris\"");
+        assert_eq!(
+            BlockFormat::MarkdownList
+                .parse::<Vec<String>>(&value)
+                .unwrap(),
+            facts
+        );
+
+        assert!(BlockFormat::KeyValue.render(&facts).is_err());
+        assert!(BlockFormat::KeyValue.parse::<Human>("name Alice").is_err());
+    }
+
+    #[test]
+    fn test_typed_block_limit() {
+        let block = Block::new("human", "").with_limit(60);
+        assert!(TypedBlock::new(block.clone(), human(), BlockFormat::Json).is_err());
+
+        let facts = vec!["likes tea".to_string()];
+        let mut block = TypedBlock::new(block, facts, BlockFormat::MarkdownList)
+            .unwrap()
+            .into_block();
+        block.value.push_str("\n- plays chess");
+        let mut typed: TypedBlock<Vec<String>> =
+            TypedBlock::parse(block, BlockFormat::MarkdownList).unwrap();
+        assert_eq!(typed.data(), &["likes tea", "plays chess"]);
+
+        let err = typed
+            .update(|facts| facts.push("x".repeat(60)))
+            .unwrap_err();
+        assert!(matches!(err, LettaError::Validation { field: Some(f), .. } if f == "value"));
+        assert_eq!(typed.data().len(), 2);
+        assert_eq!(typed.block().value, "- likes tea\n- plays chess");
+    }
+}
@@ -1,26 +1,342 @@
 //! Voice-related types.
+//!
+//! The voice-beta endpoint speaks the OpenAI chat completions protocol, so
+//! these types mirror OpenAI's request, response and streaming chunk shapes.
 
+use bon::Builder;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-/// Request for voice chat completions.
-///
-/// Note: The exact structure is not well-documented in the API.
-/// This uses a generic JSON value to accommodate any request format.
+use super::MessageToolCall;
+
+/// Role of a chat completion message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatCompletionRole {
+    /// System instructions.
+    System,
+    /// Developer instructions (newer OpenAI replacement for system).
+    Developer,
+    /// End-user input.
+    User,
+    /// Model output.
+    Assistant,
+    /// Tool output.
+    Tool,
+}
+
+/// A single message in a chat completion conversation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionMessage {
+    /// Message role.
+    pub role: ChatCompletionRole,
+    /// Text content of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Optional participant name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool calls requested by the assistant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<MessageToolCall>>,
+    /// ID of the tool call this message responds to (tool role only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatCompletionMessage {
+    /// Create a message with the given role and text content.
+    pub fn new(role: ChatCompletionRole, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: Some(content.into()),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+
+    /// Create a system message.
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(ChatCompletionRole::System, content)
+    }
+
+    /// Create a user message.
+    ///
+    /// # Example
+    /// ```
+    /// # use letta::types::{ChatCompletionMessage, ChatCompletionRole};
+    /// let message = ChatCompletionMessage::user("What's the weather like?");
+    /// assert_eq!(message.role, ChatCompletionRole::User);
+    /// ```
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(ChatCompletionRole::User, content)
+    }
+
+    /// Create an assistant message.
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(ChatCompletionRole::Assistant, content)
+    }
+}
+
+/// Request for voice chat completions (OpenAI chat completions format).
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 pub struct VoiceChatCompletionRequest {
-    /// Request data as JSON.
-    #[serde(flatten)]
-    pub data: Value,
+    /// Model identifier. The agent's own LLM configuration is used by the
+    /// server, but the field is required by the protocol.
+    #[builder(into)]
+    pub model: String,
+    /// Conversation messages; the latest user message is sent to the agent.
+    pub messages: Vec<ChatCompletionMessage>,
+    /// Whether to stream the response as server-sent events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Maximum number of tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completion_tokens: Option<u32>,
+    /// End-user identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    pub user: Option<String>,
+}
+
+/// Token usage reported by a chat completion.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionUsage {
+    /// Tokens in the prompt.
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    /// Tokens in the completion.
+    #[serde(default)]
+    pub completion_tokens: u32,
+    /// Total tokens used.
+    #[serde(default)]
+    pub total_tokens: u32,
+}
+
+/// A single choice in a chat completion response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChoice {
+    /// Index of this choice.
+    #[serde(default)]
+    pub index: u32,
+    /// The generated message.
+    pub message: ChatCompletionMessage,
+    /// Why generation stopped (e.g. "stop", "tool_calls").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
 }
 
 /// Response from voice chat completions.
-///
-/// Note: The exact structure is not well-documented in the API.
-/// This uses a generic JSON value to accommodate any response format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceChatCompletionResponse {
-    /// Response data as JSON.
-    #[serde(flatten)]
-    pub data: Value,
+    /// Completion ID.
+    pub id: String,
+    /// Object type (always "chat.completion").
+    #[serde(default)]
+    pub object: String,
+    /// Unix timestamp of creation.
+    #[serde(default)]
+    pub created: i64,
+    /// Model that produced the completion.
+    #[serde(default)]
+    pub model: String,
+    /// Generated choices.
+    pub choices: Vec<ChatCompletionChoice>,
+    /// Token usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ChatCompletionUsage>,
+}
+
+impl VoiceChatCompletionResponse {
+    /// Text content of the first choice, if any.
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.message.content.as_deref())
+    }
+}
+
+/// Partial function call in a streamed tool call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionFunctionDelta {
+    /// Function name (usually only in the first fragment).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Fragment of the JSON-encoded arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+}
+
+/// Partial tool call in a streamed chunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionToolCallDelta {
+    /// Index of the tool call being built.
+    #[serde(default)]
+    pub index: u32,
+    /// Tool call ID (usually only in the first fragment).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Tool type (always "function").
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub tool_type: Option<String>,
+    /// Function call fragment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<ChatCompletionFunctionDelta>,
+}
+
+/// Incremental message content in a streamed chunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatCompletionDelta {
+    /// Role (usually only in the first chunk).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<ChatCompletionRole>,
+    /// Content fragment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Tool call fragments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ChatCompletionToolCallDelta>>,
+}
+
+/// A single choice in a streamed chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunkChoice {
+    /// Index of this choice.
+    #[serde(default)]
+    pub index: u32,
+    /// Incremental content.
+    #[serde(default)]
+    pub delta: ChatCompletionDelta,
+    /// Why generation stopped; set on the final chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finish_reason: Option<String>,
+}
+
+/// A chunk of a streamed voice chat completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// Completion ID (shared by all chunks of one completion).
+    pub id: String,
+    /// Object type (always "chat.completion.chunk").
+    #[serde(default)]
+    pub object: String,
+    /// Unix timestamp of creation.
+    #[serde(default)]
+    pub created: i64,
+    /// Model that produced the completion.
+    #[serde(default)]
+    pub model: String,
+    /// Incremental choices.
+    #[serde(default)]
+    pub choices: Vec<ChatCompletionChunkChoice>,
+    /// Token usage, typically only present on the final chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ChatCompletionUsage>,
+}
+
+impl ChatCompletionChunk {
+    /// Content fragment of the first choice, if any.
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .and_then(|choice| choice.delta.content.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_request_serialization() {
+        let request = VoiceChatCompletionRequest::builder()
+            .model("gpt-4o-mini")
+            .messages(vec![
+                ChatCompletionMessage::system("Be brief."),
+                ChatCompletionMessage::user("Hello!"),
+            ])
+            .stream(true)
+            .build();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["model"], "gpt-4o-mini");
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["messages"][1]["content"], "Hello!");
+        assert_eq!(json["stream"], true);
+        assert!(json.get("temperature").is_none());
+        assert!(json["messages"][1].get("tool_calls").is_none());
+    }
+
+    #[test]
+    fn test_chunk_deserialization() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1700000000,
+            "model": "gpt-4o-mini",
+            "choices": [{
+                "index": 0,
+                "delta": {"role": "assistant", "content": "Hi"},
+                "finish_reason": null
+            }]
+        }"#;
+
+        let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+        assert_eq!(chunk.content(), Some("Hi"));
+        assert_eq!(
+            chunk.choices[0].delta.role,
+            Some(ChatCompletionRole::Assistant)
+        );
+        assert!(chunk.choices[0].finish_reason.is_none());
+    }
+
+    #[test]
+    fn test_chunk_tool_call_delta() {
+        let json = r#"{
+            "id": "chatcmpl-123",
+            "choices": [{
+                "index": 0,
+                "delta": {
+                    "tool_calls": [{
+                        "index": 0,
+                        "id": "call_abc",
+                        "type": "function",
+                        "function": {"name": "send_message", "arguments": "{\"mes"}
+                    }]
+                }
+            }]
+        }"#;
+
+        let chunk: ChatCompletionChunk = serde_json::from_str(json).unwrap();
+        let tool_call = &chunk.choices[0].delta.tool_calls.as_ref().unwrap()[0];
+        assert_eq!(tool_call.id.as_deref(), Some("call_abc"));
+        assert_eq!(
+            tool_call.function.as_ref().unwrap().name.as_deref(),
+            Some("send_message")
+        );
+        assert!(chunk.content().is_none());
+    }
+
+    #[test]
+    fn test_response_deserialization() {
+        let json = r#"{
+            "id": "chatcmpl-456",
+            "object": "chat.completion",
+            "created": 1700000000,
+            "model": "gpt-4o-mini",
+            "choices": [{
+                "index": 0,
+                "message": {"role": "assistant", "content": "Hello there"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 3, "total_tokens": 13}
+        }"#;
+
+        let response: VoiceChatCompletionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.content(), Some("Hello there"));
+        assert_eq!(response.usage.unwrap().total_tokens, 13);
+    }
 }
@@ -40,6 +40,46 @@ pub fn join_paths(base: &str, segments: &[&str]) -> String {
     url
 }
 
+/// Percent-encode a value for use as a single URL path segment.
+///
+/// Everything except unreserved characters (`A-Z a-z 0-9 - . _ ~`) is
+/// encoded, so names containing `/`, `?`, `#` or spaces stay one segment.
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Incremental 64-bit FNV-1a hasher, used for stable content hashes.
+///
+/// Unlike [`std::hash::DefaultHasher`], the output is the same across Rust
+/// versions and platforms, so hashes can be stored and compared later.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// The hash as 16 hex digits.
+    pub(crate) fn finish_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +118,10 @@ mod tests {
         );
         assert_eq!(join_paths("http://example.com", &[]), "http://example.com");
     }
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("my-server_1.0~"), "my-server_1.0~");
+        assert_eq!(encode_path_segment("a/b?c d#é"), "a%2Fb%3Fc%20d%23%C3%A9");
+    }
 }
@@ -0,0 +1,123 @@
+//! Integration tests for the agent-scoped API handle.
+
+use letta::client::ClientBuilder;
+use letta::error::LettaResult;
+use letta::types::*;
+use letta::LettaClient;
+use serial_test::serial;
+
+/// Create a test client for the local server.
+fn create_test_client() -> LettaResult<LettaClient> {
+    ClientBuilder::new()
+        .base_url("http://localhost:8283")
+        .build()
+}
+
+/// Create a test agent.
+async fn create_test_agent(client: &LettaClient, name: &str) -> LettaResult<AgentState> {
+    let request = CreateAgentRequest::builder()
+        .name(name)
+        .model("letta/letta-free")
+        .embedding("letta/letta-free")
+        .memory_block(Block::human("The human's name is Test User."))
+        .memory_block(Block::persona("I am a helpful test assistant."))
+        .build();
+
+    client.agents().create(request).await
+}
+
+#[tokio::test]
+#[serial]
+async fn test_agent_handle_blocks() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent = create_test_agent(&client, "Handle Blocks Agent").await?;
+    let handle = client.agent(&agent.id);
+
+    let blocks = handle.blocks().list().await?;
+    assert!(blocks.iter().any(|b| b.label == "human"));
+
+    // Attach a standalone block and verify it shows up
+    let block = client
+        .blocks()
+        .create(CreateBlockRequest {
+            value: "Shared notes".to_string(),
+            label: "shared_notes".to_string(),
+            ..Default::default()
+        })
+        .await?;
+    let block_id = block.id.clone().unwrap();
+
+    handle.blocks().attach(&block_id).await?;
+    let attached = handle.blocks().get("shared_notes").await?;
+    assert_eq!(attached.value, "Shared notes");
+
+    handle.blocks().detach(&block_id).await?;
+    let blocks = handle.blocks().list().await?;
+    assert!(!blocks.iter().any(|b| b.label == "shared_notes"));
+
+    client.blocks().delete(&block_id).await?;
+    client.agents().delete(&agent.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_agent_handle_identities() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent = create_test_agent(&client, "Handle Identities Agent").await?;
+    let handle = client.agent(&agent.id);
+
+    let identity = client
+        .identities()
+        .create(CreateIdentityRequest {
+            identifier_key: format!("handle-test-{}", uuid::Uuid::new_v4()),
+            name: "Handle Test User".to_string(),
+            identity_type: IdentityType::User,
+            project_id: None,
+            agent_ids: None,
+            block_ids: None,
+            properties: None,
+        })
+        .await?;
+
+    let updated = handle.identities().attach(&identity.id).await?;
+    assert!(updated.agent_ids.unwrap_or_default().contains(&agent.id));
+
+    let identities = handle.identities().list().await?;
+    assert!(identities.iter().any(|i| i.id == identity.id));
+
+    let updated = handle.identities().detach(&identity.id).await?;
+    assert!(!updated.agent_ids.unwrap_or_default().contains(&agent.id));
+
+    client.identities().delete(&identity.id).await?;
+    client.agents().delete(&agent.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_agent_handle_tools_and_groups() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent = create_test_agent(&client, "Handle Tools Agent").await?;
+    let handle = client.agent(&agent.id);
+
+    let tools = handle.tools().list().await?;
+    println!("Agent has {} tools attached", tools.len());
+
+    if let Some(tool) = tools.first() {
+        let tool_id = tool.id.clone().unwrap();
+        handle.tools().detach(&tool_id).await?;
+        let remaining = handle.tools().list().await?;
+        assert_eq!(remaining.len(), tools.len() - 1);
+        handle.tools().attach(&tool_id).await?;
+    }
+
+    let groups = handle.groups().list().await?;
+    assert!(groups.is_empty());
+
+    let sources = handle.sources().list().await?;
+    println!("Agent has {} sources attached", sources.len());
+
+    client.agents().delete(&agent.id).await?;
+    Ok(())
+}
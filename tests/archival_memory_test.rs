@@ -2,12 +2,17 @@
 
 use letta::client::ClientBuilder;
 use letta::error::LettaResult;
+use letta::memory::{ArchivalExport, ArchivalImport, ArchivalIngest, Chunking};
 use letta::types::agent::CreateAgentRequest;
+use letta::types::common::Metadata;
 use letta::types::memory::{
     ArchivalMemoryQueryParams, Block, CreateArchivalMemoryRequest, UpdateArchivalMemoryRequest,
 };
 use letta::{LettaClient, LettaId};
 use serial_test::serial;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 /// Create a test client for the local server.
 fn create_test_client() -> LettaResult<LettaClient> {
@@ -70,9 +75,8 @@ async fn test_create_archival_memory() -> LettaResult<()> {
     let agent_id = create_test_agent(&client).await?;
 
     // Create archival memory
-    let request = CreateArchivalMemoryRequest {
-        text: "The user mentioned they love hiking in the mountains.".to_string(),
-    };
+    let request =
+        CreateArchivalMemoryRequest::new("The user mentioned they love hiking in the mountains.");
 
     let passages = client
         .memory()
@@ -107,9 +111,7 @@ async fn test_list_archival_memory() -> LettaResult<()> {
     ];
 
     for text in &memories {
-        let request = CreateArchivalMemoryRequest {
-            text: text.to_string(),
-        };
+        let request = CreateArchivalMemoryRequest::new(*text);
         client
             .memory()
             .create_archival_memory(&agent_id, request)
@@ -167,9 +169,7 @@ async fn test_update_archival_memory() -> LettaResult<()> {
     ];
 
     for text in &memories {
-        let request = CreateArchivalMemoryRequest {
-            text: text.to_string(),
-        };
+        let request = CreateArchivalMemoryRequest::new(*text);
         client
             .memory()
             .create_archival_memory(&agent_id, request)
@@ -194,30 +194,17 @@ async fn test_update_archival_memory() -> LettaResult<()> {
         .memory()
         .create_archival_memory(
             &agent_id,
-            CreateArchivalMemoryRequest {
-                text: "User's new favorite color is red".to_string(),
-            },
+            CreateArchivalMemoryRequest::new("User's new favorite color is red"),
         )
         .await?;
 
     // Unlike what the spec says, id, text, embedding, and embedding_config are all required.
-    let test_request = UpdateArchivalMemoryRequest {
-        id: passage_id.clone(),
-        text: new_passage[0].text.clone(),
-        created_by_id: None,
-        last_updated_by_id: None,
-        created_at: None,
-        updated_at: None,
-        is_deleted: None,
-        agent_id: None,
-        embedding: new_passage[0].embedding.clone().unwrap(),
-        embedding_config: new_passage[0].embedding_config.clone().unwrap(),
-        source_id: None,
-        file_id: None,
-        file_name: None,
-        metadata: None,
-        organization_id: None,
-    };
+    let test_request = UpdateArchivalMemoryRequest::new(
+        passage_id.clone(),
+        new_passage[0].text.clone(),
+        new_passage[0].embedding.clone().unwrap(),
+        new_passage[0].embedding_config.clone().unwrap(),
+    );
 
     // Ensure it serializes correctly
     let json = serde_json::to_string(&test_request)?;
@@ -244,9 +231,7 @@ async fn test_delete_archival_memory() -> LettaResult<()> {
     let agent_id = create_test_agent(&client).await?;
 
     // Create archival memory
-    let request = CreateArchivalMemoryRequest {
-        text: "Temporary memory to be deleted.".to_string(),
-    };
+    let request = CreateArchivalMemoryRequest::new("Temporary memory to be deleted.");
 
     let passages = client
         .memory()
@@ -283,9 +268,7 @@ async fn test_archival_memory_pagination() -> LettaResult<()> {
 
     // Create multiple archival memories
     for i in 0..5 {
-        let request = CreateArchivalMemoryRequest {
-            text: format!("Memory number {}", i),
-        };
+        let request = CreateArchivalMemoryRequest::new(format!("Memory number {}", i));
         client
             .memory()
             .create_archival_memory(&agent_id, request)
@@ -309,3 +292,225 @@ async fn test_archival_memory_pagination() -> LettaResult<()> {
     client.agents().delete(&agent_id).await?;
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+async fn test_archival_bulk_ingest() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent_id = create_test_agent(&client).await?;
+
+    let long_note = "The user keeps a garden with tomatoes, basil and peppers. ".repeat(10);
+    let notes = vec![
+        "The user's favorite color is green.".to_string(),
+        "   ".to_string(),
+        long_note,
+    ];
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let report = ArchivalIngest::new()
+        .chunking(Chunking::new(200, 40))
+        .concurrency(2)
+        .on_progress(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .run(&client, &agent_id, notes)
+        .await;
+
+    assert!(report.is_complete(), "failures: {:?}", report.failed);
+    // One passage for the short note, several for the chunked one.
+    assert!(report.passage_ids.len() > 2);
+    assert!(calls.load(Ordering::SeqCst) > 2);
+
+    // Clean up
+    client.agents().delete(&agent_id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_archival_export_import() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let source_id = create_test_agent(&client).await?;
+    let target_id = create_test_agent(&client).await?;
+
+    for text in ["The user owns a cat.", "The user works nights."] {
+        let request = CreateArchivalMemoryRequest::new(text);
+        client
+            .memory()
+            .create_archival_memory(&source_id, request)
+            .await?;
+    }
+
+    let mut jsonl = Vec::new();
+    let count = ArchivalExport::new()
+        .write(&client, &source_id, &mut jsonl)
+        .await?;
+    assert_eq!(count, 2);
+    assert_eq!(jsonl.iter().filter(|&&b| b == b'\n').count(), 2);
+
+    let report = ArchivalImport::new()
+        .read(&client, &target_id, jsonl.as_slice())
+        .await?;
+    assert!(report.is_complete());
+    assert_eq!((report.passage_ids.len(), report.skipped), (2, 0));
+
+    // A second import finds every text already present.
+    let report = ArchivalImport::new()
+        .read(&client, &target_id, jsonl.as_slice())
+        .await?;
+    assert_eq!((report.passage_ids.len(), report.skipped), (0, 2));
+
+    // Clean up
+    client.agents().delete(&source_id).await?;
+    client.agents().delete(&target_id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archival_import_reports_blank_text() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let agent_id = "agent-00000000-0000-0000-0000-000000000001";
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/agents/{}/archival-memory", agent_id)))
+        .and(body_partial_json(
+            serde_json::json!({"text": "The user owns a cat."}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "id": "passage-00000000-0000-0000-0000-000000000001",
+                "agent_id": agent_id,
+                "text": "The user owns a cat.",
+            }])),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let jsonl = "{\"text\":\"The user owns a cat.\"}\n\n{\"text\":\"  \"}\n";
+    let report = ArchivalImport::new()
+        .dedup(false)
+        .read(
+            &client,
+            &LettaId::from_str(agent_id).unwrap(),
+            jsonl.as_bytes(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.passage_ids.len(), 1);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].line, 3);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_archival_memory_tags() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent_id = create_test_agent(&client).await?;
+
+    let mut metadata = Metadata::new();
+    metadata.insert("source".to_string(), serde_json::json!("chat"));
+    let requests = [
+        CreateArchivalMemoryRequest::new("The user drinks green tea.")
+            .tags(["preferences", "drinks"])
+            .metadata(metadata),
+        CreateArchivalMemoryRequest::new("The user is allergic to peanuts.").tags(["health"]),
+    ];
+    for request in requests {
+        client
+            .memory()
+            .create_archival_memory(&agent_id, request)
+            .await?;
+    }
+
+    let params = ArchivalMemoryQueryParams {
+        tags: Some(vec!["drinks".to_string()]),
+        ..Default::default()
+    };
+    let passages = client
+        .memory()
+        .list_archival_memory(&agent_id, Some(params))
+        .await?;
+    assert_eq!(passages.len(), 1);
+    assert!(passages[0].text.contains("green tea"));
+
+    let params = ArchivalMemoryQueryParams {
+        metadata_keys: Some(vec!["source".to_string()]),
+        ..Default::default()
+    };
+    let passages = client
+        .memory()
+        .list_archival_memory(&agent_id, Some(params))
+        .await?;
+    assert_eq!(passages.len(), 1);
+
+    // Clean up
+    client.agents().delete(&agent_id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archival_query_paginated_filters_across_pages() {
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let agent_id = "agent-00000000-0000-0000-0000-000000000001";
+    let passage = |n: u32, tags: &[&str]| {
+        serde_json::json!({
+            "id": format!("passage-00000000-0000-0000-0000-{:012}", n),
+            "agent_id": agent_id,
+            "text": format!("Passage {}", n),
+            "tags": tags,
+        })
+    };
+    let archival_path = format!("/v1/agents/{}/archival-memory", agent_id);
+
+    let mock_server = MockServer::start().await;
+    // The first page has no tagged passages
+    Mock::given(method("GET"))
+        .and(path(archival_path.as_str()))
+        .and(query_param_is_missing("after"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(vec![passage(1, &[]), passage(2, &["other"])]),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(archival_path.as_str()))
+        .and(query_param(
+            "after",
+            "passage-00000000-0000-0000-0000-000000000002",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![passage(3, &["meeting"])]))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let passages = client
+        .memory()
+        .archival_query_paginated(
+            &LettaId::from_str(agent_id).unwrap(),
+            Some(ArchivalMemoryQueryParams {
+                tags: Some(vec!["meeting".to_string()]),
+                limit: Some(2),
+                ..Default::default()
+            }),
+        )
+        .collect()
+        .await
+        .unwrap();
+
+    assert_eq!(passages.len(), 1);
+    assert_eq!(passages[0].text, "Passage 3");
+}
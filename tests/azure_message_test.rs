@@ -58,6 +58,7 @@ async fn test_azure_message_creation() {
                     LettaMessageUnion::HiddenReasoningMessage(_) => "HiddenReasoning",
                     LettaMessageUnion::ToolCallMessage(_) => "ToolCall",
                     LettaMessageUnion::ToolReturnMessage(_) => "ToolReturn",
+                    LettaMessageUnion::ApprovalRequestMessage(_) => "ApprovalRequest",
                 };
                 println!("  Message {}: Type={}", i, type_str);
             }
@@ -1,8 +1,11 @@
 //! Integration tests for blocks API endpoints.
 
 use letta::client::ClientBuilder;
-use letta::error::LettaResult;
-use letta::types::{Block, CreateBlockRequest, ListBlocksParams, Metadata, UpdateBlockRequest};
+use letta::error::{LettaError, LettaResult};
+use letta::types::{
+    Block, BlockFormat, BlockPrecondition, CreateBlockRequest, ListBlocksParams, Metadata,
+    TypedBlock, UpdateBlockRequest,
+};
 use letta::{LettaClient, LettaId};
 use serial_test::serial;
 use std::collections::HashMap;
@@ -303,3 +306,150 @@ async fn test_readonly_and_template_blocks() -> LettaResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+async fn test_conditional_block_update() -> LettaResult<()> {
+    let client = create_test_client()?;
+
+    let block = create_test_block(&client, "test_conditional").await?;
+    let block_id = block.id.as_ref().unwrap();
+    let precondition = BlockPrecondition::of(&block);
+
+    // Someone else edits the block after we read it
+    let request = UpdateBlockRequest {
+        value: Some("Edited elsewhere".to_string()),
+        ..Default::default()
+    };
+    client.blocks().update(block_id, request).await?;
+
+    // Our stale update is rejected with the current block
+    let request = UpdateBlockRequest {
+        value: Some("Stale edit".to_string()),
+        ..Default::default()
+    };
+    match client
+        .blocks()
+        .update_if(block_id, &precondition, request)
+        .await
+    {
+        Err(LettaError::Conflict {
+            current: Some(current),
+            ..
+        }) => assert_eq!(current.value, "Edited elsewhere"),
+        other => panic!("Expected a conflict, got {:?}", other),
+    }
+
+    // modify builds on the current value
+    let modified = client
+        .blocks()
+        .modify(block_id, |value| format!("{} and here", value))
+        .await?;
+    assert_eq!(modified.value, "Edited elsewhere and here");
+
+    client.blocks().delete(block_id).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_typed_block() -> LettaResult<()> {
+    let client = create_test_client()?;
+
+    let facts = vec!["likes tea".to_string()];
+    let typed = TypedBlock::new(
+        Block::new("test_typed", "").with_limit(100),
+        facts,
+        BlockFormat::MarkdownList,
+    )?;
+    let request = CreateBlockRequest {
+        label: typed.block().label.clone(),
+        value: typed.block().value.clone(),
+        limit: typed.block().limit,
+        ..Default::default()
+    };
+    let block = client.blocks().create(request).await?;
+    let block_id = block.id.as_ref().unwrap();
+
+    let typed: TypedBlock<Vec<String>> = client
+        .blocks()
+        .modify_typed(
+            block_id,
+            BlockFormat::MarkdownList,
+            |facts: &mut Vec<String>| facts.push("plays chess".to_string()),
+        )
+        .await?;
+    assert_eq!(typed.data(), &["likes tea", "plays chess"]);
+    assert_eq!(typed.block().value, "- likes tea\n- plays chess");
+
+    // Values over the limit are rejected before reaching the server
+    let result = client
+        .blocks()
+        .modify_typed(
+            block_id,
+            BlockFormat::MarkdownList,
+            |facts: &mut Vec<String>| facts.push("x".repeat(100)),
+        )
+        .await;
+    assert!(matches!(result, Err(LettaError::Validation { .. })));
+
+    client.blocks().delete(block_id).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_modify_retries_on_conflict() {
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let block_id = "block-00000000-0000-0000-0000-000000000001";
+    let block = |value: &str| {
+        serde_json::json!({
+            "id": block_id,
+            "label": "human",
+            "value": value,
+            "limit": 100,
+        })
+    };
+    let block_path = format!("/v1/blocks/{}", block_id);
+
+    let mock_server = MockServer::start().await;
+    // The first read sees "a"; every later read sees the concurrent edit "b"
+    Mock::given(method("GET"))
+        .and(path(block_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(block("a")))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(block_path.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(block("b")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(block_path.as_str()))
+        .and(body_partial_json(serde_json::json!({"value": "b!"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(block("b!")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let mut seen = Vec::new();
+    let modified = client
+        .blocks()
+        .modify(&LettaId::from_str(block_id).unwrap(), |value| {
+            seen.push(value.to_string());
+            format!("{}!", value)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(seen, ["a", "b"]);
+    assert_eq!(modified.value, "b!");
+}
@@ -197,6 +197,7 @@ async fn test_cloud_message_operations() {
                     LettaMessageUnion::HiddenReasoningMessage(_) => "HiddenReasoning",
                     LettaMessageUnion::ToolCallMessage(_) => "ToolCall",
                     LettaMessageUnion::ToolReturnMessage(_) => "ToolReturn",
+                    LettaMessageUnion::ApprovalRequestMessage(_) => "ApprovalRequest",
                 };
                 println!("  Message {}: Type={}", i + 1, type_str);
             }
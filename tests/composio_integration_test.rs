@@ -1,6 +1,7 @@
 //! Integration tests for Composio endpoints.
 
 use letta::error::LettaResult;
+use letta::types::{ComposioActionQuery, ComposioAppQuery};
 use letta::LettaClient;
 use serial_test::serial;
 
@@ -76,3 +77,55 @@ async fn test_add_composio_tool() -> LettaResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+#[ignore = "ensure COMPOSIO_API_KEY is set in your server environment before running this test"]
+async fn test_search_and_bulk_add_composio_actions() -> LettaResult<()> {
+    let client = setup_test_client()?;
+
+    let apps = client
+        .tools()
+        .search_composio_apps(&ComposioAppQuery {
+            query: Some("github".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        })
+        .await?;
+    let Some(app) = apps.first() else {
+        println!("GitHub app not available");
+        return Ok(());
+    };
+
+    let actions = client
+        .tools()
+        .search_composio_actions(
+            &app.key,
+            &ComposioActionQuery {
+                query: Some("star repository".to_string()),
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await?;
+    assert!(actions.len() <= 2);
+    for action in &actions {
+        println!(
+            "{}: {} parameter(s)",
+            action.name,
+            action.parameters().len()
+        );
+    }
+
+    let names: Vec<&str> = actions.iter().map(|a| a.name.as_str()).collect();
+    let report = client.tools().add_composio_tools(&names).await;
+    assert!(report.failed.is_empty(), "{:?}", report.failed);
+
+    for tool in report.succeeded {
+        if let Some(id) = tool.id {
+            client.tools().delete(&id).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,10 +2,14 @@
 
 use letta::client::ClientBuilder;
 use letta::error::LettaResult;
+use letta::memory::{BlockHistory, InMemoryStore};
 use letta::types::agent::CreateAgentRequest;
-use letta::types::memory::{Block, UpdateMemoryBlockRequest};
+use letta::types::memory::{
+    Block, CreateBlockRequest, SummarizationPolicy, UpdateMemoryBlockRequest,
+};
 use letta::{LettaClient, LettaId};
 use serial_test::serial;
+use std::str::FromStr;
 
 /// Create a test client for the local server.
 fn create_test_client() -> LettaResult<LettaClient> {
@@ -196,3 +200,123 @@ async fn test_attach_detach_memory_block() -> LettaResult<()> {
     client.agents().delete(&agent_id).await?;
     Ok(())
 }
+
+#[tokio::test]
+#[serial]
+async fn test_block_history_restore() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent_id = create_test_agent(&client).await?;
+    let history = BlockHistory::new(InMemoryStore::new());
+
+    let recorded = history.snapshot_agent(&client, &agent_id).await?;
+    let human = recorded.iter().find(|s| s.label == "human").unwrap();
+    let block_id = human.block_id.clone();
+
+    // The agent's block changes behind our back
+    let update_request = UpdateMemoryBlockRequest {
+        value: Some("The human's name is Someone Else.".to_string()),
+        ..Default::default()
+    };
+    client
+        .memory()
+        .update_core_memory_block(&agent_id, "human", update_request)
+        .await?;
+
+    let recorded = history.snapshot_agent(&client, &agent_id).await?;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].version, 2);
+    let diff = history.diff(&block_id, 1, 2)?;
+    assert_eq!(diff.added(), 1);
+    assert_eq!(diff.removed(), 1);
+
+    let restored = history.restore(&client, &block_id, 1).await?;
+    assert_eq!(restored.value, "The human's name is Test User.");
+    assert_eq!(history.latest(&block_id)?.unwrap().version, 3);
+
+    // Clean up
+    client.agents().delete(&agent_id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_shared_block_bulk_attach() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent_ids = vec![
+        create_test_agent(&client).await?,
+        create_test_agent(&client).await?,
+    ];
+
+    let request = CreateBlockRequest {
+        label: "shared_policy".to_string(),
+        value: "Always be polite.".to_string(),
+        ..Default::default()
+    };
+    let block = client.blocks().create(request).await?;
+    let block_id = block.id.clone().unwrap();
+
+    let report = client
+        .blocks()
+        .attach_to_agents(&block_id, agent_ids.clone())
+        .await?;
+    assert!(report.is_complete(), "{:?}", report.failed);
+    assert_eq!(report.succeeded, agent_ids);
+
+    let attached = client.blocks().list_agents(&block_id).await?;
+    for agent_id in &agent_ids {
+        assert!(attached.iter().any(|agent| &agent.id == agent_id));
+    }
+
+    // A missing agent fails without stopping the others
+    let missing = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000").unwrap();
+    let mut targets = agent_ids.clone();
+    targets.insert(0, missing.clone());
+    let report = client
+        .blocks()
+        .detach_from_agents(&block_id, targets)
+        .await?;
+    assert_eq!(report.succeeded, agent_ids);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].item, missing.to_string());
+    assert!(client.blocks().list_agents(&block_id).await?.is_empty());
+
+    // Clean up
+    client.blocks().delete(&block_id).await?;
+    for agent_id in &agent_ids {
+        client.agents().delete(agent_id).await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_context_usage_and_recall_memory() -> LettaResult<()> {
+    let client = create_test_client()?;
+    let agent_id = create_test_agent(&client).await?;
+
+    let usage = client.agents().context_usage(&agent_id).await?;
+    assert!(usage.context_window > 0);
+    assert!(usage.total_tokens > 0);
+    assert!(usage.fraction() > 0.0 && usage.fraction() < 1.0);
+
+    // A fresh agent is nowhere near the threshold.
+    let outcome = client
+        .agents()
+        .apply_summarization_policy(&agent_id, &SummarizationPolicy::default())
+        .await?;
+    assert!(!outcome.summarized());
+
+    let summary = client
+        .memory()
+        .get_recall_memory_summary(&agent_id, 10)
+        .await?;
+    assert!(summary.core_memory.is_some());
+    assert!(summary
+        .recall_memory
+        .iter()
+        .all(|entry| entry.agent_id == agent_id));
+
+    // Clean up
+    client.agents().delete(&agent_id).await?;
+    Ok(())
+}
@@ -322,3 +322,68 @@ async fn test_agent_list_groups() {
     let _ = client.agents().delete(&agent.id).await;
     let _ = client.agents().delete(&agent2.id).await;
 }
+
+#[tokio::test]
+async fn test_sleeptime_agent_management() {
+    let client = get_test_client();
+
+    let agent = client
+        .agents()
+        .create(
+            CreateAgentRequest::builder()
+                .name("Sleeptime Test Agent")
+                .model("letta/letta-free")
+                .embedding("letta/letta-free")
+                .memory_block(Block::human("The user's name is Ada."))
+                .enable_sleeptime(true)
+                .build(),
+        )
+        .await
+        .expect("Failed to create agent");
+
+    let group = client
+        .sleeptime()
+        .get_group(&agent.id)
+        .await
+        .expect("Failed to get sleeptime group");
+    assert!(group.is_sleeptime());
+
+    let sleeptime_agent_id = client
+        .sleeptime()
+        .get_agent(&agent.id)
+        .await
+        .expect("Failed to get sleeptime agent");
+    assert_ne!(sleeptime_agent_id, agent.id);
+
+    if group.manager_type == ManagerType::Sleeptime {
+        let updated = client
+            .sleeptime()
+            .update_frequency(&agent.id, 3)
+            .await
+            .expect("Failed to update sleeptime frequency");
+        assert_eq!(updated.sleeptime_agent_frequency, Some(3));
+    }
+    assert!(client
+        .sleeptime()
+        .update_frequency(&agent.id, 0)
+        .await
+        .is_err());
+
+    let activity = client
+        .sleeptime()
+        .activity(&agent.id, 5)
+        .await
+        .expect("Failed to get sleeptime activity");
+    assert_eq!(activity.sleeptime_agent_id, sleeptime_agent_id);
+    assert!(activity.runs.len() <= 5);
+    println!(
+        "Sleeptime agent {} ran {} time(s), modified {:?}",
+        sleeptime_agent_id,
+        activity.runs.len(),
+        activity.modified_labels()
+    );
+
+    // Clean up
+    let _ = client.agents().delete(&sleeptime_agent_id).await;
+    let _ = client.agents().delete(&agent.id).await;
+}
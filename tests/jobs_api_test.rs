@@ -14,7 +14,7 @@ async fn test_list_jobs() {
     let client = get_test_client();
 
     // List jobs (might be empty)
-    let result = client.jobs().list(None, None, None).await;
+    let result = client.jobs().list(None).await;
 
     let jobs = result.expect("Failed to list jobs");
 
@@ -84,7 +84,10 @@ async fn test_list_jobs_with_filters() {
     // Test listing with status filter
     let running_jobs = client
         .jobs()
-        .list(Some(JobStatus::Running), None, None)
+        .list(Some(ListJobsParams {
+            statuses: Some(vec![JobStatus::Running]),
+            ..Default::default()
+        }))
         .await
         .expect("Failed to list running jobs");
 
@@ -96,10 +99,117 @@ async fn test_list_jobs_with_filters() {
     // Test listing with limit
     let limited_jobs = client
         .jobs()
-        .list(None, Some(5), None)
+        .list(Some(ListJobsParams {
+            limit: Some(5),
+            ..Default::default()
+        }))
         .await
         .expect("Failed to list jobs with limit");
 
     println!("Found {} jobs (limited to 5)", limited_jobs.len());
     assert!(limited_jobs.len() <= 5);
 }
+
+#[tokio::test]
+async fn test_cancel_run_job() {
+    let client = get_test_client();
+
+    let agent = client
+        .agents()
+        .create(CreateAgentRequest {
+            name: Some("Test Agent for Job Cancel".to_string()),
+            model: Some("letta/letta-free".to_string()),
+            embedding: Some("letta/letta-free".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to create agent");
+
+    let run = client
+        .messages()
+        .create_async(
+            &agent.id,
+            CreateMessagesRequest {
+                messages: vec![MessageCreate::user("Hello from cancel test!")],
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create async run");
+
+    // The run shows up as a job for this agent
+    let jobs = client
+        .jobs()
+        .list_for_agent(&agent.id, None)
+        .await
+        .expect("Failed to list agent jobs");
+    assert!(jobs
+        .iter()
+        .all(|job| job.agent_id() == Some(agent.id.clone())));
+    assert!(
+        jobs.iter().any(|job| job.id.as_ref() == Some(&run.id)),
+        "The agent's jobs should include the run"
+    );
+
+    let cancelled = client
+        .jobs()
+        .cancel(&run.id)
+        .await
+        .expect("Failed to cancel job");
+    println!("Cancelled job status: {:?}", cancelled.status);
+
+    let _ = client.agents().delete(&agent.id).await;
+}
+
+#[tokio::test]
+async fn test_list_for_agent_scans_all_pages() {
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let agent_id = "agent-00000000-0000-0000-0000-000000000001";
+    let other_id = "agent-00000000-0000-0000-0000-000000000002";
+    let job = |n: u32, agent: &str| {
+        serde_json::json!({
+            "id": format!("job-00000000-0000-0000-0000-{:012}", n),
+            "status": "completed",
+            "metadata": {"agent_id": agent},
+        })
+    };
+
+    let mock_server = MockServer::start().await;
+    // A full first page of another agent's jobs.
+    let first_page: Vec<_> = (1..=100).map(|n| job(n, other_id)).collect();
+    Mock::given(method("GET"))
+        .and(path("/v1/jobs/"))
+        .and(query_param_is_missing("after"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/jobs/"))
+        .and(query_param(
+            "after",
+            "job-00000000-0000-0000-0000-000000000100",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(vec![job(101, agent_id), job(102, other_id)]),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = letta::client::ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let jobs = client
+        .jobs()
+        .list_for_agent(&agent_id.parse().unwrap(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(
+        jobs[0].id.as_ref().unwrap().to_string(),
+        "job-00000000-0000-0000-0000-000000000101"
+    );
+}
@@ -424,3 +424,71 @@ async fn test_multiple_mcp_servers() {
     let final_servers = client.tools().list_mcp_servers().await.unwrap();
     assert_eq!(final_servers.len(), initial_count);
 }
+
+#[tokio::test]
+#[serial]
+async fn test_mcp_api_unreachable_server() {
+    let client = setup_test_client();
+
+    // Nothing listens on this port, so the test fails without an API error
+    let config = SseServerConfig::new("test-unreachable", "http://127.0.0.1:9/sse")
+        .bearer_token("not-a-real-token");
+    let result = client
+        .mcp()
+        .test(config)
+        .await
+        .expect("An unreachable MCP server should not be an error");
+
+    assert!(!result.is_success());
+    assert!(result.tools.is_empty());
+    println!("Test result: {:?}", result.error);
+}
+
+#[tokio::test]
+async fn test_mcp_test_error_mapping() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let cases = [
+        (
+            400,
+            serde_json::json!({"detail": {
+                "code": "MCPServerConnectionError",
+                "message": "Connection refused",
+                "server_name": "test-server"
+            }}),
+            true,
+        ),
+        (401, serde_json::json!({"detail": "Invalid token"}), false),
+        (404, serde_json::json!({"detail": "Not Found"}), false),
+        (
+            422,
+            serde_json::json!({"detail": [{"loc": ["body", "url"], "msg": "field required"}]}),
+            false,
+        ),
+        (500, serde_json::json!({"detail": "Internal error"}), false),
+    ];
+
+    for (status, body, connection_failure) in cases {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/tools/mcp/servers/test"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+        let client = letta::client::ClientBuilder::new()
+            .base_url(&mock_server.uri())
+            .build()
+            .unwrap();
+
+        let config = SseServerConfig::new("test-server", "http://127.0.0.1:9/sse");
+        let result = client.mcp().test(config).await;
+        if connection_failure {
+            let result = result.expect("A connection failure should be a failed result");
+            assert!(!result.is_success());
+            assert_eq!(result.error.as_deref(), Some("Connection refused"));
+        } else {
+            assert!(result.is_err(), "status {} should be an error", status);
+        }
+    }
+}
@@ -414,3 +414,174 @@ async fn test_message_pagination() {
         Err(e) => println!("Warning: Failed to delete test agent: {:?}", e),
     }
 }
+
+#[tokio::test]
+#[serial]
+async fn test_client_tools() {
+    use letta::tool::{ClientToolRegistry, ParamType, ToolDefinition, ToolParam};
+    use letta::types::{LettaMessageUnion, StopReasonType};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let config = ClientConfig::new("http://localhost:8283").unwrap();
+    let client = LettaClient::new(config).unwrap();
+
+    // Register the tool's signature; its body only runs if approval is skipped
+    let tool_request =
+        ToolDefinition::new("read_local_note", "Read a note stored on this machine.")
+            .param(ToolParam::new(
+                "title",
+                ParamType::String,
+                "Title of the note",
+            ))
+            .returns(ParamType::String, "The note's contents")
+            .body("raise NotImplementedError('executed by the client')")
+            .to_create_request()
+            .unwrap();
+    let tool = client.tools().upsert(tool_request).await.unwrap();
+
+    let create_request = CreateAgentRequest::builder()
+        .name("Client Tools Test Agent")
+        .agent_type(AgentType::MemGPT)
+        .model("letta/letta-free")
+        .embedding("letta/letta-free")
+        .memory_block(Block::human("The human's name is User"))
+        .memory_block(Block::persona("I am a helpful assistant"))
+        .build();
+    let agent = client.agents().create(create_request).await.unwrap();
+    let agent_id = &agent.id;
+
+    client
+        .memory()
+        .attach_tool_to_agent(agent_id, tool.id.as_ref().unwrap())
+        .await
+        .unwrap();
+    client
+        .memory()
+        .set_tool_requires_approval(agent_id, "read_local_note", true)
+        .await
+        .unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let tools = ClientToolRegistry::new().register("read_local_note", {
+        let calls = Arc::clone(&calls);
+        move |_args| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, String>("Buy oat milk.".to_string()) }
+        }
+    });
+
+    let response = client
+        .messages()
+        .create_with_client_tools(
+            agent_id,
+            CreateMessagesRequest {
+                messages: vec![MessageCreate::user(
+                    "Use read_local_note to read my note titled 'shopping' and tell me what it says.",
+                )],
+                max_steps: Some(5),
+                ..Default::default()
+            },
+            &tools,
+        )
+        .await
+        .unwrap();
+
+    println!(
+        "Stop reason: {:?}, client tool calls: {}",
+        response.stop_reason.stop_reason,
+        calls.load(Ordering::SeqCst)
+    );
+    let requested = response
+        .messages
+        .iter()
+        .any(|m| matches!(m, LettaMessageUnion::ApprovalRequestMessage(_)));
+    assert!(requested, "The agent should have requested read_local_note");
+    assert!(
+        calls.load(Ordering::SeqCst) > 0,
+        "The client-side handler should have run"
+    );
+    assert_ne!(
+        response.stop_reason.stop_reason,
+        StopReasonType::RequiresApproval
+    );
+
+    client.agents().delete(agent_id).await.unwrap();
+    client
+        .tools()
+        .delete(tool.id.as_ref().unwrap())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_client_tools_leave_unhandled_approvals_pending() {
+    use letta::tool::{pending_tool_calls, ClientToolRegistry};
+    use letta::types::StopReasonType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let agent_id: letta::LettaId = "agent-00000000-0000-0000-0000-000000000001"
+        .parse()
+        .unwrap();
+    let mock_server = MockServer::start().await;
+    // The agent waits on a server tool that needs a person's approval. The
+    // client must not answer it, so exactly one request is sent.
+    Mock::given(method("POST"))
+        .and(path(format!("/v1/agents/{}/messages", agent_id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messages": [{
+                "message_type": "approval_request_message",
+                "id": "message-00000000-0000-0000-0000-000000000002",
+                "date": "2024-01-01T00:00:00Z",
+                "tool_call": {
+                    "name": "send_email",
+                    "arguments": "{\"to\": \"ada@example.com\"}",
+                    "tool_call_id": "call-1"
+                }
+            }],
+            "stop_reason": {"message_type": "stop_reason", "stop_reason": "requires_approval"},
+            "usage": {}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let tools = ClientToolRegistry::new().register("read_local_note", {
+        let calls = Arc::clone(&calls);
+        move |_args| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok::<_, String>("note".to_string()) }
+        }
+    });
+
+    let client = letta::client::ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let response = client
+        .messages()
+        .create_with_client_tools(
+            &agent_id,
+            CreateMessagesRequest {
+                messages: vec![MessageCreate::user("Email Ada")],
+                ..Default::default()
+            },
+            &tools,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.stop_reason.stop_reason,
+        StopReasonType::RequiresApproval
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    let pending = pending_tool_calls(&response.messages, &tools);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].name, "send_email");
+    assert!(!tools.handles_all(&pending));
+}
@@ -16,7 +16,7 @@ async fn test_list_runs() {
     // List runs (might be empty)
     let runs = client
         .runs()
-        .list(&[])
+        .list(None)
         .await
         .expect("this should return something");
     println!("Found {} runs", runs.len());
@@ -74,7 +74,7 @@ async fn test_create_and_get_run() {
     // Try to get messages for the run
     let messages = client
         .runs()
-        .get_messages(&run.id)
+        .get_messages(&run.id, None)
         .await
         .expect("Failed to get run messages");
     println!("Run has {} messages", messages.len());
@@ -82,11 +82,34 @@ async fn test_create_and_get_run() {
     // Try to get steps for the run
     let steps = client
         .runs()
-        .get_steps(&run.id)
+        .get_steps(&run.id, None)
         .await
         .expect("Failed to get run steps");
     println!("Run has {} steps", steps.len());
 
+    // Page through the run's messages one at a time
+    let paged = client
+        .runs()
+        .paginated_messages(
+            &run.id,
+            Some(ListRunMessagesParams {
+                limit: Some(1),
+                ..Default::default()
+            }),
+        )
+        .collect()
+        .await
+        .expect("Failed to page run messages");
+    println!("Paged {} run messages", paged.len());
+
+    // Fetch usage for the run
+    let usage = client
+        .runs()
+        .usage(&run.id)
+        .await
+        .expect("Failed to get run usage");
+    println!("Run usage: {:?}", usage.total_tokens);
+
     // Clean up
     let _ = client.agents().delete(&agent.id).await;
 }
@@ -142,3 +165,128 @@ async fn test_list_active_runs() {
     // Clean up
     let _ = client.agents().delete(&agent.id).await;
 }
+
+#[tokio::test]
+async fn test_list_runs_with_filters() {
+    let client = get_test_client();
+
+    let agent = client
+        .agents()
+        .create(CreateAgentRequest {
+            name: Some("Test Agent for Run Filters".to_string()),
+            model: Some("letta/letta-free".to_string()),
+            embedding: Some("letta/letta-free".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to create agent");
+
+    let run = client
+        .messages()
+        .create_async(
+            &agent.id,
+            CreateMessagesRequest {
+                messages: vec![MessageCreate {
+                    role: MessageRole::User,
+                    content: "Hello from the filter test!".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create async run");
+
+    let runs = client
+        .runs()
+        .paginated(Some(ListRunsParams {
+            agent_ids: Some(vec![agent.id.clone()]),
+            limit: Some(1),
+            ..Default::default()
+        }))
+        .collect()
+        .await
+        .expect("Failed to page runs");
+    assert!(runs.iter().any(|r| r.id == run.id));
+
+    let none = client
+        .runs()
+        .list(Some(ListRunsParams {
+            agent_ids: Some(vec![agent.id.clone()]),
+            statuses: Some(vec![JobStatus::Expired]),
+            ..Default::default()
+        }))
+        .await
+        .expect("Failed to list runs");
+    assert!(none.is_empty());
+
+    let _ = client.agents().delete(&agent.id).await;
+}
+
+#[tokio::test]
+async fn test_paginated_runs_filter_across_pages() {
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let run = |n: u32, status: &str| {
+        serde_json::json!({
+            "id": format!("run-00000000-0000-0000-0000-00000000000{}", n),
+            "status": status,
+        })
+    };
+
+    let mock_server = MockServer::start().await;
+    // Page 1 holds no failed runs; a filtered stream must still fetch page 2.
+    Mock::given(method("GET"))
+        .and(path("/v1/runs"))
+        .and(query_param_is_missing("after"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(vec![run(1, "completed"), run(2, "running")]),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/runs"))
+        .and(query_param(
+            "after",
+            "run-00000000-0000-0000-0000-000000000002",
+        ))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(vec![run(3, "failed"), run(4, "completed")]),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/runs"))
+        .and(query_param(
+            "after",
+            "run-00000000-0000-0000-0000-000000000004",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec![run(5, "failed")]))
+        .mount(&mock_server)
+        .await;
+
+    let client = letta::client::ClientBuilder::new()
+        .base_url(&mock_server.uri())
+        .build()
+        .unwrap();
+    let failed: Vec<Run> = client
+        .runs()
+        .paginated(Some(ListRunsParams {
+            statuses: Some(vec![JobStatus::Failed]),
+            limit: Some(2),
+            ..Default::default()
+        }))
+        .collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<String> = failed.iter().map(|run| run.id.to_string()).collect();
+    assert_eq!(
+        ids,
+        [
+            "run-00000000-0000-0000-0000-000000000003",
+            "run-00000000-0000-0000-0000-000000000005"
+        ]
+    );
+}
@@ -386,6 +386,56 @@ async fn test_agent_sources() -> LettaResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn test_agent_files() -> LettaResult<()> {
+    let client = create_test_client()?;
+
+    let agent = create_test_agent(&client).await?;
+    let source = create_test_source(&client, "agent_files").await?;
+    let source_id = source.id.as_ref().unwrap();
+
+    client
+        .sources()
+        .upload_file(
+            source_id,
+            format!("agent_file_{}.txt", chrono::Utc::now().timestamp()),
+            Bytes::from_static(b"Notes the agent can open and close."),
+            Some("text/plain".to_string()),
+        )
+        .await?;
+    sleep(Duration::from_secs(1)).await;
+
+    let sources_api = client.sources();
+    sources_api
+        .agent_sources(agent.id.clone())
+        .attach(source_id)
+        .await?;
+    let files_api = sources_api.agent_files(agent.id.clone());
+
+    let files = files_api.list(None).await?;
+    assert_eq!(files.len(), 1, "Attached source file should be visible");
+    assert_eq!(&files[0].metadata.source_id, source_id);
+    let file_id = files[0].metadata.id.clone().unwrap();
+
+    let closed = files_api.close_all().await?;
+    println!("Closed files: {:?}", closed);
+    assert!(files_api.list_open().await?.is_empty());
+
+    files_api.open(&file_id).await?;
+    let open = files_api.list_open().await?;
+    assert!(open.iter().any(|f| f.file_id == file_id));
+
+    files_api.close(&file_id).await?;
+    let files = files_api.list(None).await?;
+    assert!(!files[0].is_open);
+
+    client.agents().delete(&agent.id).await?;
+    client.sources().delete(source_id).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn test_source_with_multiple_files() -> LettaResult<()> {
@@ -573,3 +623,49 @@ async fn test_source_metadata() -> LettaResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_list_open_follows_cursor() -> LettaResult<()> {
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000001").unwrap();
+    let attachment = |n: u32| {
+        serde_json::json!({
+            "id": format!("file_agent-00000000-0000-0000-0000-00000000000{}", n),
+            "file_id": format!("file-00000000-0000-0000-0000-00000000000{}", n),
+            "file_name": format!("notes-{}.txt", n),
+            "source_id": "source-00000000-0000-0000-0000-000000000001",
+            "is_open": true,
+        })
+    };
+
+    let mock_server = MockServer::start().await;
+    let files_path = format!("/v1/agents/{}/files", agent_id);
+    Mock::given(method("GET"))
+        .and(path(files_path.as_str()))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "files": [attachment(1), attachment(2)],
+            "next_cursor": "page-2",
+            "has_more": true,
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(files_path.as_str()))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "files": [attachment(3)],
+            "next_cursor": "page-3",
+            "has_more": false,
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ClientBuilder::new().base_url(&mock_server.uri()).build()?;
+    let open = client.sources().agent_files(agent_id).list_open().await?;
+    let names: Vec<&str> = open.iter().map(|f| f.file_name.as_str()).collect();
+    assert_eq!(names, ["notes-1.txt", "notes-2.txt", "notes-3.txt"]);
+    Ok(())
+}
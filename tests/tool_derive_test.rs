@@ -0,0 +1,90 @@
+//! Tests for the `LettaTool` derive macro.
+
+#![cfg(feature = "derive")]
+
+use letta::tool::{LettaTool, ParamType};
+use serde_json::json;
+
+/// Echoes back the input message.
+#[derive(LettaTool)]
+#[letta_tool(returns = "The echoed message")]
+#[allow(dead_code)]
+struct Echo {
+    /// The message to echo
+    message: String,
+}
+
+/// Performs basic arithmetic operations.
+///
+/// Supports addition and multiplication.
+#[derive(LettaTool)]
+#[letta_tool(name = "calculator")]
+#[allow(dead_code)]
+struct CalculatorArgs {
+    /// Operation to perform
+    operation: String,
+    /// Operands to combine
+    operands: Vec<f64>,
+    /// Round the result to this many decimal places
+    #[letta_tool(default = 2)]
+    precision: Option<u8>,
+    /// Whether to show the working
+    #[letta_tool(rename = "verbose")]
+    r#explain: Option<bool>,
+}
+
+/// Returns the current time.
+#[derive(LettaTool)]
+struct CurrentTime;
+
+#[test]
+fn test_derive_schemas() {
+    assert_eq!(Echo::tool_name(), "echo");
+    assert_eq!(
+        Echo::args_json_schema(),
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {"type": "string", "description": "The message to echo"}
+            },
+            "required": ["message"]
+        })
+    );
+    assert_eq!(
+        Echo::json_schema()["description"],
+        "Echoes back the input message."
+    );
+}
+
+#[test]
+fn test_derive_attributes() {
+    let definition = CalculatorArgs::tool_definition();
+    assert_eq!(definition.name, "calculator");
+    assert_eq!(
+        definition.description,
+        "Performs basic arithmetic operations.\n\nSupports addition and multiplication."
+    );
+    assert_eq!(
+        definition.parameters[1].ty,
+        ParamType::array(ParamType::Number)
+    );
+    assert_eq!(definition.parameters[2].default, Some(json!(2)));
+    assert_eq!(definition.parameters[3].name, "verbose");
+
+    let schema = CalculatorArgs::args_json_schema();
+    assert_eq!(schema["required"], json!(["operation", "operands"]));
+    assert_eq!(schema["properties"]["verbose"]["type"], "boolean");
+}
+
+#[test]
+fn test_derive_create_request() {
+    let request = Echo::create_request("return message").unwrap();
+    assert!(request
+        .source_code
+        .starts_with("def echo(message: str) -> str:"));
+    assert_eq!(request.json_schema, Some(Echo::json_schema()));
+
+    let skeleton = CurrentTime::create_request_skeleton().unwrap();
+    assert!(skeleton.source_code.starts_with("def current_time():"));
+    assert!(skeleton.source_code.contains("NotImplementedError"));
+}
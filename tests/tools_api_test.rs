@@ -2,6 +2,7 @@
 
 use letta::client::ClientBuilder;
 use letta::error::LettaResult;
+use letta::tool::{LocalTool, ToolTestCase, ToolTester};
 use letta::types::agent::CreateAgentRequest;
 use letta::types::memory::Block;
 use letta::types::tool::{CreateToolRequest, ListToolsParams, SourceType, Tool, UpdateToolRequest};
@@ -419,6 +420,29 @@ def add_numbers(a: float, b: float) -> float:
     Ok(())
 }
 
+#[tokio::test]
+#[serial]
+async fn test_tool_tester_cases() -> LettaResult<()> {
+    let client = create_test_client()?;
+
+    let tool = LocalTool::from_file("examples/weather_tool.py")?;
+    let cases = ToolTestCase::load("examples/weather_tool_cases.json")?;
+    let results = ToolTester::from_request(&tool.request)
+        .run_cases(&client, &cases)
+        .await;
+
+    for result in &results {
+        assert!(
+            result.passed(),
+            "{}: {:?}",
+            result.case.label(),
+            result.failures
+        );
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 async fn test_upsert_base_tools() {
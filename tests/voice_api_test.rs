@@ -1,9 +1,9 @@
 //! Integration tests for the Voice API (beta).
 
+use futures::StreamExt;
 use letta::client::{ClientConfig, LettaClient};
 use letta::error::LettaResult;
 use letta::types::*;
-use serde_json::json;
 use std::str::FromStr;
 
 /// Get a test client for the local server.
@@ -49,17 +49,10 @@ async fn test_voice_chat_completions() -> LettaResult<()> {
 async fn test_voice_endpoint(client: &LettaClient, agent_id: &LettaId) -> LettaResult<()> {
     println!("Testing voice endpoint with agent: {}", agent_id);
 
-    // Create a test request - since the structure isn't documented,
-    // we'll use a generic JSON object
-    let request = VoiceChatCompletionRequest {
-        data: json!({
-            "message": "Hello from voice test",
-            "voice_settings": {
-                "language": "en",
-                "speed": 1.0
-            }
-        }),
-    };
+    let request = VoiceChatCompletionRequest::builder()
+        .model("gpt-4o-mini")
+        .messages(vec![ChatCompletionMessage::user("Hello from voice test")])
+        .build();
 
     // Test without user_id first
     let response = client
@@ -68,17 +61,28 @@ async fn test_voice_endpoint(client: &LettaClient, agent_id: &LettaId) -> LettaR
         .await?;
     println!(
         "Voice response received without user_id: {:?}",
-        response.data
+        response.content()
     );
 
     // Also test with user_id
     let response = client
         .voice()
-        .create_voice_chat_completions(agent_id, request, Some("test-user-123"))
+        .create_voice_chat_completions(agent_id, request.clone(), Some("test-user-123"))
         .await?;
-    println!("Voice response received: {:?}", response.data);
-    // Since this is a beta endpoint, we can't make strong assertions
-    // about the response structure
+    println!("Voice response received: {:?}", response.content());
+
+    // And the streaming variant
+    let mut stream = client
+        .voice()
+        .create_voice_chat_completions_stream(agent_id, request, Some("test-user-123"))
+        .await?;
+    let mut text = String::new();
+    while let Some(chunk) = stream.next().await {
+        if let Some(content) = chunk?.content() {
+            text.push_str(content);
+        }
+    }
+    println!("Streamed voice response: {}", text);
     Ok(())
 }
 
@@ -88,11 +92,10 @@ async fn test_voice_with_invalid_agent() -> LettaResult<()> {
 
     let fake_agent_id = LettaId::from_str("agent-00000000-0000-0000-0000-000000000000").unwrap();
 
-    let request = VoiceChatCompletionRequest {
-        data: json!({
-            "message": "Test message"
-        }),
-    };
+    let request = VoiceChatCompletionRequest::builder()
+        .model("gpt-4o-mini")
+        .messages(vec![ChatCompletionMessage::user("Test message")])
+        .build();
 
     let result = client
         .voice()